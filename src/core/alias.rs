@@ -0,0 +1,389 @@
+//! Property aliases for XMP
+//!
+//! Some XMP properties are aliases of others: `tiff:Artist` is the first item
+//! of `dc:creator`, `photoshop:Caption` is the default language of
+//! `dc:description`, and so on. Only the base property is stored. This module
+//! keeps the alias registry; [`XmpMeta`](crate::XmpMeta) follows it when
+//! parsing and when accessing properties.
+
+use crate::core::error::{XmpError, XmpResult};
+use crate::core::namespace::ns;
+use crate::core::node::{ArrayNode, ArrayType, Node, StructureNode};
+use crate::core::xpath::{parse_path, PathComponents};
+use crate::types::qualifier::Qualifier;
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+// Global alias registry for thread safety
+static GLOBAL_ALIAS_MAP: OnceLock<RwLock<AliasMap>> = OnceLock::new();
+
+/// How an alias maps onto its base property
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AliasForm {
+    /// The alias is the base property itself
+    Simple,
+    /// The alias is the first item of an ordered array (`rdf:Seq`)
+    Ordered,
+    /// The alias is the first item of an unordered array (`rdf:Bag`)
+    Unordered,
+    /// The alias is the first item of an alternative array (`rdf:Alt`)
+    Alternative,
+    /// The alias is the `x-default` item of a language alternative
+    AltText,
+}
+
+impl AliasForm {
+    /// The array form of the base property, if the alias is an array item
+    pub fn array_type(self) -> Option<ArrayType> {
+        match self {
+            AliasForm::Simple => None,
+            AliasForm::Ordered => Some(ArrayType::Ordered),
+            AliasForm::Unordered => Some(ArrayType::Unordered),
+            AliasForm::Alternative | AliasForm::AltText => Some(ArrayType::Alternative),
+        }
+    }
+}
+
+/// The base property of an alias
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AliasInfo {
+    /// Namespace URI of the base property
+    pub namespace_uri: String,
+    /// Name of the base property
+    pub name: String,
+    /// How the alias maps onto the base property
+    pub form: AliasForm,
+}
+
+impl AliasInfo {
+    /// Path of the aliased node, relative to the base namespace
+    /// (e.g., `creator[1]` or `description[?xml:lang="x-default"]`)
+    pub fn base_path(&self) -> String {
+        match self.form {
+            AliasForm::Simple => self.name.clone(),
+            AliasForm::AltText => format!("{}[?xml:lang=\"x-default\"]", self.name),
+            _ => format!("{}[1]", self.name),
+        }
+    }
+
+    /// Parsed form of [`base_path`](Self::base_path)
+    pub(crate) fn base_components(&self) -> PathComponents {
+        parse_path(&self.base_path()).expect("alias base paths are valid")
+    }
+}
+
+/// Map of alias properties to their base properties
+#[derive(Debug, Clone, Default)]
+pub struct AliasMap {
+    /// Keyed by `namespace_uri:name` of the alias
+    aliases: HashMap<String, AliasInfo>,
+}
+
+impl AliasMap {
+    /// Create a new alias map with the standard XMP aliases registered
+    pub fn new() -> Self {
+        let mut map = Self::default();
+        map.register_standard_aliases();
+        map
+    }
+
+    /// Register an alias
+    ///
+    /// # Arguments
+    ///
+    /// * `alias_ns` - The namespace URI of the alias
+    /// * `alias_name` - The name of the alias
+    /// * `base_ns` - The namespace URI of the base property
+    /// * `base_name` - The name of the base property
+    /// * `form` - How the alias maps onto the base property
+    ///
+    /// # Returns
+    ///
+    /// Returns an error if the alias is already registered to a different
+    /// base, or if the registration would chain aliases.
+    pub fn register(
+        &mut self,
+        alias_ns: &str,
+        alias_name: &str,
+        base_ns: &str,
+        base_name: &str,
+        form: AliasForm,
+    ) -> XmpResult<()> {
+        let alias_key = format!("{}:{}", alias_ns, alias_name);
+        let base_key = format!("{}:{}", base_ns, base_name);
+        if alias_key == base_key {
+            return Err(XmpError::BadParam(format!(
+                "Property '{}' cannot be an alias of itself",
+                alias_key
+            )));
+        }
+
+        let info = AliasInfo {
+            namespace_uri: base_ns.to_string(),
+            name: base_name.to_string(),
+            form,
+        };
+        if let Some(existing) = self.aliases.get(&alias_key) {
+            if *existing == info {
+                return Ok(());
+            }
+            return Err(XmpError::BadParam(format!(
+                "Alias '{}' is already registered to '{}:{}'",
+                alias_key, existing.namespace_uri, existing.name
+            )));
+        }
+        if self.aliases.contains_key(&base_key) {
+            return Err(XmpError::BadParam(format!(
+                "Base property '{}' is itself an alias",
+                base_key
+            )));
+        }
+        if self
+            .aliases
+            .values()
+            .any(|other| other.namespace_uri == alias_ns && other.name == alias_name)
+        {
+            return Err(XmpError::BadParam(format!(
+                "Property '{}' is the base of another alias",
+                alias_key
+            )));
+        }
+
+        self.aliases.insert(alias_key, info);
+        Ok(())
+    }
+
+    /// Get the base property of an alias
+    pub fn resolve(&self, alias_ns: &str, alias_name: &str) -> Option<&AliasInfo> {
+        self.aliases.get(&format!("{}:{}", alias_ns, alias_name))
+    }
+
+    /// Get all registered aliases as a vector of (alias namespace URI, alias name, base) tuples
+    pub fn get_all_aliases(&self) -> Vec<(String, String, AliasInfo)> {
+        self.aliases
+            .iter()
+            .filter_map(|(key, info)| {
+                let pos = key.rfind(':')?;
+                Some((
+                    key[..pos].to_string(),
+                    key[pos + 1..].to_string(),
+                    info.clone(),
+                ))
+            })
+            .collect()
+    }
+
+    /// Register the aliases defined by the XMP specification
+    fn register_standard_aliases(&mut self) {
+        use AliasForm::*;
+        let standard: &[(&str, &str, &str, &str, AliasForm)] = &[
+            // XMP Basic
+            (ns::XMP, "Author", ns::DC, "creator", Ordered),
+            (ns::XMP, "Authors", ns::DC, "creator", Simple),
+            (ns::XMP, "Description", ns::DC, "description", Simple),
+            (ns::XMP, "Format", ns::DC, "format", Simple),
+            (ns::XMP, "Keywords", ns::DC, "subject", Simple),
+            (ns::XMP, "Locale", ns::DC, "language", Simple),
+            (ns::XMP, "Title", ns::DC, "title", Simple),
+            (ns::XMP_RIGHTS, "Copyright", ns::DC, "rights", Simple),
+            // PDF
+            (ns::PDF, "Author", ns::DC, "creator", Ordered),
+            (ns::PDF, "BaseURL", ns::XMP, "BaseURL", Simple),
+            (ns::PDF, "CreationDate", ns::XMP, "CreateDate", Simple),
+            (ns::PDF, "Creator", ns::XMP, "CreatorTool", Simple),
+            (ns::PDF, "ModDate", ns::XMP, "ModifyDate", Simple),
+            (ns::PDF, "Subject", ns::DC, "description", AltText),
+            (ns::PDF, "Title", ns::DC, "title", AltText),
+            // Photoshop
+            (ns::PHOTOSHOP, "Author", ns::DC, "creator", Ordered),
+            (ns::PHOTOSHOP, "Caption", ns::DC, "description", AltText),
+            (ns::PHOTOSHOP, "Copyright", ns::DC, "rights", AltText),
+            (ns::PHOTOSHOP, "Keywords", ns::DC, "subject", Simple),
+            (ns::PHOTOSHOP, "Marked", ns::XMP_RIGHTS, "Marked", Simple),
+            (ns::PHOTOSHOP, "Title", ns::DC, "title", AltText),
+            (
+                ns::PHOTOSHOP,
+                "WebStatement",
+                ns::XMP_RIGHTS,
+                "WebStatement",
+                Simple,
+            ),
+            // TIFF and EXIF
+            (ns::TIFF, "Artist", ns::DC, "creator", Ordered),
+            (ns::TIFF, "Copyright", ns::DC, "rights", AltText),
+            (ns::TIFF, "DateTime", ns::XMP, "ModifyDate", Simple),
+            (ns::TIFF, "ImageDescription", ns::DC, "description", AltText),
+            (ns::TIFF, "Software", ns::XMP, "CreatorTool", Simple),
+            (ns::EXIF, "DateTimeDigitized", ns::XMP, "CreateDate", Simple),
+        ];
+        for &(alias_ns, alias_name, base_ns, base_name, form) in standard {
+            // These should never fail, so we use unwrap
+            self.register(alias_ns, alias_name, base_ns, base_name, form)
+                .unwrap();
+        }
+    }
+}
+
+fn get_global_alias_map() -> &'static RwLock<AliasMap> {
+    GLOBAL_ALIAS_MAP.get_or_init(|| RwLock::new(AliasMap::new()))
+}
+
+/// Register an alias globally
+///
+/// Namespaces are given as URIs. See [`AliasMap::register`].
+///
+/// # Example
+///
+/// ```rust
+/// use xmpkit::core::alias::{register_alias, resolve_alias, AliasForm};
+///
+/// register_alias("http://example.com/old/", "Author", "http://purl.org/dc/elements/1.1/", "creator", AliasForm::Ordered)
+///     .unwrap();
+/// let base = resolve_alias("http://example.com/old/", "Author").unwrap();
+/// assert_eq!(base.base_path(), "creator[1]");
+/// ```
+pub fn register_alias(
+    alias_ns: &str,
+    alias_name: &str,
+    base_ns: &str,
+    base_name: &str,
+    form: AliasForm,
+) -> XmpResult<()> {
+    if alias_ns.is_empty() || alias_name.is_empty() || base_ns.is_empty() || base_name.is_empty() {
+        return Err(XmpError::BadParam(
+            "Alias and base names cannot be empty".to_string(),
+        ));
+    }
+    let map = get_global_alias_map();
+    let mut guard = map.write().expect("Alias registry lock poisoned");
+    guard.register(alias_ns, alias_name, base_ns, base_name, form)
+}
+
+/// Get the base property of an alias from the global registry
+pub fn resolve_alias(alias_ns: &str, alias_name: &str) -> Option<AliasInfo> {
+    let map = get_global_alias_map();
+    let guard = map.read().expect("Alias registry lock poisoned");
+    guard.resolve(alias_ns, alias_name).cloned()
+}
+
+/// Get all registered aliases from the global registry
+///
+/// Returns a vector of (alias namespace URI, alias name, base) tuples.
+pub fn get_all_aliases() -> Vec<(String, String, AliasInfo)> {
+    let map = get_global_alias_map();
+    let guard = map.read().expect("Alias registry lock poisoned");
+    guard.get_all_aliases()
+}
+
+/// Move aliased top-level properties to their base properties
+///
+/// A simple alias value becomes the first (or `x-default`) item of its base
+/// array. An alias that is already an array, as some writers store e.g.
+/// `tiff:ImageDescription`, is taken as the whole base array, in the form
+/// of the base. When the base property already holds the aliased value
+/// (e.g., both `tiff:Artist` and `dc:creator[1]` are present), the base wins
+/// and the alias is dropped.
+pub(crate) fn normalize_aliases(root: &mut StructureNode) {
+    let aliased: Vec<(String, AliasInfo)> = root
+        .fields
+        .keys()
+        .filter_map(|key| {
+            let pos = key.rfind(':')?;
+            Some((key.clone(), resolve_alias(&key[..pos], &key[pos + 1..])?))
+        })
+        .collect();
+
+    for (key, info) in aliased {
+        let Some(mut node) = root.remove_field(&key) else {
+            continue;
+        };
+        let base_key = format!("{}:{}", info.namespace_uri, info.name);
+        let Some(array_type) = info.form.array_type() else {
+            if root.get_field(&base_key).is_none() {
+                root.set_field(base_key, node);
+            }
+            continue;
+        };
+
+        if let Node::Array(mut alias_array) = node {
+            alias_array.array_type = array_type;
+            match root.get_field(&base_key) {
+                Some(Node::Array(array)) if !array.items.is_empty() => {}
+                Some(Node::Array(_)) | None => root.set_field(base_key, Node::Array(alias_array)),
+                Some(_) => {}
+            }
+            continue;
+        }
+
+        if info.form == AliasForm::AltText && node.get_qualifier(ns::XML, "lang").is_none() {
+            node.set_qualifier(Qualifier::new(ns::XML, "lang", "x-default"));
+        }
+        match root.get_field_mut(&base_key) {
+            None => {
+                let mut array = ArrayNode::new(array_type);
+                array.append(node);
+                root.set_field(base_key, Node::Array(array));
+            }
+            Some(Node::Array(array)) if info.form == AliasForm::AltText => {
+                let has_default = array.items.iter().any(|item| {
+                    item.get_qualifier(ns::XML, "lang")
+                        .is_some_and(|q| q.value == "x-default")
+                });
+                if !has_default {
+                    array.items.insert(0, node);
+                }
+            }
+            Some(Node::Array(array)) if array.items.is_empty() => array.append(node),
+            Some(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alias_map_register() {
+        let mut map = AliasMap::new();
+        let info = map.resolve(ns::TIFF, "Artist").unwrap();
+        assert_eq!(info.namespace_uri, ns::DC);
+        assert_eq!(info.base_path(), "creator[1]");
+        assert_eq!(
+            map.resolve(ns::TIFF, "ImageDescription")
+                .unwrap()
+                .base_path(),
+            "description[?xml:lang=\"x-default\"]"
+        );
+
+        // Re-registering the same alias is fine, a different base is not
+        assert!(map
+            .register(ns::TIFF, "Artist", ns::DC, "creator", AliasForm::Ordered)
+            .is_ok());
+        assert!(map
+            .register(
+                ns::TIFF,
+                "Artist",
+                ns::DC,
+                "contributor",
+                AliasForm::Ordered
+            )
+            .is_err());
+        // No chains
+        assert!(map
+            .register(
+                "http://example.com/a/",
+                "A",
+                ns::TIFF,
+                "Artist",
+                AliasForm::Simple
+            )
+            .is_err());
+        assert!(map
+            .register(ns::DC, "creator", ns::DC, "contributor", AliasForm::Simple)
+            .is_err());
+        assert!(map
+            .register(ns::DC, "x", ns::DC, "x", AliasForm::Simple)
+            .is_err());
+    }
+}
@@ -0,0 +1,136 @@
+//! Canonical encoding of the XMP data model
+//!
+//! Two trees that mean the same thing encode to the same bytes. The encoding
+//! ignores the things RDF/XML leaves open:
+//! - the order of items in unordered arrays (`rdf:Bag`)
+//! - the order of structure fields, top-level properties and qualifiers
+//! - namespace prefixes (keys are stored with namespace URIs)
+//! - leading and trailing whitespace around values
+//!
+//! Array forms, URI flags and qualifiers are part of the encoding. Items of
+//! ordered (`rdf:Seq`) and alternative (`rdf:Alt`) arrays keep their order.
+
+use crate::core::node::{ArrayType, Node, StructureNode};
+use crate::types::qualifier::Qualifier;
+
+/// Encode the fields of a structure (or the root of a tree)
+pub(crate) fn encode_fields(structure: &StructureNode, out: &mut Vec<u8>) {
+    let mut fields: Vec<(&String, &Node)> = structure.fields.iter().collect();
+    fields.sort_by(|a, b| a.0.cmp(b.0));
+    write_len(out, fields.len());
+    for (key, node) in fields {
+        write_str(out, key);
+        encode_node(node, out);
+    }
+}
+
+/// Encode a node and everything below it
+pub(crate) fn encode_node(node: &Node, out: &mut Vec<u8>) {
+    match node {
+        Node::Simple(simple) => {
+            out.push(b'S');
+            out.push(simple.is_uri as u8);
+            write_str(out, simple.value.trim());
+        }
+        Node::Array(array) => {
+            out.push(b'A');
+            out.push(match array.array_type {
+                ArrayType::Ordered => 0,
+                ArrayType::Unordered => 1,
+                ArrayType::Alternative => 2,
+            });
+            let mut items: Vec<Vec<u8>> = array
+                .items
+                .iter()
+                .map(|item| {
+                    let mut encoded = Vec::new();
+                    encode_node(item, &mut encoded);
+                    encoded
+                })
+                .collect();
+            if array.array_type == ArrayType::Unordered {
+                items.sort();
+            }
+            write_len(out, items.len());
+            for item in items {
+                write_len(out, item.len());
+                out.extend_from_slice(&item);
+            }
+        }
+        Node::Structure(structure) => {
+            out.push(b'T');
+            encode_fields(structure, out);
+        }
+    }
+    encode_qualifiers(node.qualifiers(), out);
+}
+
+fn encode_qualifiers(qualifiers: &[Qualifier], out: &mut Vec<u8>) {
+    let mut qualifiers: Vec<(&str, &str, &str)> = qualifiers
+        .iter()
+        .map(|q| (q.namespace.as_str(), q.name.as_str(), q.value.trim()))
+        .collect();
+    qualifiers.sort();
+    write_len(out, qualifiers.len());
+    for (namespace, name, value) in qualifiers {
+        write_str(out, namespace);
+        write_str(out, name);
+        write_str(out, value);
+    }
+}
+
+fn write_len(out: &mut Vec<u8>, len: usize) {
+    out.extend_from_slice(&(len as u64).to_le_bytes());
+}
+
+fn write_str(out: &mut Vec<u8>, s: &str) {
+    write_len(out, s.len());
+    out.extend_from_slice(s.as_bytes());
+}
+
+/// 128-bit FNV-1a digest
+///
+/// Unlike `std::hash`, the result is stable across Rust versions and
+/// platforms, so it can be stored.
+pub(crate) fn fnv1a_128(bytes: &[u8]) -> u128 {
+    const OFFSET_BASIS: u128 = 0x6c62272e07bb014262b821756295c58d;
+    const PRIME: u128 = 0x0000000001000000000000000000013B;
+    bytes.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ byte as u128).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fnv1a_128() {
+        // Reference values for FNV-1a 128
+        assert_eq!(fnv1a_128(b""), 0x6c62272e07bb014262b821756295c58d);
+        assert_eq!(fnv1a_128(b"a"), 0xd228cb696f1a8caf78912b704e4a8964);
+    }
+
+    #[test]
+    fn test_bag_order_is_ignored() {
+        let mut bag_a = crate::core::node::ArrayNode::new(ArrayType::Unordered);
+        bag_a.append(Node::simple("x"));
+        bag_a.append(Node::simple("y"));
+        let mut bag_b = crate::core::node::ArrayNode::new(ArrayType::Unordered);
+        bag_b.append(Node::simple("y"));
+        bag_b.append(Node::simple(" x "));
+
+        let encode = |node: &Node| {
+            let mut out = Vec::new();
+            encode_node(node, &mut out);
+            out
+        };
+        let a = Node::Array(bag_a.clone());
+        let b = Node::Array(bag_b.clone());
+        assert_eq!(encode(&a), encode(&b));
+
+        bag_a.array_type = ArrayType::Ordered;
+        bag_b.array_type = ArrayType::Ordered;
+        assert_ne!(encode(&Node::Array(bag_a)), encode(&Node::Array(bag_b)));
+    }
+}
@@ -0,0 +1,307 @@
+//! Character encodings of XMP Packets
+//!
+//! XMP Packets may be encoded in UTF-8, UTF-16 or UTF-32 (XMP Specification
+//! Part 1, 7.3.2). Writers put a byte order mark in the `begin` attribute of
+//! the `<?xpacket?>` header, so the encoding can be told from the byte
+//! pattern of the first characters of the packet.
+
+use std::io::{self, Read};
+
+/// Character encoding of an XMP Packet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PacketEncoding {
+    /// UTF-8
+    Utf8,
+    /// UTF-16, big endian
+    Utf16Be,
+    /// UTF-16, little endian
+    Utf16Le,
+    /// UTF-32, big endian
+    Utf32Be,
+    /// UTF-32, little endian
+    Utf32Le,
+}
+
+impl PacketEncoding {
+    /// Detect the encoding of packet bytes.
+    ///
+    /// Returns the encoding and the length of the leading byte order mark
+    /// (0 if there is none). Without a byte order mark, the encoding is
+    /// inferred from the zero bytes around the first character (e.g. `<` of
+    /// `<?xpacket`), as described in Appendix F of the XML specification.
+    /// Anything else is taken as UTF-8.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use xmpkit::core::encoding::PacketEncoding;
+    ///
+    /// assert_eq!(PacketEncoding::detect(b"\xFE\xFF\x00<"), (PacketEncoding::Utf16Be, 2));
+    /// assert_eq!(PacketEncoding::detect(b"<\x00?\x00"), (PacketEncoding::Utf16Le, 0));
+    /// assert_eq!(PacketEncoding::detect(b"<?xpacket"), (PacketEncoding::Utf8, 0));
+    /// ```
+    pub fn detect(bytes: &[u8]) -> (Self, usize) {
+        match bytes {
+            [0xEF, 0xBB, 0xBF, ..] => (Self::Utf8, 3),
+            [0x00, 0x00, 0xFE, 0xFF, ..] => (Self::Utf32Be, 4),
+            [0xFF, 0xFE, 0x00, 0x00, ..] => (Self::Utf32Le, 4),
+            [0xFE, 0xFF, ..] => (Self::Utf16Be, 2),
+            [0xFF, 0xFE, ..] => (Self::Utf16Le, 2),
+            [0x00, 0x00, 0x00, c, ..] if *c != 0 => (Self::Utf32Be, 0),
+            [c, 0x00, 0x00, 0x00, ..] if *c != 0 => (Self::Utf32Le, 0),
+            [0x00, c, ..] if *c != 0 => (Self::Utf16Be, 0),
+            [c, 0x00, ..] if *c != 0 => (Self::Utf16Le, 0),
+            _ => (Self::Utf8, 0),
+        }
+    }
+
+    /// Size of a code unit in bytes
+    pub fn unit_size(self) -> usize {
+        match self {
+            Self::Utf8 => 1,
+            Self::Utf16Be | Self::Utf16Le => 2,
+            Self::Utf32Be | Self::Utf32Le => 4,
+        }
+    }
+
+    /// Encoding name as used in XML declarations (e.g., `UTF-16BE`)
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Utf8 => "UTF-8",
+            Self::Utf16Be => "UTF-16BE",
+            Self::Utf16Le => "UTF-16LE",
+            Self::Utf32Be => "UTF-32BE",
+            Self::Utf32Le => "UTF-32LE",
+        }
+    }
+}
+
+/// A reader decoding packet bytes in any XMP encoding to UTF-8
+///
+/// The encoding is detected from the first bytes and the byte order mark is
+/// dropped. Text is decoded chunk by chunk, so the packet is never held in
+/// memory as a whole.
+pub(crate) struct PacketReader<R> {
+    inner: R,
+    encoding: PacketEncoding,
+    /// Bytes read from `inner` and not decoded yet
+    input: Vec<u8>,
+    /// Decoded bytes not returned yet, from `output_pos`
+    output: Vec<u8>,
+    output_pos: usize,
+}
+
+impl<R: Read> PacketReader<R> {
+    /// Size of the chunks read from the inner reader
+    const CHUNK_SIZE: usize = 8192;
+
+    /// Detect the encoding from the first bytes of `inner`
+    pub(crate) fn new(mut inner: R) -> io::Result<Self> {
+        let mut head = [0u8; 4];
+        let mut len = 0;
+        while len < head.len() {
+            match inner.read(&mut head[len..]) {
+                Ok(0) => break,
+                Ok(n) => len += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        let (encoding, bom_len) = PacketEncoding::detect(&head[..len]);
+        Ok(Self {
+            inner,
+            encoding,
+            input: head[bom_len..len].to_vec(),
+            output: Vec::new(),
+            output_pos: 0,
+        })
+    }
+
+    /// Read and decode the next chunk, returning `false` at the end
+    fn fill(&mut self) -> io::Result<bool> {
+        self.output.clear();
+        self.output_pos = 0;
+        let mut chunk = vec![0u8; Self::CHUNK_SIZE];
+        while self.output.is_empty() {
+            let n = match self.inner.read(&mut chunk) {
+                Ok(n) => n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            if n == 0 {
+                if self.input.is_empty() {
+                    return Ok(false);
+                }
+                // Leftover bytes from the peeked head
+                self.decode()?;
+                if !self.input.is_empty() {
+                    return Err(invalid_data(format!(
+                        "truncated {} character",
+                        self.encoding.name()
+                    )));
+                }
+                return Ok(!self.output.is_empty());
+            }
+            self.input.extend_from_slice(&chunk[..n]);
+            self.decode()?;
+        }
+        Ok(true)
+    }
+
+    /// Decode the complete characters of `input` into `output`
+    fn decode(&mut self) -> io::Result<()> {
+        let unit_size = self.encoding.unit_size();
+        let mut pos = 0;
+        while pos + unit_size <= self.input.len() {
+            let unit = self.unit_at(pos);
+            let (c, len) = match self.encoding {
+                PacketEncoding::Utf16Be | PacketEncoding::Utf16Le => {
+                    match unit {
+                        0xD800..=0xDBFF => {
+                            if pos + 4 > self.input.len() {
+                                // The low surrogate is in the next chunk
+                                break;
+                            }
+                            let low = self.unit_at(pos + 2);
+                            if !(0xDC00..=0xDFFF).contains(&low) {
+                                return Err(invalid_data("unpaired UTF-16 surrogate"));
+                            }
+                            let code = 0x10000 + ((unit - 0xD800) << 10) + (low - 0xDC00);
+                            (char::from_u32(code), 4)
+                        }
+                        0xDC00..=0xDFFF => return Err(invalid_data("unpaired UTF-16 surrogate")),
+                        _ => (char::from_u32(unit), 2),
+                    }
+                }
+                _ => (char::from_u32(unit), 4),
+            };
+            let c = c.ok_or_else(|| {
+                invalid_data(format!("invalid {} character", self.encoding.name()))
+            })?;
+            let mut utf8 = [0u8; 4];
+            self.output
+                .extend_from_slice(c.encode_utf8(&mut utf8).as_bytes());
+            pos += len;
+        }
+        self.input.drain(..pos);
+        Ok(())
+    }
+
+    /// The code unit at `pos` of `input`
+    fn unit_at(&self, pos: usize) -> u32 {
+        let bytes = &self.input[pos..];
+        match self.encoding {
+            PacketEncoding::Utf8 => bytes[0] as u32,
+            PacketEncoding::Utf16Be => u16::from_be_bytes([bytes[0], bytes[1]]) as u32,
+            PacketEncoding::Utf16Le => u16::from_le_bytes([bytes[0], bytes[1]]) as u32,
+            PacketEncoding::Utf32Be => u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            PacketEncoding::Utf32Le => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        }
+    }
+}
+
+impl<R: Read> Read for PacketReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.encoding == PacketEncoding::Utf8 {
+            // No decoding, only the peeked head to return first
+            if self.input.is_empty() {
+                return self.inner.read(buf);
+            }
+            let n = buf.len().min(self.input.len());
+            buf[..n].copy_from_slice(&self.input[..n]);
+            self.input.drain(..n);
+            return Ok(n);
+        }
+
+        if self.output_pos == self.output.len() && !self.fill()? {
+            return Ok(0);
+        }
+        let n = buf.len().min(self.output.len() - self.output_pos);
+        buf[..n].copy_from_slice(&self.output[self.output_pos..self.output_pos + n]);
+        self.output_pos += n;
+        Ok(n)
+    }
+}
+
+fn invalid_data(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encode text with a byte order mark
+    fn encode(text: &str, encoding: PacketEncoding) -> Vec<u8> {
+        let text = format!("\u{FEFF}{}", text);
+        match encoding {
+            PacketEncoding::Utf8 => text.into_bytes(),
+            PacketEncoding::Utf16Be => text.encode_utf16().flat_map(u16::to_be_bytes).collect(),
+            PacketEncoding::Utf16Le => text.encode_utf16().flat_map(u16::to_le_bytes).collect(),
+            PacketEncoding::Utf32Be => text
+                .chars()
+                .flat_map(|c| (c as u32).to_be_bytes())
+                .collect(),
+            PacketEncoding::Utf32Le => text
+                .chars()
+                .flat_map(|c| (c as u32).to_le_bytes())
+                .collect(),
+        }
+    }
+
+    /// A reader returning one byte at a time, to split characters across reads
+    struct ByteReader<'a>(&'a [u8]);
+
+    impl Read for ByteReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let Some((first, rest)) = self.0.split_first() else {
+                return Ok(0);
+            };
+            buf[0] = *first;
+            self.0 = rest;
+            Ok(1)
+        }
+    }
+
+    #[test]
+    fn test_packet_reader() {
+        let text = "<dc:title>Caf\u{E9} \u{1F600} \u{65E5}\u{672C}</dc:title>";
+        for encoding in [
+            PacketEncoding::Utf8,
+            PacketEncoding::Utf16Be,
+            PacketEncoding::Utf16Le,
+            PacketEncoding::Utf32Be,
+            PacketEncoding::Utf32Le,
+        ] {
+            let bytes = encode(text, encoding);
+            let (detected, bom_len) = PacketEncoding::detect(&bytes);
+            assert_eq!(detected, encoding);
+            // Same encoding without the byte order mark
+            assert_eq!(PacketEncoding::detect(&bytes[bom_len..]).0, encoding);
+
+            let mut reader = PacketReader::new(ByteReader(&bytes)).unwrap();
+            let mut decoded = String::new();
+            reader.read_to_string(&mut decoded).unwrap();
+            assert_eq!(decoded, text);
+        }
+    }
+
+    #[test]
+    fn test_packet_reader_invalid() {
+        // Unpaired high surrogate
+        let bytes = [0x00, b'<', 0xD8, 0x00, 0x00, b'a'];
+        let mut reader = PacketReader::new(&bytes[..]).unwrap();
+        let error = reader.read_to_end(&mut Vec::new()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+        // Truncated character
+        let bytes = [0x00, b'<', 0x00];
+        let mut reader = PacketReader::new(&bytes[..]).unwrap();
+        assert!(reader.read_to_end(&mut Vec::new()).is_err());
+
+        // Out of range code point
+        let bytes = [b'<', 0, 0, 0, 0xFF, 0xFF, 0xFF, 0x7F];
+        let mut reader = PacketReader::new(&bytes[..]).unwrap();
+        assert!(reader.read_to_end(&mut Vec::new()).is_err());
+    }
+}
@@ -25,7 +25,7 @@ pub enum XmpError {
 
     /// Parse error (XML/RDF parsing failed)
     #[error("Parse error: {0}")]
-    ParseError(String),
+    ParseError(ParseErrorInfo),
 
     /// Serialization error
     #[error("Serialization error: {0}")]
@@ -46,11 +46,121 @@ pub enum XmpError {
     /// Operation not supported
     #[error("Operation not supported: {0}")]
     NotSupported(String),
+
+    /// Input rejected for exceeding a size limit, or for a construct that
+    /// is not allowed in untrusted input (e.g., a DOCTYPE declaration)
+    #[error("Limit exceeded: {0}")]
+    LimitExceeded(String),
 }
 
 /// Result type alias for XMP operations
 pub type XmpResult<T> = Result<T, XmpError>;
 
+impl XmpError {
+    /// Location of a parse error in the packet, if known
+    pub fn parse_location(&self) -> Option<&ParseLocation> {
+        match self {
+            XmpError::ParseError(info) => info.location.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Details of a [`XmpError::ParseError`]
+///
+/// Errors found by the XMP parser carry the location of the problem in the
+/// packet. Other parse errors (e.g., malformed file structures) only have a
+/// message.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseErrorInfo {
+    /// Description of the problem
+    pub message: String,
+    /// Where the problem is in the packet
+    pub location: Option<Box<ParseLocation>>,
+}
+
+impl ParseErrorInfo {
+    /// Create error details with a message and no location
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            location: None,
+        }
+    }
+}
+
+impl From<String> for ParseErrorInfo {
+    fn from(message: String) -> Self {
+        Self::new(message)
+    }
+}
+
+impl From<&str> for ParseErrorInfo {
+    fn from(message: &str) -> Self {
+        Self::new(message)
+    }
+}
+
+impl std::fmt::Display for ParseErrorInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)?;
+        if let Some(location) = &self.location {
+            write!(f, " at {}", location)?;
+        }
+        Ok(())
+    }
+}
+
+/// Location of a parse error in the packet text
+///
+/// Offsets, lines and columns refer to the packet as UTF-8 text. For packets
+/// in UTF-16 or UTF-32, this is the decoded text.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseLocation {
+    /// Byte offset from the start of the packet
+    pub offset: u64,
+    /// Line number, starting at 1
+    pub line: u64,
+    /// Column in bytes, starting at 1
+    pub column: u64,
+    /// Qualified names of the enclosing elements, outermost first
+    pub path: Vec<String>,
+    /// Short excerpt of the source at the error (e.g., the start tag of the
+    /// offending element)
+    pub snippet: String,
+}
+
+impl ParseLocation {
+    /// Element path joined with `/` (e.g., `rdf:RDF/rdf:Description/dc:creator/rdf:Seq`)
+    pub fn path_string(&self) -> String {
+        self.path.join("/")
+    }
+}
+
+impl std::fmt::Display for ParseLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)?;
+        if !self.path.is_empty() {
+            write!(f, " in {}", self.path_string())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::ser::Error for XmpError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        XmpError::SerializationError(msg.to_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::de::Error for XmpError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        XmpError::BadValue(msg.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -61,6 +171,29 @@ mod tests {
         assert!(err.to_string().contains("Bad parameter: test"));
     }
 
+    #[test]
+    fn test_parse_error_display() {
+        let err = XmpError::ParseError("no elements".into());
+        assert_eq!(err.to_string(), "Parse error: no elements");
+        assert!(err.parse_location().is_none());
+
+        let err = XmpError::ParseError(ParseErrorInfo {
+            message: "Unknown parseType".to_string(),
+            location: Some(Box::new(ParseLocation {
+                offset: 120,
+                line: 4,
+                column: 9,
+                path: vec!["rdf:RDF".to_string(), "rdf:Description".to_string()],
+                snippet: "<rdf:Description rdf:parseType=\"Other\">".to_string(),
+            })),
+        });
+        assert_eq!(
+            err.to_string(),
+            "Parse error: Unknown parseType at line 4, column 9 in rdf:RDF/rdf:Description"
+        );
+        assert_eq!(err.parse_location().unwrap().offset, 120);
+    }
+
     #[test]
     fn test_io_error_conversion() {
         let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "file not found");
@@ -0,0 +1,461 @@
+//! Deserializer reading XMP values
+
+use crate::core::error::{XmpError, XmpResult};
+use crate::core::namespace::{get_global_namespace_prefix, get_global_namespace_uri, NamespaceMap};
+use crate::core::node::ArrayType;
+use crate::types::value::XmpValue;
+use serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+
+/// A `serde::Deserializer` reading an [`XmpValue`]
+///
+/// Simple values are converted to what the target type asks for, since
+/// parsed XMP only holds text. A simple value read as a sequence is a
+/// one-item sequence, and a language alternative read as a string gives the
+/// default text.
+#[derive(Debug)]
+pub struct Deserializer<'a> {
+    value: XmpValue,
+    context: Context<'a>,
+}
+
+/// How structure field names are resolved
+#[derive(Debug, Clone, Default)]
+struct Context<'a> {
+    /// Namespace of unprefixed field names
+    namespace_uri: Option<String>,
+    /// Prefixes in addition to the global ones
+    namespaces: Option<&'a NamespaceMap>,
+    /// Whether the value holds the top-level properties of a schema
+    schema: bool,
+}
+
+impl Context<'_> {
+    fn uri(&self, prefix: &str) -> Option<String> {
+        self.namespaces
+            .and_then(|namespaces| namespaces.get_uri(prefix).map(String::from))
+            .or_else(|| get_global_namespace_uri(prefix))
+    }
+
+    fn prefix(&self, uri: &str) -> Option<String> {
+        self.namespaces
+            .and_then(|namespaces| namespaces.get_prefix(uri).map(String::from))
+            .or_else(|| get_global_namespace_prefix(uri))
+    }
+
+    /// Split a name into its namespace URI (if known) and local name
+    ///
+    /// Node keys use the namespace URI (`http://...:name`), while Rust field
+    /// names use a prefix (`dc:name`) or nothing.
+    fn split<'n>(&self, name: &'n str) -> (Option<String>, &'n str) {
+        match name.rfind(':') {
+            Some(pos) if name[..pos].contains(':') => {
+                (Some(name[..pos].to_string()), &name[pos + 1..])
+            }
+            Some(pos) => (self.uri(&name[..pos]), &name[pos + 1..]),
+            None => (self.namespace_uri.clone(), name),
+        }
+    }
+
+    /// Whether a structure key names the given Rust field
+    fn matches(&self, key: &str, field: &str) -> bool {
+        if key == field {
+            return true;
+        }
+        let (key_uri, key_local) = self.split(key);
+        let (field_uri, field_local) = self.split(field);
+        key_local == field_local
+            && match (key_uri, field_uri) {
+                (Some(key_uri), Some(field_uri)) => key_uri == field_uri,
+                // Without a namespace to go by, the local name decides
+                (_, None) => true,
+                (None, Some(_)) => false,
+            }
+    }
+
+    /// The context for the value of a structure field
+    ///
+    /// Unprefixed fields of a structure belong to the namespace of the
+    /// top-level property holding it.
+    fn field(&self, key: &str) -> Self {
+        let namespace_uri = match self.schema {
+            true => self.split(key).0.or_else(|| self.namespace_uri.clone()),
+            false => self.namespace_uri.clone(),
+        };
+        Self {
+            namespace_uri,
+            namespaces: self.namespaces,
+            schema: false,
+        }
+    }
+
+    /// The name of a structure key as a map key
+    fn display_key(&self, key: String) -> String {
+        let Some(pos) = key.rfind(':') else {
+            return key;
+        };
+        let uri = &key[..pos];
+        if !uri.contains(':') {
+            return key;
+        }
+        if Some(uri) == self.namespace_uri.as_deref() {
+            return key[pos + 1..].to_string();
+        }
+        match self.prefix(uri) {
+            Some(prefix) => format!("{}:{}", prefix, &key[pos + 1..]),
+            None => key,
+        }
+    }
+}
+
+impl Deserializer<'static> {
+    /// Create a deserializer for a value
+    pub fn new(value: XmpValue) -> Self {
+        Self {
+            value,
+            context: Context::default(),
+        }
+    }
+}
+
+impl<'a> Deserializer<'a> {
+    /// Create a deserializer for the top-level properties of a schema,
+    /// keyed by `namespace_uri:name`
+    ///
+    /// Unprefixed field names resolve to `namespace_uri`, and prefixes are
+    /// looked up in `namespaces` before the global registry.
+    pub(crate) fn for_schema(
+        properties: XmpValue,
+        namespace_uri: &str,
+        namespaces: &'a NamespaceMap,
+    ) -> Self {
+        Self {
+            value: properties,
+            context: Context {
+                namespace_uri: Some(namespace_uri.to_string()),
+                namespaces: Some(namespaces),
+                schema: true,
+            },
+        }
+    }
+
+    fn invalid(&self, expected: &str) -> XmpError {
+        XmpError::BadValue(format!("Expected {}, found {:?}", expected, self.value))
+    }
+
+    /// The value as text, if it is simple
+    fn text(self) -> XmpResult<String> {
+        match self.value {
+            XmpValue::String(s) | XmpValue::Uri(s) => Ok(s),
+            XmpValue::Integer(i) => Ok(i.to_string()),
+            XmpValue::Boolean(b) => Ok(if b { "True" } else { "False" }.to_string()),
+            XmpValue::Real(r) => Ok(r.to_string()),
+            XmpValue::Rational(n, d) => Ok(format!("{}/{}", n, d)),
+            XmpValue::DateTime(dt) => Ok(dt.format()),
+            XmpValue::AltText(items) => {
+                let default = items.iter().position(|(lang, _)| lang == "x-default");
+                match default.or(if items.is_empty() { None } else { Some(0) }) {
+                    Some(index) => Ok(items.into_iter().nth(index).unwrap().1),
+                    None => Err(XmpError::BadValue(
+                        "Expected text, found an empty language alternative".to_string(),
+                    )),
+                }
+            }
+            _ => Err(self.invalid("text")),
+        }
+    }
+
+    fn integer(&self) -> XmpResult<i64> {
+        self.value
+            .as_int()
+            .ok_or_else(|| self.invalid("an integer"))
+    }
+
+    /// The value as sequence items
+    fn items(self) -> Vec<XmpValue> {
+        match self.value {
+            XmpValue::Array(_, items) => items,
+            XmpValue::AltText(items) => items
+                .into_iter()
+                .map(|(lang, text)| {
+                    XmpValue::Array(
+                        ArrayType::Ordered,
+                        vec![XmpValue::String(lang), XmpValue::String(text)],
+                    )
+                })
+                .collect(),
+            value => vec![value],
+        }
+    }
+
+    /// The value as map entries
+    fn entries(self) -> XmpResult<Vec<(String, XmpValue, Context<'a>)>> {
+        match self.value {
+            XmpValue::Structure(fields) => Ok(fields
+                .into_iter()
+                .map(|(key, value)| {
+                    let context = self.context.field(&key);
+                    (self.context.display_key(key), value, context)
+                })
+                .collect()),
+            XmpValue::AltText(items) => Ok(items
+                .into_iter()
+                .map(|(lang, text)| (lang, XmpValue::String(text), self.context.field("")))
+                .collect()),
+            _ => Err(self.invalid("a structure")),
+        }
+    }
+}
+
+macro_rules! deserialize_integer {
+    ($($method:ident => $visit:ident($ty:ty),)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> XmpResult<V::Value> {
+                let value = self.integer()?;
+                let value = <$ty>::try_from(value).map_err(|_| {
+                    XmpError::BadValue(format!(
+                        "{} is out of range for {}",
+                        value,
+                        stringify!($ty)
+                    ))
+                })?;
+                visitor.$visit(value)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'_> {
+    type Error = XmpError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> XmpResult<V::Value> {
+        match self.value {
+            XmpValue::Integer(i) => visitor.visit_i64(i),
+            XmpValue::Boolean(b) => visitor.visit_bool(b),
+            XmpValue::Real(r) => visitor.visit_f64(r),
+            XmpValue::Array(..) => self.deserialize_seq(visitor),
+            XmpValue::AltText(_) | XmpValue::Structure(_) => self.deserialize_map(visitor),
+            _ => visitor.visit_string(self.text()?),
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> XmpResult<V::Value> {
+        let value = self
+            .value
+            .as_bool()
+            .ok_or_else(|| self.invalid("a boolean"))?;
+        visitor.visit_bool(value)
+    }
+
+    deserialize_integer! {
+        deserialize_i8 => visit_i8(i8),
+        deserialize_i16 => visit_i16(i16),
+        deserialize_i32 => visit_i32(i32),
+        deserialize_u8 => visit_u8(u8),
+        deserialize_u16 => visit_u16(u16),
+        deserialize_u32 => visit_u32(u32),
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> XmpResult<V::Value> {
+        visitor.visit_i64(self.integer()?)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> XmpResult<V::Value> {
+        // Values above i64::MAX are stored as text
+        let value = match &self.value {
+            XmpValue::String(s) => s.trim().parse::<u64>().ok(),
+            value => value.as_int().and_then(|i| u64::try_from(i).ok()),
+        };
+        visitor.visit_u64(value.ok_or_else(|| self.invalid("an unsigned integer"))?)
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> XmpResult<V::Value> {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> XmpResult<V::Value> {
+        let value = self
+            .value
+            .as_f64()
+            .ok_or_else(|| self.invalid("a number"))?;
+        visitor.visit_f64(value)
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> XmpResult<V::Value> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> XmpResult<V::Value> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> XmpResult<V::Value> {
+        visitor.visit_string(self.text()?)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, _visitor: V) -> XmpResult<V::Value> {
+        Err(XmpError::NotSupported(
+            "Byte strings cannot be represented in XMP".to_string(),
+        ))
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> XmpResult<V::Value> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> XmpResult<V::Value> {
+        // Absent properties never reach the deserializer
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> XmpResult<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> XmpResult<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> XmpResult<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> XmpResult<V::Value> {
+        if matches!(self.value, XmpValue::Structure(_)) {
+            return Err(self.invalid("an array"));
+        }
+        let context = self.context.field("");
+        visitor.visit_seq(SeqAccess {
+            items: self.items().into_iter(),
+            context,
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V) -> XmpResult<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> XmpResult<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> XmpResult<V::Value> {
+        visitor.visit_map(MapAccess {
+            entries: self.entries()?.into_iter(),
+            value: None,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> XmpResult<V::Value> {
+        if !matches!(self.value, XmpValue::Structure(_)) {
+            return Err(self.invalid("a structure"));
+        }
+        let context = self.context.clone();
+        let entries: Vec<_> = self
+            .entries()?
+            .into_iter()
+            .filter_map(|(key, value, field_context)| {
+                let field = fields.iter().find(|field| context.matches(&key, field))?;
+                Some((field.to_string(), value, field_context))
+            })
+            .collect();
+        visitor.visit_map(MapAccess {
+            entries: entries.into_iter(),
+            value: None,
+        })
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> XmpResult<V::Value> {
+        // Only unit variants have an XMP form
+        let variant: de::value::StringDeserializer<XmpError> = self.text()?.into_deserializer();
+        visitor.visit_enum(variant)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> XmpResult<V::Value> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> XmpResult<V::Value> {
+        visitor.visit_unit()
+    }
+}
+
+struct SeqAccess<'a> {
+    items: std::vec::IntoIter<XmpValue>,
+    context: Context<'a>,
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess<'_> {
+    type Error = XmpError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> XmpResult<Option<T::Value>> {
+        self.items
+            .next()
+            .map(|value| {
+                seed.deserialize(Deserializer {
+                    value,
+                    context: self.context.clone(),
+                })
+            })
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.items.len())
+    }
+}
+
+struct MapAccess<'a> {
+    entries: std::vec::IntoIter<(String, XmpValue, Context<'a>)>,
+    value: Option<(XmpValue, Context<'a>)>,
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess<'_> {
+    type Error = XmpError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> XmpResult<Option<K::Value>> {
+        let Some((key, value, context)) = self.entries.next() else {
+            return Ok(None);
+        };
+        self.value = Some((value, context));
+        let key: de::value::StringDeserializer<XmpError> = key.into_deserializer();
+        seed.deserialize(key).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> XmpResult<V::Value> {
+        let (value, context) = self
+            .value
+            .take()
+            .ok_or_else(|| XmpError::InternalError("Map value without a key".to_string()))?;
+        seed.deserialize(Deserializer { value, context })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
@@ -0,0 +1,217 @@
+//! Mapping between Rust types and XMP values
+//!
+//! This module provides a [`Serializer`] that turns any `serde::Serialize`
+//! type into an [`XmpValue`], and a [`Deserializer`] that reads one back.
+//! [`XmpMeta::write_schema`] and [`XmpMeta::read_schema`] use them to map a
+//! whole struct to the properties of a schema.
+//!
+//! | Rust | XMP |
+//! |------|-----|
+//! | `bool`, integers, floats, strings | simple values |
+//! | `Option<T>` | `T`, or nothing for `None` |
+//! | `Vec<T>`, tuples | ordered array (`rdf:Seq`) |
+//! | [`Bag<T>`] / [`Alt<T>`] | unordered (`rdf:Bag`) / alternative (`rdf:Alt`) array |
+//! | [`LangAlt`] | language alternative |
+//! | structs, maps | structures |
+//! | unit enum variants | the variant name |
+//!
+//! Field names without a prefix belong to the namespace of the schema (or
+//! of the top-level property, for nested structures). Other namespaces are
+//! reached with `#[serde(rename = "prefix:Name")]`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//! use xmpkit::core::mapping::Bag;
+//! use xmpkit::XmpMeta;
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! struct Photo {
+//!     #[serde(rename = "Rating")]
+//!     rating: Option<i64>,
+//!     #[serde(rename = "Label")]
+//!     label: String,
+//!     #[serde(rename = "dc:subject")]
+//!     subjects: Bag<String>,
+//! }
+//!
+//! let photo = Photo {
+//!     rating: Some(4),
+//!     label: "Red".to_string(),
+//!     subjects: Bag(vec!["sunset".to_string()]),
+//! };
+//!
+//! let mut meta = XmpMeta::new();
+//! meta.write_schema("xmp", &photo).unwrap();
+//! assert!(meta.has_property("dc", "subject[1]"));
+//!
+//! let read: Photo = meta.read_schema("xmp").unwrap();
+//! assert_eq!(read, photo);
+//! ```
+//!
+//! [`XmpMeta::write_schema`]: crate::XmpMeta::write_schema
+//! [`XmpMeta::read_schema`]: crate::XmpMeta::read_schema
+
+mod de;
+mod ser;
+
+pub use de::Deserializer;
+pub(crate) use ser::FieldsSerializer;
+pub use ser::Serializer;
+
+use crate::core::error::{XmpError, XmpResult};
+use crate::types::value::XmpValue;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// Newtype name the [`Serializer`] recognizes for [`Bag`]
+const BAG: &str = "$xmpkit::Bag";
+/// Newtype name the [`Serializer`] recognizes for [`Alt`]
+const ALT: &str = "$xmpkit::Alt";
+/// Newtype name the [`Serializer`] recognizes for [`LangAlt`]
+const LANG_ALT: &str = "$xmpkit::LangAlt";
+
+/// Items stored as an unordered array (`rdf:Bag`)
+///
+/// Other serializers see a plain sequence.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Bag<T>(pub Vec<T>);
+
+/// Items stored as an alternative array (`rdf:Alt`)
+///
+/// Other serializers see a plain sequence.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Alt<T>(pub Vec<T>);
+
+/// Language alternative, as `(language, text)` pairs
+///
+/// Other serializers see a sequence of pairs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LangAlt(pub Vec<(String, String)>);
+
+impl<T: Serialize> Serialize for Bag<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(BAG, &self.0)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Bag<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(Bag)
+    }
+}
+
+impl<T: Serialize> Serialize for Alt<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(ALT, &self.0)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Alt<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(Alt)
+    }
+}
+
+impl Serialize for LangAlt {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(LANG_ALT, &self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for LangAlt {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(LangAlt)
+    }
+}
+
+/// Convert a Rust value to an [`XmpValue`].
+///
+/// Fails for values with no XMP representation, such as `None`, `()` or
+/// byte strings.
+pub fn to_value<T: Serialize + ?Sized>(value: &T) -> XmpResult<XmpValue> {
+    value
+        .serialize(Serializer)?
+        .ok_or_else(|| XmpError::BadValue("Value has no XMP representation".to_string()))
+}
+
+/// Convert an [`XmpValue`] to a Rust value.
+///
+/// Simple values are converted as needed, so `"42"` reads as an integer and
+/// `"True"` as a boolean. Structure fields are matched by their full name
+/// or their local name.
+pub fn from_value<T: DeserializeOwned>(value: XmpValue) -> XmpResult<T> {
+    T::deserialize(Deserializer::new(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::node::ArrayType;
+    use serde::{Deserialize, Serialize};
+    use std::collections::HashMap;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Kind {
+        Photo,
+        Video,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Item {
+        name: String,
+        count: u32,
+        kind: Kind,
+        tags: Bag<String>,
+        title: LangAlt,
+        ratio: Option<f64>,
+    }
+
+    #[test]
+    fn test_value_round_trip() {
+        let item = Item {
+            name: "a".to_string(),
+            count: 3,
+            kind: Kind::Video,
+            tags: Bag(vec!["x".to_string(), "y".to_string()]),
+            title: LangAlt(vec![("x-default".to_string(), "A".to_string())]),
+            ratio: None,
+        };
+        let value = to_value(&item).unwrap();
+        let XmpValue::Structure(fields) = &value else {
+            panic!("expected a structure, got {:?}", value);
+        };
+        assert_eq!(fields.len(), 5);
+        assert_eq!(fields["count"], XmpValue::Integer(3));
+        assert_eq!(fields["kind"], XmpValue::String("Video".to_string()));
+        assert_eq!(fields["tags"].array_type(), Some(ArrayType::Unordered));
+        assert!(matches!(fields["title"], XmpValue::AltText(_)));
+
+        assert_eq!(from_value::<Item>(value).unwrap(), item);
+        assert!(to_value(&None::<i32>).is_err());
+    }
+
+    #[test]
+    fn test_from_parsed_strings() {
+        // Parsed values are all text
+        let mut fields = HashMap::new();
+        fields.insert("name".to_string(), XmpValue::String("b".to_string()));
+        fields.insert("count".to_string(), XmpValue::String("7".to_string()));
+        fields.insert("kind".to_string(), XmpValue::String("Photo".to_string()));
+        fields.insert("tags".to_string(), XmpValue::String("solo".to_string()));
+        fields.insert(
+            "title".to_string(),
+            XmpValue::AltText(vec![("en".to_string(), "B".to_string())]),
+        );
+        fields.insert("ratio".to_string(), XmpValue::String("0.5".to_string()));
+
+        let item: Item = from_value(XmpValue::Structure(fields)).unwrap();
+        assert_eq!(item.count, 7);
+        assert_eq!(item.tags, Bag(vec!["solo".to_string()]));
+        assert_eq!(item.ratio, Some(0.5));
+
+        assert!(from_value::<u8>(XmpValue::String("300".to_string())).is_err());
+        assert!(from_value::<bool>(XmpValue::String("True".to_string())).unwrap());
+    }
+}
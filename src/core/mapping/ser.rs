@@ -0,0 +1,563 @@
+//! Serializer producing XMP values
+
+use super::{ALT, BAG, LANG_ALT};
+use crate::core::error::{XmpError, XmpResult};
+use crate::core::node::ArrayType;
+use crate::types::value::XmpValue;
+use serde::ser::{self, Impossible, Serialize};
+use std::collections::HashMap;
+
+/// A `serde::Serializer` producing [`XmpValue`]s
+///
+/// The output is `None` for values that have no XMP representation
+/// (`Option::None` and `()`); structure fields holding them are left out.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Serializer;
+
+fn unsupported<T>(what: &str) -> XmpResult<T> {
+    Err(XmpError::NotSupported(format!(
+        "{} cannot be represented in XMP",
+        what
+    )))
+}
+
+/// Serialize a value that must be present (array items and map keys)
+fn required<T: Serialize + ?Sized>(value: &T) -> XmpResult<XmpValue> {
+    value
+        .serialize(Serializer)?
+        .ok_or_else(|| XmpError::BadValue("Array items and keys cannot be empty".to_string()))
+}
+
+impl ser::Serializer for Serializer {
+    type Ok = Option<XmpValue>;
+    type Error = XmpError;
+
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = Impossible<Self::Ok, XmpError>;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = MapSerializer;
+    type SerializeStructVariant = Impossible<Self::Ok, XmpError>;
+
+    fn serialize_bool(self, v: bool) -> XmpResult<Self::Ok> {
+        Ok(Some(XmpValue::Boolean(v)))
+    }
+
+    fn serialize_i8(self, v: i8) -> XmpResult<Self::Ok> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> XmpResult<Self::Ok> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> XmpResult<Self::Ok> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i64(self, v: i64) -> XmpResult<Self::Ok> {
+        Ok(Some(XmpValue::Integer(v)))
+    }
+
+    fn serialize_u8(self, v: u8) -> XmpResult<Self::Ok> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_u16(self, v: u16) -> XmpResult<Self::Ok> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> XmpResult<Self::Ok> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> XmpResult<Self::Ok> {
+        match i64::try_from(v) {
+            Ok(v) => self.serialize_i64(v),
+            // Keep the digits rather than failing
+            Err(_) => self.serialize_str(&v.to_string()),
+        }
+    }
+
+    fn serialize_f32(self, v: f32) -> XmpResult<Self::Ok> {
+        self.serialize_f64(v.into())
+    }
+
+    fn serialize_f64(self, v: f64) -> XmpResult<Self::Ok> {
+        Ok(Some(XmpValue::Real(v)))
+    }
+
+    fn serialize_char(self, v: char) -> XmpResult<Self::Ok> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> XmpResult<Self::Ok> {
+        Ok(Some(XmpValue::String(v.to_string())))
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> XmpResult<Self::Ok> {
+        unsupported("Byte strings")
+    }
+
+    fn serialize_none(self) -> XmpResult<Self::Ok> {
+        Ok(None)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> XmpResult<Self::Ok> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> XmpResult<Self::Ok> {
+        Ok(None)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> XmpResult<Self::Ok> {
+        Ok(None)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> XmpResult<Self::Ok> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> XmpResult<Self::Ok> {
+        let value = value.serialize(self)?;
+        let array_type = match name {
+            BAG => ArrayType::Unordered,
+            ALT => ArrayType::Alternative,
+            LANG_ALT => return lang_alt(value).map(Some),
+            _ => return Ok(value),
+        };
+        match value {
+            Some(XmpValue::Array(_, items)) => Ok(Some(XmpValue::Array(array_type, items))),
+            _ => Err(XmpError::BadValue(format!(
+                "Expected a sequence for {}",
+                name
+            ))),
+        }
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> XmpResult<Self::Ok> {
+        unsupported("Enum variants with data")
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> XmpResult<Self::SerializeSeq> {
+        Ok(SeqSerializer {
+            items: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> XmpResult<Self::SerializeTuple> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> XmpResult<Self::SerializeTupleStruct> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> XmpResult<Self::SerializeTupleVariant> {
+        unsupported("Enum variants with data")
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> XmpResult<Self::SerializeMap> {
+        Ok(MapSerializer::default())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> XmpResult<Self::SerializeStruct> {
+        Ok(MapSerializer::default())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> XmpResult<Self::SerializeStructVariant> {
+        unsupported("Enum variants with data")
+    }
+}
+
+/// Turn a serialized `Vec<(String, String)>` into a language alternative
+fn lang_alt(value: Option<XmpValue>) -> XmpResult<XmpValue> {
+    let Some(XmpValue::Array(_, items)) = value else {
+        return Err(XmpError::BadValue(
+            "Expected (language, text) pairs".to_string(),
+        ));
+    };
+    items
+        .into_iter()
+        .map(|item| match item {
+            XmpValue::Array(_, pair) => match <[XmpValue; 2]>::try_from(pair) {
+                Ok([XmpValue::String(lang), XmpValue::String(text)]) => Ok((lang, text)),
+                _ => Err(XmpError::BadValue(
+                    "Expected (language, text) pairs".to_string(),
+                )),
+            },
+            _ => Err(XmpError::BadValue(
+                "Expected (language, text) pairs".to_string(),
+            )),
+        })
+        .collect::<XmpResult<_>>()
+        .map(XmpValue::AltText)
+}
+
+/// Collects sequence items into an ordered array
+pub struct SeqSerializer {
+    items: Vec<XmpValue>,
+}
+
+impl ser::SerializeSeq for SeqSerializer {
+    type Ok = Option<XmpValue>;
+    type Error = XmpError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> XmpResult<()> {
+        self.items.push(required(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> XmpResult<Self::Ok> {
+        Ok(Some(XmpValue::Array(ArrayType::Ordered, self.items)))
+    }
+}
+
+impl ser::SerializeTuple for SeqSerializer {
+    type Ok = Option<XmpValue>;
+    type Error = XmpError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> XmpResult<()> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> XmpResult<Self::Ok> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqSerializer {
+    type Ok = Option<XmpValue>;
+    type Error = XmpError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> XmpResult<()> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> XmpResult<Self::Ok> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+/// Serialize a map key, which must be a string or an integer
+fn map_key<T: Serialize + ?Sized>(key: &T) -> XmpResult<String> {
+    match required(key)? {
+        XmpValue::String(key) => Ok(key),
+        XmpValue::Integer(key) => Ok(key.to_string()),
+        _ => unsupported("Map keys other than strings"),
+    }
+}
+
+/// Collects struct fields and map entries into a structure
+#[derive(Default)]
+pub struct MapSerializer {
+    fields: HashMap<String, XmpValue>,
+    key: Option<String>,
+}
+
+impl MapSerializer {
+    fn insert<T: Serialize + ?Sized>(&mut self, key: String, value: &T) -> XmpResult<()> {
+        if let Some(value) = value.serialize(Serializer)? {
+            self.fields.insert(key, value);
+        }
+        Ok(())
+    }
+}
+
+impl ser::SerializeMap for MapSerializer {
+    type Ok = Option<XmpValue>;
+    type Error = XmpError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> XmpResult<()> {
+        self.key = Some(map_key(key)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> XmpResult<()> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| XmpError::InternalError("Map value without a key".to_string()))?;
+        self.insert(key, value)
+    }
+
+    fn end(self) -> XmpResult<Self::Ok> {
+        Ok(Some(XmpValue::Structure(self.fields)))
+    }
+}
+
+impl ser::SerializeStruct for MapSerializer {
+    type Ok = Option<XmpValue>;
+    type Error = XmpError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> XmpResult<()> {
+        self.insert(key.to_string(), value)
+    }
+
+    fn end(self) -> XmpResult<Self::Ok> {
+        ser::SerializeMap::end(self)
+    }
+}
+
+/// A `serde::Serializer` for the top level of a schema
+///
+/// Only accepts structs and maps. Unlike [`Serializer`], it keeps the names
+/// of fields with no XMP representation, with `None` as their value, so
+/// they can be deleted.
+pub(crate) struct FieldsSerializer;
+
+fn not_fields<T>() -> XmpResult<T> {
+    Err(XmpError::BadParam(
+        "Only structs and maps can be written as a schema".to_string(),
+    ))
+}
+
+impl ser::Serializer for FieldsSerializer {
+    type Ok = HashMap<String, Option<XmpValue>>;
+    type Error = XmpError;
+
+    type SerializeSeq = Impossible<Self::Ok, XmpError>;
+    type SerializeTuple = Impossible<Self::Ok, XmpError>;
+    type SerializeTupleStruct = Impossible<Self::Ok, XmpError>;
+    type SerializeTupleVariant = Impossible<Self::Ok, XmpError>;
+    type SerializeMap = FieldsCollector;
+    type SerializeStruct = FieldsCollector;
+    type SerializeStructVariant = Impossible<Self::Ok, XmpError>;
+
+    fn serialize_bool(self, _v: bool) -> XmpResult<Self::Ok> {
+        not_fields()
+    }
+
+    fn serialize_i8(self, _v: i8) -> XmpResult<Self::Ok> {
+        not_fields()
+    }
+
+    fn serialize_i16(self, _v: i16) -> XmpResult<Self::Ok> {
+        not_fields()
+    }
+
+    fn serialize_i32(self, _v: i32) -> XmpResult<Self::Ok> {
+        not_fields()
+    }
+
+    fn serialize_i64(self, _v: i64) -> XmpResult<Self::Ok> {
+        not_fields()
+    }
+
+    fn serialize_u8(self, _v: u8) -> XmpResult<Self::Ok> {
+        not_fields()
+    }
+
+    fn serialize_u16(self, _v: u16) -> XmpResult<Self::Ok> {
+        not_fields()
+    }
+
+    fn serialize_u32(self, _v: u32) -> XmpResult<Self::Ok> {
+        not_fields()
+    }
+
+    fn serialize_u64(self, _v: u64) -> XmpResult<Self::Ok> {
+        not_fields()
+    }
+
+    fn serialize_f32(self, _v: f32) -> XmpResult<Self::Ok> {
+        not_fields()
+    }
+
+    fn serialize_f64(self, _v: f64) -> XmpResult<Self::Ok> {
+        not_fields()
+    }
+
+    fn serialize_char(self, _v: char) -> XmpResult<Self::Ok> {
+        not_fields()
+    }
+
+    fn serialize_str(self, _v: &str) -> XmpResult<Self::Ok> {
+        not_fields()
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> XmpResult<Self::Ok> {
+        not_fields()
+    }
+
+    fn serialize_none(self) -> XmpResult<Self::Ok> {
+        not_fields()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> XmpResult<Self::Ok> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> XmpResult<Self::Ok> {
+        not_fields()
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> XmpResult<Self::Ok> {
+        not_fields()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> XmpResult<Self::Ok> {
+        not_fields()
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> XmpResult<Self::Ok> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> XmpResult<Self::Ok> {
+        not_fields()
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> XmpResult<Self::SerializeSeq> {
+        not_fields()
+    }
+
+    fn serialize_tuple(self, _len: usize) -> XmpResult<Self::SerializeTuple> {
+        not_fields()
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> XmpResult<Self::SerializeTupleStruct> {
+        not_fields()
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> XmpResult<Self::SerializeTupleVariant> {
+        not_fields()
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> XmpResult<Self::SerializeMap> {
+        Ok(FieldsCollector::default())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> XmpResult<Self::SerializeStruct> {
+        Ok(FieldsCollector::default())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> XmpResult<Self::SerializeStructVariant> {
+        not_fields()
+    }
+}
+
+/// Collects the top-level fields for [`FieldsSerializer`]
+#[derive(Default)]
+pub(crate) struct FieldsCollector {
+    fields: HashMap<String, Option<XmpValue>>,
+    key: Option<String>,
+}
+
+impl ser::SerializeMap for FieldsCollector {
+    type Ok = HashMap<String, Option<XmpValue>>;
+    type Error = XmpError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> XmpResult<()> {
+        self.key = Some(map_key(key)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> XmpResult<()> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| XmpError::InternalError("Map value without a key".to_string()))?;
+        self.fields.insert(key, value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> XmpResult<Self::Ok> {
+        Ok(self.fields)
+    }
+}
+
+impl ser::SerializeStruct for FieldsCollector {
+    type Ok = HashMap<String, Option<XmpValue>>;
+    type Error = XmpError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> XmpResult<()> {
+        self.fields
+            .insert(key.to_string(), value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> XmpResult<Self::Ok> {
+        Ok(self.fields)
+    }
+}
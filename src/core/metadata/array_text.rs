@@ -0,0 +1,432 @@
+//! Conversion between arrays and delimited text
+//!
+//! This module provides [`XmpMeta::catenate_array_items`] and
+//! [`XmpMeta::separate_array_items`], the equivalents of the Adobe SDK's
+//! `XMPUtils::CatenateArrayItems` and `XMPUtils::SeparateArrayItems`. They
+//! turn an array such as `dc:subject` into text like `sunset; "beach, sea"`
+//! for editing, and back.
+
+use super::XmpMeta;
+use crate::core::error::{XmpError, XmpResult};
+use crate::core::node::{ArrayNode, ArrayType, Node};
+use crate::core::xpath::parse_path;
+
+/// Separator used when none is given
+const DEFAULT_SEPARATOR: &str = "; ";
+
+/// Quote used when none is given
+const DEFAULT_QUOTE: char = '"';
+
+/// Quote pairs recognized when separating text
+const QUOTE_PAIRS: &[(char, char)] = &[
+    ('"', '"'),
+    ('\'', '\''),
+    ('\u{201C}', '\u{201D}'), // “ ”
+    ('\u{2018}', '\u{2019}'), // ‘ ’
+    ('\u{00AB}', '\u{00BB}'), // « »
+    ('\u{300C}', '\u{300D}'), // 「 」
+];
+
+/// Options for [`XmpMeta::separate_array_items`].
+///
+/// Use the builder pattern to configure options.
+///
+/// # Example
+///
+/// ```rust
+/// use xmpkit::core::metadata::SeparateOptions;
+///
+/// // Add keywords to the existing ones, skipping duplicates
+/// let options = SeparateOptions::default().preserve_existing().unique();
+/// ```
+#[derive(Default, Clone, Copy, Debug)]
+pub struct SeparateOptions {
+    /// Form of the array if it has to be created (default: unordered)
+    pub array_type: Option<ArrayType>,
+    /// Commas are part of the items; only semicolons separate them
+    pub allow_commas: bool,
+    /// Keep the existing items and append the new ones
+    pub preserve_existing: bool,
+    /// Skip items whose value is already in the array
+    pub unique: bool,
+}
+
+impl SeparateOptions {
+    /// Create the array as an ordered array (`rdf:Seq`) if it does not exist.
+    pub fn ordered(mut self) -> Self {
+        self.array_type = Some(ArrayType::Ordered);
+        self
+    }
+
+    /// Create the array as an alternative array (`rdf:Alt`) if it does not exist.
+    pub fn alternative(mut self) -> Self {
+        self.array_type = Some(ArrayType::Alternative);
+        self
+    }
+
+    /// Treat commas as part of the items; only semicolons separate them.
+    pub fn allow_commas(mut self) -> Self {
+        self.allow_commas = true;
+        self
+    }
+
+    /// Keep the existing items and append the new ones.
+    ///
+    /// By default the array is replaced by the items of the text. Existing
+    /// items whose value reappears keep their qualifiers either way.
+    pub fn preserve_existing(mut self) -> Self {
+        self.preserve_existing = true;
+        self
+    }
+
+    /// Skip items whose value is already in the array.
+    pub fn unique(mut self) -> Self {
+        self.unique = true;
+        self
+    }
+}
+
+impl XmpMeta {
+    /// Join the items of an array into a single string.
+    ///
+    /// Items containing separators, quotes or surrounding whitespace are
+    /// quoted, so [`separate_array_items`](Self::separate_array_items) gives
+    /// the same items back. A missing array gives an empty string.
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - The namespace URI or prefix of the array
+    /// * `path` - The array path
+    /// * `separator` - The separator between items: one semicolon, optionally
+    ///   surrounded by spaces (empty for `"; "`)
+    /// * `quotes` - The opening and closing quote, or a single character used
+    ///   for both (empty for `"`)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use xmpkit::XmpMeta;
+    ///
+    /// let mut meta = XmpMeta::new();
+    /// meta.append_array_item("dc", "subject", "sunset".into()).unwrap();
+    /// meta.append_array_item("dc", "subject", "beach, sea".into()).unwrap();
+    ///
+    /// let text = meta.catenate_array_items("dc", "subject", "; ", "\"").unwrap();
+    /// assert_eq!(text, "sunset; \"beach, sea\"");
+    /// ```
+    pub fn catenate_array_items(
+        &self,
+        namespace: &str,
+        path: &str,
+        separator: &str,
+        quotes: &str,
+    ) -> XmpResult<String> {
+        let separator = if separator.is_empty() {
+            DEFAULT_SEPARATOR
+        } else {
+            separator
+        };
+        if separator.matches(';').count() != 1 || separator.chars().any(|c| c != ';' && c != ' ') {
+            return Err(XmpError::BadParam(
+                "Separator must be one semicolon, optionally surrounded by spaces".to_string(),
+            ));
+        }
+        let mut quote_chars = quotes.chars();
+        let (open, close) = match (quote_chars.next(), quote_chars.next(), quote_chars.next()) {
+            (None, _, _) => (DEFAULT_QUOTE, DEFAULT_QUOTE),
+            (Some(q), None, _) => (q, q),
+            (Some(open), Some(close), None) => (open, close),
+            _ => {
+                return Err(XmpError::BadParam(
+                    "Quotes must be one or two characters".to_string(),
+                ))
+            }
+        };
+
+        let Some(array) = self.array_node(namespace, path)? else {
+            return Ok(String::new());
+        };
+        let mut items = Vec::with_capacity(array.items.len());
+        for item in &array.items {
+            let value = &item
+                .as_simple()
+                .ok_or_else(|| XmpError::BadParam("Array items must be simple".to_string()))?
+                .value;
+            items.push(quote_item(value, open, close));
+        }
+        Ok(items.join(separator))
+    }
+
+    /// Split a string into items and store them in an array.
+    ///
+    /// Items are separated by semicolons, commas (unless
+    /// [`allow_commas`](SeparateOptions::allow_commas) is set) and line breaks.
+    /// Whitespace around items is dropped and empty items are skipped. An
+    /// item may be quoted with `"`, `'` or typographic quotes; inside quotes,
+    /// whitespace is kept and a doubled closing quote stands for the quote
+    /// itself.
+    ///
+    /// An existing array keeps its form; otherwise one is created as
+    /// described by [`SeparateOptions`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use xmpkit::core::metadata::SeparateOptions;
+    /// use xmpkit::XmpMeta;
+    ///
+    /// let mut meta = XmpMeta::new();
+    /// meta.separate_array_items("dc", "subject", "sunset; beach, holiday", SeparateOptions::default())
+    ///     .unwrap();
+    /// assert_eq!(meta.get_array_size("dc", "subject"), Some(3));
+    /// ```
+    pub fn separate_array_items(
+        &mut self,
+        namespace: &str,
+        path: &str,
+        text: &str,
+        options: SeparateOptions,
+    ) -> XmpResult<()> {
+        let ns_uri = self.resolve_namespace_uri_or_error(namespace)?;
+        let parsed = parse_path(path)?;
+        let existing = self.array_node(namespace, path)?;
+        let array_type = existing
+            .as_ref()
+            .map(|array| array.array_type)
+            .or(options.array_type)
+            .unwrap_or(ArrayType::Unordered);
+
+        let mut old_items = existing.map(|array| array.items).unwrap_or_default();
+        let mut array = ArrayNode::new(array_type);
+        if options.preserve_existing {
+            array.items = std::mem::take(&mut old_items);
+        }
+
+        for value in split_items(text, options.allow_commas) {
+            if options.unique
+                && array
+                    .items
+                    .iter()
+                    .any(|item| simple_value(item) == Some(&value))
+            {
+                continue;
+            }
+            // Reuse a replaced item with the same value to keep its qualifiers
+            let item = match old_items
+                .iter()
+                .position(|item| simple_value(item) == Some(&value))
+            {
+                Some(pos) => old_items.remove(pos),
+                None => Node::simple(value),
+            };
+            array.append(item);
+        }
+
+        self.put_node(ns_uri, &parsed.components, |_, _| Ok(Node::Array(array)))
+    }
+
+    /// Get a copy of the array at a path, failing if the node is not an array
+    fn array_node(&self, namespace: &str, path: &str) -> XmpResult<Option<ArrayNode>> {
+        let parsed = parse_path(path)?;
+        let root = root_read!(self.root);
+        match self.get_node_by_components(&root, namespace, &parsed.components) {
+            None => Ok(None),
+            Some((Node::Array(array), _)) => Ok(Some(array.clone())),
+            Some(_) => Err(XmpError::BadParam(format!(
+                "Property '{}' is not an array",
+                path
+            ))),
+        }
+    }
+}
+
+fn simple_value(node: &Node) -> Option<&String> {
+    node.as_simple().map(|simple| &simple.value)
+}
+
+/// Quote an item if separating the catenated text would not give it back
+fn quote_item(value: &str, open: char, close: char) -> String {
+    let needs_quotes = value != value.trim()
+        || value.starts_with(|c| QUOTE_PAIRS.iter().any(|&(q, _)| q == c))
+        || value.contains(|c: char| c == ';' || c == ',' || c.is_control());
+    if !needs_quotes {
+        return value.to_string();
+    }
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push(open);
+    for c in value.chars() {
+        if c == close {
+            quoted.push(close);
+        }
+        quoted.push(c);
+    }
+    quoted.push(close);
+    quoted
+}
+
+/// Split delimited text into items
+///
+/// Whitespace around items is dropped, but not whitespace inside quotes.
+fn split_items(text: &str, allow_commas: bool) -> Vec<String> {
+    let is_separator = |c: char| c == ';' || (c == ',' && !allow_commas) || c.is_control();
+    let mut items = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    loop {
+        while chars
+            .next_if(|c| c.is_whitespace() || is_separator(*c))
+            .is_some()
+        {}
+        let Some(&first) = chars.peek() else {
+            break;
+        };
+
+        let mut quoted = None;
+        if let Some(&(_, close)) = QUOTE_PAIRS.iter().find(|&&(open, _)| open == first) {
+            chars.next();
+            let mut text = String::new();
+            while let Some(c) = chars.next() {
+                if c == close && chars.next_if_eq(&close).is_none() {
+                    break;
+                }
+                text.push(c);
+            }
+            quoted = Some(text);
+        }
+        // Unquoted text, or anything trailing a closing quote
+        let mut unquoted = String::new();
+        while let Some(c) = chars.next_if(|c| !is_separator(*c)) {
+            unquoted.push(c);
+        }
+
+        let item = match quoted {
+            Some(mut item) => {
+                item.push_str(unquoted.trim_end());
+                item
+            }
+            None => unquoted.trim().to_string(),
+        };
+        if !item.is_empty() {
+            items.push(item);
+        }
+    }
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::value::XmpValue;
+
+    fn items(meta: &XmpMeta) -> Vec<String> {
+        let size = meta.get_array_size("dc", "subject").unwrap_or(0);
+        (0..size)
+            .filter_map(|i| meta.get_array_item("dc", "subject", i))
+            .filter_map(|v| v.as_str().map(String::from))
+            .collect()
+    }
+
+    #[test]
+    fn test_split_items() {
+        assert_eq!(
+            split_items("sunset; beach, holiday", false),
+            vec!["sunset", "beach", "holiday"]
+        );
+        assert_eq!(
+            split_items("sunset; beach, holiday", true),
+            vec!["sunset", "beach, holiday"]
+        );
+        assert_eq!(
+            split_items(
+                "\"a; b\" ;; \u{201C}say \u{201D}\u{201D}hi\u{201D}; 'x''s'\n y",
+                false
+            ),
+            vec!["a; b", "say \u{201D}hi", "x's", "y"]
+        );
+        assert_eq!(
+            split_items(" ' a ' ;  b c  ; \"x\" y ", false),
+            vec![" a ", "b c", "x y"]
+        );
+    }
+
+    #[test]
+    fn test_catenate_separate_round_trip() {
+        let mut meta = XmpMeta::new();
+        for value in ["sunset", "beach, sea", " padded ", "\"quoted\"", "a;b"] {
+            meta.append_array_item("dc", "subject", value.into())
+                .unwrap();
+        }
+        let text = meta.catenate_array_items("dc", "subject", "", "").unwrap();
+        assert_eq!(
+            text,
+            "sunset; \"beach, sea\"; \" padded \"; \"\"\"quoted\"\"\"; \"a;b\""
+        );
+
+        let mut copy = XmpMeta::new();
+        copy.separate_array_items("dc", "subject", &text, SeparateOptions::default())
+            .unwrap();
+        assert_eq!(
+            items(&copy),
+            vec!["sunset", "beach, sea", " padded ", "\"quoted\"", "a;b"]
+        );
+        assert!(matches!(
+            copy.get_property("dc", "subject"),
+            Some(XmpValue::Array(ArrayType::Unordered, _))
+        ));
+
+        assert!(meta
+            .catenate_array_items("dc", "subject", ", ", "")
+            .is_err());
+        assert!(meta
+            .catenate_array_items("dc", "subject", "; ", "<<>>")
+            .is_err());
+        assert_eq!(
+            meta.catenate_array_items("dc", "missing", "", "").unwrap(),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_separate_preserve_and_unique() {
+        let mut meta = XmpMeta::new();
+        meta.separate_array_items("dc", "subject", "sunset; beach", SeparateOptions::default())
+            .unwrap();
+        meta.set_qualifier("dc", "subject[2]", "xmp", "Rating", "5")
+            .unwrap();
+
+        // Replacing keeps the qualifiers of items that are still there
+        meta.separate_array_items(
+            "dc",
+            "subject",
+            "beach; holiday",
+            SeparateOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(items(&meta), vec!["beach", "holiday"]);
+        assert!(meta.has_qualifier("dc", "subject[1]", "xmp", "Rating"));
+
+        meta.separate_array_items(
+            "dc",
+            "subject",
+            "holiday, sunset",
+            SeparateOptions::default().preserve_existing().unique(),
+        )
+        .unwrap();
+        assert_eq!(items(&meta), vec!["beach", "holiday", "sunset"]);
+
+        meta.separate_array_items(
+            "dc",
+            "subject",
+            "sunset",
+            SeparateOptions::default().preserve_existing(),
+        )
+        .unwrap();
+        assert_eq!(items(&meta), vec!["beach", "holiday", "sunset", "sunset"]);
+
+        meta.set_property("dc", "format", "image/jpeg".into())
+            .unwrap();
+        assert!(meta
+            .separate_array_items("dc", "format", "a", SeparateOptions::default())
+            .is_err());
+    }
+}
@@ -0,0 +1,711 @@
+//! Structured differences between XMP metadata trees
+//!
+//! [`XmpMeta::diff`] lists what changed between two metadata objects, down to
+//! array items and qualifiers. [`XmpMeta::apply_patch`] replays such a diff on
+//! another object.
+
+use super::node::root_read_with;
+use super::{split_qualifier_step, XmpMeta};
+use crate::core::canonical::encode_node;
+use crate::core::error::{XmpError, XmpResult};
+use crate::core::namespace::{get_global_namespace_prefix, NamespaceMap};
+use crate::core::node::{ArrayNode, ArrayType, Node, SimpleNode, StructureNode};
+use crate::core::xpath::parse_path;
+use crate::types::qualifier::Qualifier;
+use std::collections::BTreeMap;
+
+/// Differences between two [`XmpMeta`] objects, as produced by [`XmpMeta::diff`]
+///
+/// Changes are listed in the order they have to be applied: removals of
+/// array items come from the highest index down, and the children of an
+/// added node follow the node itself. Paths use the prefixes listed in
+/// [`namespaces`](Self::namespaces), so a diff can be stored (with the
+/// `serde` feature) and applied to an object that does not know them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct XmpDiff {
+    /// Prefixes used in the change paths, keyed by namespace URI
+    pub namespaces: BTreeMap<String, String>,
+    /// The changes
+    pub changes: Vec<XmpChange>,
+}
+
+impl XmpDiff {
+    /// Whether the two objects had no differences
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// A single change in an [`XmpDiff`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct XmpChange {
+    /// Namespace URI of the top-level property
+    pub namespace_uri: String,
+    /// Path of the changed node (e.g., `subject[2]`, `Flash/exif:Fired` or
+    /// `creator[1]/?xmp:Role`)
+    pub path: String,
+    /// What happened to the node
+    pub kind: XmpChangeKind,
+}
+
+/// The kind of an [`XmpChange`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum XmpChangeKind {
+    /// The node was added
+    ///
+    /// The qualifiers and children of an added node are listed as separate
+    /// changes right after it.
+    Added(XmpDiffNode),
+    /// The node was removed
+    ///
+    /// The removals of its qualifiers and children are listed right before it.
+    Removed(XmpDiffNode),
+    /// The node was replaced by a node of another kind (e.g., a simple
+    /// value by an array), in place
+    ///
+    /// The removals of the old node's qualifiers and children are listed
+    /// right before it, and the additions of the new node's right after.
+    Replaced {
+        /// The previous node
+        old: XmpDiffNode,
+        /// The new node
+        new: XmpDiffNode,
+    },
+    /// The value of a simple node or qualifier changed
+    Changed {
+        /// Previous value
+        old: String,
+        /// New value
+        new: String,
+    },
+}
+
+/// An added or removed node, without its qualifiers and children
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum XmpDiffNode {
+    /// A simple value (or a qualifier)
+    Simple {
+        /// The value
+        value: String,
+        /// Whether the value is a URI (`rdf:resource`)
+        is_uri: bool,
+    },
+    /// An array of the given form
+    Array(ArrayType),
+    /// A structure
+    Structure,
+}
+
+impl XmpDiffNode {
+    fn of(node: &Node) -> Self {
+        match node {
+            Node::Simple(simple) => XmpDiffNode::Simple {
+                value: simple.value.clone(),
+                is_uri: simple.is_uri,
+            },
+            Node::Array(array) => XmpDiffNode::Array(array.array_type),
+            Node::Structure(_) => XmpDiffNode::Structure,
+        }
+    }
+
+    fn to_node(&self) -> Node {
+        match self {
+            XmpDiffNode::Simple {
+                value,
+                is_uri: true,
+            } => Node::Simple(SimpleNode::uri(value)),
+            XmpDiffNode::Simple { value, .. } => Node::simple(value.as_str()),
+            XmpDiffNode::Array(array_type) => Node::array(*array_type),
+            XmpDiffNode::Structure => Node::structure(),
+        }
+    }
+
+    /// Whether two nodes can be compared in place rather than replaced
+    fn same_shape(&self, other: &XmpDiffNode) -> bool {
+        match (self, other) {
+            (XmpDiffNode::Simple { is_uri: a, .. }, XmpDiffNode::Simple { is_uri: b, .. }) => {
+                a == b
+            }
+            (a, b) => a == b,
+        }
+    }
+}
+
+impl XmpMeta {
+    /// Compute the changes that turn this metadata into `other`.
+    ///
+    /// Items of ordered and alternative arrays are compared by position.
+    /// Items of unordered arrays (`rdf:Bag`) are matched by value, so
+    /// reordering them is not a change.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use xmpkit::core::metadata::XmpChangeKind;
+    /// use xmpkit::XmpMeta;
+    ///
+    /// let mut before = XmpMeta::new();
+    /// before.set_property("xmp", "Rating", "3".into()).unwrap();
+    /// let mut after = before.clone();
+    /// after.set_property("xmp", "Rating", "5".into()).unwrap();
+    ///
+    /// let diff = before.diff(&after);
+    /// assert_eq!(diff.changes[0].path, "Rating");
+    /// assert!(matches!(diff.changes[0].kind, XmpChangeKind::Changed { .. }));
+    ///
+    /// before.apply_patch(&diff).unwrap();
+    /// assert!(before.semantically_eq(&after));
+    /// ```
+    pub fn diff(&self, other: &XmpMeta) -> XmpDiff {
+        let old_root = root_read_with(&self.root, |root| root.clone());
+        let new_root = root_read_with(&other.root, |root| root.clone());
+        let mut differ = Differ {
+            old_namespaces: &self.namespaces,
+            new_namespaces: &other.namespaces,
+            diff: XmpDiff::default(),
+        };
+        differ.diff_fields("", None, &old_root, &new_root);
+        differ.diff
+    }
+
+    /// Apply the changes of a diff produced by [`diff`](Self::diff).
+    ///
+    /// Missing namespace prefixes of the diff are registered first. Removing
+    /// a node that does not exist is not an error, and changing one that does
+    /// not exist creates it.
+    pub fn apply_patch(&mut self, diff: &XmpDiff) -> XmpResult<()> {
+        for (uri, prefix) in &diff.namespaces {
+            match self.resolve_namespace_uri(prefix) {
+                Some(bound) if bound == *uri => {}
+                Some(bound) => {
+                    return Err(XmpError::BadSchema(format!(
+                        "Prefix '{}' of the diff is bound to '{}' instead of '{}'",
+                        prefix, bound, uri
+                    )))
+                }
+                None => self.namespaces.register(uri, prefix)?,
+            }
+        }
+
+        for change in &diff.changes {
+            // Address the schema by its prefix, bound above: namespace
+            // arguments are only taken as URIs when they start with http://
+            let namespace = diff
+                .namespaces
+                .get(&change.namespace_uri)
+                .unwrap_or(&change.namespace_uri);
+            let ns_uri = self.resolve_namespace_uri_or_error(namespace)?;
+            let parsed = parse_path(&change.path)?;
+            let (components, qual_name) = split_qualifier_step(&parsed.components);
+
+            match (&change.kind, qual_name) {
+                (XmpChangeKind::Removed(_), Some(qual_name)) => {
+                    self.delete_qualifier_at(namespace, components, qual_name, None)?
+                }
+                (XmpChangeKind::Removed(_), None) => {
+                    self.delete_property(namespace, &change.path)?
+                }
+                (XmpChangeKind::Added(XmpDiffNode::Simple { value, .. }), Some(qual_name))
+                | (XmpChangeKind::Changed { new: value, .. }, Some(qual_name)) => {
+                    self.set_qualifier_at(namespace, components, qual_name, None, value)?
+                }
+                (XmpChangeKind::Added(_) | XmpChangeKind::Replaced { .. }, Some(_)) => {
+                    return Err(XmpError::BadValue(
+                        "Qualifier values must be simple".to_string(),
+                    ))
+                }
+                (XmpChangeKind::Added(node), None)
+                | (XmpChangeKind::Replaced { new: node, .. }, None) => {
+                    let node = node.to_node();
+                    self.put_node(ns_uri, components, |_, _| Ok(node))?
+                }
+                (XmpChangeKind::Changed { new, .. }, None) => {
+                    let updated = {
+                        let mut root = root_write!(self.root);
+                        match self.get_node_by_components_mut(&mut root, namespace, components) {
+                            Some((Node::Simple(simple), _)) => {
+                                simple.value = new.clone();
+                                true
+                            }
+                            _ => false,
+                        }
+                    };
+                    if !updated {
+                        let node = Node::simple(new.as_str());
+                        self.put_node(ns_uri, components, |_, _| Ok(node))?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Walks two trees side by side and records the changes
+struct Differ<'a> {
+    old_namespaces: &'a NamespaceMap,
+    new_namespaces: &'a NamespaceMap,
+    diff: XmpDiff,
+}
+
+impl Differ<'_> {
+    /// Get (and record) the prefix used for a namespace URI in paths
+    fn prefix(&mut self, uri: &str) -> String {
+        if let Some(prefix) = self.diff.namespaces.get(uri) {
+            return prefix.clone();
+        }
+        let candidate = self
+            .new_namespaces
+            .get_prefix(uri)
+            .or_else(|| self.old_namespaces.get_prefix(uri))
+            .map(String::from)
+            .or_else(|| get_global_namespace_prefix(uri));
+        let taken = |prefix: &str| self.diff.namespaces.values().any(|p| p == prefix);
+        let prefix = match candidate {
+            Some(prefix) if !taken(&prefix) => prefix,
+            _ => (1..)
+                .map(|n| format!("ns{}", n))
+                .find(|prefix| !taken(prefix))
+                .unwrap(),
+        };
+        self.diff.namespaces.insert(uri.to_string(), prefix.clone());
+        prefix
+    }
+
+    /// Split a `namespace_uri:name` key into its namespace and a prefixed name
+    fn qualified_name(&mut self, key: &str) -> (String, String) {
+        match key.rfind(':') {
+            Some(pos) => {
+                let prefix = self.prefix(&key[..pos]);
+                (
+                    key[..pos].to_string(),
+                    format!("{}:{}", prefix, &key[pos + 1..]),
+                )
+            }
+            None => (String::new(), key.to_string()),
+        }
+    }
+
+    fn push(&mut self, ns_uri: &str, path: String, kind: XmpChangeKind) {
+        self.diff.changes.push(XmpChange {
+            namespace_uri: ns_uri.to_string(),
+            path,
+            kind,
+        });
+    }
+
+    /// Compare the fields of two structures (or roots, when `parent` is `None`)
+    fn diff_fields(
+        &mut self,
+        ns_uri: &str,
+        parent: Option<&str>,
+        old: &StructureNode,
+        new: &StructureNode,
+    ) {
+        let field_location = |differ: &mut Self, key: &str| {
+            let (field_ns, name) = differ.qualified_name(key);
+            match parent {
+                // Top-level properties are addressed by namespace and local name
+                None => {
+                    let local = name.split_once(':').map_or(name.as_str(), |(_, l)| l);
+                    (field_ns, local.to_string())
+                }
+                Some(parent) => (ns_uri.to_string(), format!("{}/{}", parent, name)),
+            }
+        };
+
+        for (key, node) in &old.fields {
+            if !new.fields.contains_key(key) {
+                let (ns, path) = field_location(self, key);
+                self.remove_subtree(&ns, path, node);
+            }
+        }
+        for (key, old_node) in &old.fields {
+            if let Some(new_node) = new.fields.get(key) {
+                let (ns, path) = field_location(self, key);
+                self.diff_nodes(&ns, path, old_node, new_node);
+            }
+        }
+        for (key, node) in &new.fields {
+            if !old.fields.contains_key(key) {
+                let (ns, path) = field_location(self, key);
+                self.add_subtree(&ns, path, node);
+            }
+        }
+    }
+
+    fn diff_nodes(&mut self, ns_uri: &str, path: String, old: &Node, new: &Node) {
+        let (old_shape, new_shape) = (XmpDiffNode::of(old), XmpDiffNode::of(new));
+        if !old_shape.same_shape(&new_shape) {
+            // A removal followed by an addition would shift the later items
+            // of an array in between
+            self.remove_contents(ns_uri, &path, old);
+            self.push(
+                ns_uri,
+                path.clone(),
+                XmpChangeKind::Replaced {
+                    old: old_shape,
+                    new: new_shape,
+                },
+            );
+            self.add_contents(ns_uri, &path, new);
+            return;
+        }
+
+        self.diff_qualifiers(ns_uri, &path, old.qualifiers(), new.qualifiers());
+        match (old, new) {
+            (Node::Simple(old), Node::Simple(new)) if old.value != new.value => {
+                self.push(
+                    ns_uri,
+                    path,
+                    XmpChangeKind::Changed {
+                        old: old.value.clone(),
+                        new: new.value.clone(),
+                    },
+                );
+            }
+            (Node::Structure(old), Node::Structure(new)) => {
+                self.diff_fields(ns_uri, Some(&path), old, new)
+            }
+            (Node::Array(old), Node::Array(new)) => self.diff_arrays(ns_uri, &path, old, new),
+            _ => {}
+        }
+    }
+
+    fn diff_arrays(&mut self, ns_uri: &str, path: &str, old: &ArrayNode, new: &ArrayNode) {
+        let item_path = |index: usize| format!("{}[{}]", path, index + 1);
+
+        if old.array_type == ArrayType::Unordered {
+            let encode = |node: &Node| {
+                let mut out = Vec::new();
+                encode_node(node, &mut out);
+                out
+            };
+            let mut unmatched: Vec<Option<Vec<u8>>> =
+                new.items.iter().map(|item| Some(encode(item))).collect();
+            let mut removed = Vec::new();
+            for (index, item) in old.items.iter().enumerate() {
+                let encoded = encode(item);
+                match unmatched.iter().position(|n| n.as_ref() == Some(&encoded)) {
+                    Some(pos) => unmatched[pos] = None,
+                    None => removed.push(index),
+                }
+            }
+            for &index in removed.iter().rev() {
+                self.remove_subtree(ns_uri, item_path(index), &old.items[index]);
+            }
+            let mut next = old.items.len() - removed.len();
+            for (item, unmatched) in new.items.iter().zip(&unmatched) {
+                if unmatched.is_some() {
+                    self.add_subtree(ns_uri, item_path(next), item);
+                    next += 1;
+                }
+            }
+            return;
+        }
+
+        let common = old.items.len().min(new.items.len());
+        for index in 0..common {
+            self.diff_nodes(
+                ns_uri,
+                item_path(index),
+                &old.items[index],
+                &new.items[index],
+            );
+        }
+        for index in (common..old.items.len()).rev() {
+            self.remove_subtree(ns_uri, item_path(index), &old.items[index]);
+        }
+        for index in common..new.items.len() {
+            self.add_subtree(ns_uri, item_path(index), &new.items[index]);
+        }
+    }
+
+    fn diff_qualifiers(&mut self, ns_uri: &str, path: &str, old: &[Qualifier], new: &[Qualifier]) {
+        let find = |list: &[Qualifier], q: &Qualifier| {
+            list.iter()
+                .find(|other| other.namespace == q.namespace && other.name == q.name)
+                .cloned()
+        };
+        for qualifier in old {
+            if find(new, qualifier).is_none() {
+                let path = self.qualifier_path(path, qualifier);
+                self.push(ns_uri, path, removed_qualifier(qualifier));
+            }
+        }
+        for qualifier in new {
+            match find(old, qualifier) {
+                Some(old) if old.value != qualifier.value => {
+                    let path = self.qualifier_path(path, qualifier);
+                    let kind = XmpChangeKind::Changed {
+                        old: old.value,
+                        new: qualifier.value.clone(),
+                    };
+                    self.push(ns_uri, path, kind);
+                }
+                Some(_) => {}
+                None => {
+                    let path = self.qualifier_path(path, qualifier);
+                    self.push(ns_uri, path, added_qualifier(qualifier));
+                }
+            }
+        }
+    }
+
+    fn qualifier_path(&mut self, path: &str, qualifier: &Qualifier) -> String {
+        let prefix = self.prefix(&qualifier.namespace);
+        format!("{}/?{}:{}", path, prefix, qualifier.name)
+    }
+
+    /// Record the addition of a node, then its qualifiers and children
+    fn add_subtree(&mut self, ns_uri: &str, path: String, node: &Node) {
+        self.push(
+            ns_uri,
+            path.clone(),
+            XmpChangeKind::Added(XmpDiffNode::of(node)),
+        );
+        self.add_contents(ns_uri, &path, node);
+    }
+
+    /// Record the addition of a node's qualifiers and children
+    fn add_contents(&mut self, ns_uri: &str, path: &str, node: &Node) {
+        for qualifier in node.qualifiers() {
+            let qual_path = self.qualifier_path(path, qualifier);
+            self.push(ns_uri, qual_path, added_qualifier(qualifier));
+        }
+        match node {
+            Node::Simple(_) => {}
+            Node::Array(array) => {
+                for (index, item) in array.items.iter().enumerate() {
+                    self.add_subtree(ns_uri, format!("{}[{}]", path, index + 1), item);
+                }
+            }
+            Node::Structure(structure) => {
+                for (key, field) in &structure.fields {
+                    let (_, name) = self.qualified_name(key);
+                    self.add_subtree(ns_uri, format!("{}/{}", path, name), field);
+                }
+            }
+        }
+    }
+
+    /// Record the removal of a node's children and qualifiers, then the node
+    fn remove_subtree(&mut self, ns_uri: &str, path: String, node: &Node) {
+        self.remove_contents(ns_uri, &path, node);
+        self.push(ns_uri, path, XmpChangeKind::Removed(XmpDiffNode::of(node)));
+    }
+
+    /// Record the removal of a node's children and qualifiers
+    fn remove_contents(&mut self, ns_uri: &str, path: &str, node: &Node) {
+        match node {
+            Node::Simple(_) => {}
+            Node::Array(array) => {
+                for (index, item) in array.items.iter().enumerate().rev() {
+                    self.remove_subtree(ns_uri, format!("{}[{}]", path, index + 1), item);
+                }
+            }
+            Node::Structure(structure) => {
+                for (key, field) in &structure.fields {
+                    let (_, name) = self.qualified_name(key);
+                    self.remove_subtree(ns_uri, format!("{}/{}", path, name), field);
+                }
+            }
+        }
+        for qualifier in node.qualifiers() {
+            let qual_path = self.qualifier_path(path, qualifier);
+            self.push(ns_uri, qual_path, removed_qualifier(qualifier));
+        }
+    }
+}
+
+fn added_qualifier(qualifier: &Qualifier) -> XmpChangeKind {
+    XmpChangeKind::Added(XmpDiffNode::Simple {
+        value: qualifier.value.clone(),
+        is_uri: false,
+    })
+}
+
+fn removed_qualifier(qualifier: &Qualifier) -> XmpChangeKind {
+    XmpChangeKind::Removed(XmpDiffNode::Simple {
+        value: qualifier.value.clone(),
+        is_uri: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::value::XmpValue;
+
+    fn sample() -> XmpMeta {
+        XmpMeta::parse(
+            r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+ <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+  <rdf:Description rdf:about="" xmlns:dc="http://purl.org/dc/elements/1.1/"
+    xmlns:xmp="http://ns.adobe.com/xap/1.0/" xmlns:exif="http://ns.adobe.com/exif/1.0/"
+    xmlns:cust="urn:example:custom:">
+   <dc:subject><rdf:Bag><rdf:li>sunset</rdf:li><rdf:li>beach</rdf:li></rdf:Bag></dc:subject>
+   <dc:creator><rdf:Seq><rdf:li>Alice</rdf:li><rdf:li>Bob</rdf:li></rdf:Seq></dc:creator>
+   <exif:Flash rdf:parseType="Resource">
+    <exif:Fired>True</exif:Fired>
+    <exif:Mode>2</exif:Mode>
+   </exif:Flash>
+   <xmp:Rating>3</xmp:Rating>
+   <cust:Note>draft</cust:Note>
+  </rdf:Description>
+ </rdf:RDF>
+</x:xmpmeta>"#,
+        )
+        .unwrap()
+    }
+
+    fn paths(diff: &XmpDiff) -> Vec<&str> {
+        diff.changes.iter().map(|c| c.path.as_str()).collect()
+    }
+
+    #[test]
+    fn test_diff_reports_paths() {
+        let before = sample();
+        assert!(before.diff(&before.clone()).is_empty());
+
+        let mut after = before.clone();
+        after
+            .set_property("exif", "Flash/exif:Fired", "False".into())
+            .unwrap();
+        after.delete_property("dc", "creator[2]").unwrap();
+        after
+            .set_qualifier("dc", "creator[1]", "xmp", "Role", "author")
+            .unwrap();
+        after.delete_property("xmp", "Rating").unwrap();
+        after
+            .append_array_item("dc", "subject", "stock".into())
+            .unwrap();
+
+        let diff = before.diff(&after);
+        assert_eq!(
+            paths(&diff),
+            vec![
+                "Rating",
+                "subject[3]",
+                "creator[1]/?xmp:Role",
+                "creator[2]",
+                "Flash/exif:Fired",
+            ]
+        );
+        assert_eq!(
+            diff.changes[4].kind,
+            XmpChangeKind::Changed {
+                old: "True".to_string(),
+                new: "False".to_string()
+            }
+        );
+        assert_eq!(
+            diff.changes[1].kind,
+            XmpChangeKind::Added(XmpDiffNode::Simple {
+                value: "stock".to_string(),
+                is_uri: false
+            })
+        );
+
+        // Reordering a Bag is not a change
+        let mut reordered = before.clone();
+        reordered
+            .set_property(
+                "dc",
+                "subject",
+                XmpValue::Array(ArrayType::Unordered, vec!["beach".into(), "sunset".into()]),
+            )
+            .unwrap();
+        assert!(before.diff(&reordered).is_empty());
+    }
+
+    #[test]
+    fn test_apply_patch_round_trip() {
+        let before = sample();
+        let mut after = before.clone();
+        after
+            .set_property(
+                "dc",
+                "subject",
+                XmpValue::Array(ArrayType::Ordered, vec!["stock".into()]),
+            )
+            .unwrap();
+        after.delete_property("exif", "Flash").unwrap();
+        after.set_property("cust", "Note", "final".into()).unwrap();
+        after
+            .set_struct_field("xmp", "Thumbnail", "xmp:format", "JPEG".into())
+            .unwrap();
+        after
+            .set_qualifier("dc", "creator[2]", "xmp", "Role", "editor")
+            .unwrap();
+
+        let diff = before.diff(&after);
+        let mut patched = before.clone();
+        patched.apply_patch(&diff).unwrap();
+        assert!(patched.semantically_eq(&after));
+
+        // The custom namespace travels with the diff
+        let mut note = XmpMeta::new();
+        note.copy_schema(&after, "cust").unwrap();
+        let mut other = XmpMeta::new();
+        other.apply_patch(&XmpMeta::new().diff(&note)).unwrap();
+        assert_eq!(
+            other.get_property("cust", "Note"),
+            Some(XmpValue::String("final".to_string()))
+        );
+
+        // And the reverse diff undoes it
+        patched.apply_patch(&after.diff(&before)).unwrap();
+        assert!(patched.semantically_eq(&before));
+    }
+
+    #[test]
+    fn test_apply_patch_item_changes_kind() {
+        let before = sample();
+        let mut after = before.clone();
+        after
+            .set_property(
+                "dc",
+                "creator",
+                XmpValue::Array(
+                    ArrayType::Ordered,
+                    vec![
+                        XmpValue::Array(ArrayType::Unordered, vec!["x".into()]),
+                        "Bob".into(),
+                    ],
+                ),
+            )
+            .unwrap();
+
+        let diff = before.diff(&after);
+        assert_eq!(
+            diff.changes[0],
+            XmpChange {
+                namespace_uri: crate::core::namespace::ns::DC.to_string(),
+                path: "creator[1]".to_string(),
+                kind: XmpChangeKind::Replaced {
+                    old: XmpDiffNode::Simple {
+                        value: "Alice".to_string(),
+                        is_uri: false
+                    },
+                    new: XmpDiffNode::Array(ArrayType::Unordered),
+                },
+            }
+        );
+
+        let mut patched = before.clone();
+        patched.apply_patch(&diff).unwrap();
+        assert!(patched.semantically_eq(&after));
+        assert_eq!(
+            patched.get_array_item("dc", "creator", 1),
+            Some(XmpValue::String("Bob".to_string()))
+        );
+
+        patched.apply_patch(&after.diff(&before)).unwrap();
+        assert!(patched.semantically_eq(&before));
+    }
+}
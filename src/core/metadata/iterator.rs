@@ -0,0 +1,590 @@
+//! Deep iteration over XMP metadata
+//!
+//! This module provides [`XmpIterator`], the equivalent of the Adobe SDK's
+//! `XMPIterator`. It walks the whole data model: schemas, properties, nested
+//! structure fields, array items and qualifiers.
+
+use super::XmpMeta;
+use crate::core::namespace::{get_global_namespace_prefix, NamespaceMap};
+use crate::core::node::{ArrayType, Node, StructureNode};
+use crate::core::xpath::{build_path, parse_path, PathComponent, PathComponents};
+
+/// Options for iterating an [`XmpMeta`] tree.
+///
+/// Use the builder pattern to configure options.
+///
+/// # Example
+///
+/// ```rust
+/// use xmpkit::core::metadata::IterOptions;
+/// use xmpkit::core::namespace::ns;
+///
+/// // Only the leaf values of the Dublin Core schema, without qualifiers
+/// let options = IterOptions::default()
+///     .schema(ns::DC)
+///     .just_leaf_nodes()
+///     .omit_qualifiers();
+/// ```
+#[derive(Default, Clone, Debug)]
+pub struct IterOptions {
+    /// Namespace URI or prefix of the schema to start at (default: all schemas)
+    pub schema_ns: Option<String>,
+    /// Property path to start at, within `schema_ns`
+    pub property_path: Option<String>,
+    /// Only visit the immediate children of the starting point
+    pub just_children: bool,
+    /// Only visit leaf nodes (nodes without children)
+    pub just_leaf_nodes: bool,
+    /// Return only the last step of each path instead of the full path
+    pub just_leaf_name: bool,
+    /// Do not visit qualifiers
+    pub omit_qualifiers: bool,
+}
+
+impl IterOptions {
+    /// Start at the given schema (namespace URI or prefix).
+    pub fn schema(mut self, namespace: impl Into<String>) -> Self {
+        self.schema_ns = Some(namespace.into());
+        self
+    }
+
+    /// Start at the given property (e.g., `"creator"` or `"History[2]"`).
+    pub fn property(mut self, namespace: impl Into<String>, path: impl Into<String>) -> Self {
+        self.schema_ns = Some(namespace.into());
+        self.property_path = Some(path.into());
+        self
+    }
+
+    /// Only visit the immediate children of the starting point.
+    ///
+    /// Without a starting point, the children are the schemas.
+    pub fn just_children(mut self) -> Self {
+        self.just_children = true;
+        self
+    }
+
+    /// Only visit leaf nodes.
+    pub fn just_leaf_nodes(mut self) -> Self {
+        self.just_leaf_nodes = true;
+        self
+    }
+
+    /// Return only the last path step (e.g., `[1]` or `?xml:lang`).
+    pub fn just_leaf_name(mut self) -> Self {
+        self.just_leaf_name = true;
+        self
+    }
+
+    /// Do not visit qualifiers.
+    pub fn omit_qualifiers(mut self) -> Self {
+        self.omit_qualifiers = true;
+        self
+    }
+}
+
+/// Kind of node visited by an [`XmpIterator`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XmpNodeKind {
+    /// A schema (all top-level properties sharing a namespace)
+    Schema,
+    /// A simple value (also used for qualifiers)
+    Simple,
+    /// An array of the given form
+    Array(ArrayType),
+    /// A structure
+    Structure,
+}
+
+/// A node visited by an [`XmpIterator`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct XmpIterItem {
+    /// Namespace URI of the schema the node belongs to
+    pub namespace_uri: String,
+    /// Path of the node (e.g., `dc:creator[1]/?xml:lang`), empty for schema nodes
+    pub path: String,
+    /// Kind of node
+    pub kind: XmpNodeKind,
+    /// Value of simple nodes and qualifiers
+    pub value: Option<String>,
+    /// Whether the node is a qualifier of its parent
+    pub is_qualifier: bool,
+    /// Whether the node has qualifiers
+    pub has_qualifiers: bool,
+    /// Whether the node has an `xml:lang` qualifier
+    pub has_lang: bool,
+}
+
+/// A flattened node with the bounds needed to skip over parts of the tree
+struct Entry {
+    item: XmpIterItem,
+    leaf_name: String,
+    depth: usize,
+    is_leaf: bool,
+    /// Index just past this node's subtree
+    subtree_end: usize,
+    /// Index just past the subtree of this node's parent
+    parent_end: usize,
+}
+
+/// Iterator over the nodes of an [`XmpMeta`] tree, in document order.
+///
+/// Each node is visited before its qualifiers, and its qualifiers before its
+/// children. The iterator works on a snapshot, so the metadata object can be
+/// modified while iterating.
+///
+/// # Example
+///
+/// ```rust
+/// use xmpkit::core::metadata::IterOptions;
+/// use xmpkit::XmpMeta;
+///
+/// let mut meta = XmpMeta::new();
+/// meta.append_array_item("dc", "creator", "Alice".into()).unwrap();
+/// meta.set_localized_text("dc", "title", "", "x-default", "Title").unwrap();
+///
+/// let paths: Vec<String> = meta
+///     .iter(IterOptions::default().just_leaf_nodes().omit_qualifiers())
+///     .map(|item| item.path)
+///     .collect();
+/// assert_eq!(paths, vec!["dc:creator[1]", "dc:title[1]"]);
+/// ```
+pub struct XmpIterator {
+    entries: Vec<Entry>,
+    pos: usize,
+    last: Option<usize>,
+    options: IterOptions,
+}
+
+impl XmpIterator {
+    /// Skip the children and qualifiers of the node returned last.
+    pub fn skip_subtree(&mut self) {
+        if let Some(last) = self.last {
+            self.pos = self.pos.max(self.entries[last].subtree_end);
+        }
+    }
+
+    /// Skip the remaining siblings of the node returned last.
+    pub fn skip_siblings(&mut self) {
+        if let Some(last) = self.last {
+            self.pos = self.pos.max(self.entries[last].parent_end);
+        }
+    }
+
+    fn is_visible(&self, entry: &Entry) -> bool {
+        if self.options.just_children && entry.depth != 1 {
+            return false;
+        }
+        if self.options.just_leaf_nodes && !entry.is_leaf {
+            return false;
+        }
+        true
+    }
+}
+
+impl Iterator for XmpIterator {
+    type Item = XmpIterItem;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pos < self.entries.len() {
+            let index = self.pos;
+            self.pos += 1;
+            if !self.is_visible(&self.entries[index]) {
+                continue;
+            }
+            self.last = Some(index);
+            let entry = &self.entries[index];
+            let mut item = entry.item.clone();
+            if self.options.just_leaf_name {
+                item.path = entry.leaf_name.clone();
+            }
+            return Some(item);
+        }
+        None
+    }
+}
+
+/// Builds the flattened entry list for an iterator
+struct TreeFlattener<'a> {
+    namespaces: &'a NamespaceMap,
+    omit_qualifiers: bool,
+    entries: Vec<Entry>,
+}
+
+impl TreeFlattener<'_> {
+    /// Turn a `namespace_uri:name` key into `prefix:name`
+    fn prefixed_name(&self, key: &str) -> String {
+        let Some(colon_pos) = key.rfind(':') else {
+            return key.to_string();
+        };
+        let (uri, name) = (&key[..colon_pos], &key[colon_pos + 1..]);
+        match self
+            .namespaces
+            .get_prefix(uri)
+            .map(|p| p.to_string())
+            .or_else(|| get_global_namespace_prefix(uri))
+        {
+            Some(prefix) => format!("{}:{}", prefix, name),
+            None => key.to_string(),
+        }
+    }
+
+    /// Append an entry, returning its index
+    fn push(&mut self, item: XmpIterItem, leaf_name: String, depth: usize, is_leaf: bool) -> usize {
+        self.entries.push(Entry {
+            item,
+            leaf_name,
+            depth,
+            is_leaf,
+            subtree_end: 0,
+            parent_end: 0,
+        });
+        self.entries.len() - 1
+    }
+
+    /// Close the subtree of `index`, recording the bounds of its children
+    fn close(&mut self, index: usize, children: &[usize]) {
+        let end = self.entries.len();
+        self.entries[index].subtree_end = end;
+        for &child in children {
+            self.entries[child].parent_end = end;
+        }
+    }
+
+    /// Add a schema node and its properties
+    fn add_schema(&mut self, ns_uri: &str, properties: &[(&String, &Node)], depth: usize) -> usize {
+        let item = XmpIterItem {
+            namespace_uri: ns_uri.to_string(),
+            path: String::new(),
+            kind: XmpNodeKind::Schema,
+            value: None,
+            is_qualifier: false,
+            has_qualifiers: false,
+            has_lang: false,
+        };
+        let index = self.push(item, String::new(), depth, false);
+        let mut children = Vec::with_capacity(properties.len());
+        for (key, node) in properties {
+            let name = self.prefixed_name(key);
+            children.push(self.add_node(ns_uri, &name, &name, node, depth + 1));
+        }
+        self.close(index, &children);
+        index
+    }
+
+    /// Add a property node, its qualifiers and its children
+    fn add_node(
+        &mut self,
+        ns_uri: &str,
+        path: &str,
+        leaf_name: &str,
+        node: &Node,
+        depth: usize,
+    ) -> usize {
+        use crate::core::namespace::ns;
+
+        let qualifiers = node.qualifiers();
+        let (kind, value, is_leaf) = match node {
+            Node::Simple(simple) => (XmpNodeKind::Simple, Some(simple.value.clone()), true),
+            Node::Array(array) => (XmpNodeKind::Array(array.array_type), None, array.is_empty()),
+            Node::Structure(structure) => {
+                (XmpNodeKind::Structure, None, structure.fields.is_empty())
+            }
+        };
+        let item = XmpIterItem {
+            namespace_uri: ns_uri.to_string(),
+            path: path.to_string(),
+            kind,
+            value,
+            is_qualifier: false,
+            has_qualifiers: !qualifiers.is_empty(),
+            has_lang: qualifiers
+                .iter()
+                .any(|q| q.namespace == ns::XML && q.name == "lang"),
+        };
+        let index = self.push(item, leaf_name.to_string(), depth, is_leaf);
+        let mut children = Vec::new();
+
+        if !self.omit_qualifiers {
+            for qualifier in qualifiers {
+                let name = format!(
+                    "?{}",
+                    self.prefixed_name(&format!("{}:{}", qualifier.namespace, qualifier.name))
+                );
+                let item = XmpIterItem {
+                    namespace_uri: ns_uri.to_string(),
+                    path: format!("{}/{}", path, name),
+                    kind: XmpNodeKind::Simple,
+                    value: Some(qualifier.value.clone()),
+                    is_qualifier: true,
+                    has_qualifiers: false,
+                    has_lang: false,
+                };
+                let qual_index = self.push(item, name, depth + 1, true);
+                self.close(qual_index, &[]);
+                children.push(qual_index);
+            }
+        }
+
+        match node {
+            Node::Simple(_) => {}
+            Node::Array(array) => {
+                for (i, item) in array.items.iter().enumerate() {
+                    let step = format!("[{}]", i + 1);
+                    let item_path = format!("{}{}", path, step);
+                    children.push(self.add_node(ns_uri, &item_path, &step, item, depth + 1));
+                }
+            }
+            Node::Structure(structure) => {
+                for (key, field) in &structure.fields {
+                    let name = self.prefixed_name(key);
+                    let field_path = format!("{}/{}", path, name);
+                    children.push(self.add_node(ns_uri, &field_path, &name, field, depth + 1));
+                }
+            }
+        }
+
+        self.close(index, &children);
+        index
+    }
+}
+
+/// Group the top-level properties by schema namespace, in document order
+fn group_by_schema(root: &StructureNode) -> Vec<(String, Vec<(&String, &Node)>)> {
+    let mut schemas: indexmap::IndexMap<String, Vec<(&String, &Node)>> = indexmap::IndexMap::new();
+    for (key, node) in &root.fields {
+        let ns_uri = key.rfind(':').map(|pos| &key[..pos]).unwrap_or("");
+        schemas
+            .entry(ns_uri.to_string())
+            .or_default()
+            .push((key, node));
+    }
+    schemas.into_iter().collect()
+}
+
+impl XmpMeta {
+    /// Iterate over the nodes of the metadata tree.
+    ///
+    /// See [`IterOptions`] for how to restrict the iteration to a schema or
+    /// property, or to leaf nodes only. An unknown starting point yields an
+    /// empty iterator.
+    pub fn iter(&self, options: IterOptions) -> XmpIterator {
+        let entries = super::node::root_read_with(&self.root, |root| {
+            let mut flattener = TreeFlattener {
+                namespaces: &self.namespaces,
+                omit_qualifiers: options.omit_qualifiers,
+                entries: Vec::new(),
+            };
+
+            match (&options.schema_ns, &options.property_path) {
+                (Some(namespace), Some(path)) => {
+                    let Some(ns_uri) = self.resolve_namespace_uri(namespace) else {
+                        return Vec::new();
+                    };
+                    let Some((node, _)) = self.get_node_by_path(root, namespace, path) else {
+                        return Vec::new();
+                    };
+                    let Ok(mut parsed) = parse_path(path) else {
+                        return Vec::new();
+                    };
+                    // Prefix the property name; the rest of the path has
+                    // prefixes already
+                    if let Some(PathComponent::Name(name)) = parsed.components.first_mut() {
+                        *name = flattener.prefixed_name(&format!("{}:{}", ns_uri, name));
+                    }
+                    let full_path = build_path(&parsed);
+                    let leaf_name = match parsed.components.as_slice() {
+                        [_] => full_path.clone(),
+                        [.., last] => build_path(&PathComponents {
+                            components: vec![last.clone()],
+                        }),
+                        [] => String::new(),
+                    };
+                    let index = flattener.add_node(&ns_uri, &full_path, &leaf_name, node, 0);
+                    flattener.entries[index].parent_end = flattener.entries.len();
+                }
+                (Some(namespace), None) => {
+                    let Some(ns_uri) = self.resolve_namespace_uri(namespace) else {
+                        return Vec::new();
+                    };
+                    if let Some((_, properties)) = group_by_schema(root)
+                        .into_iter()
+                        .find(|(uri, _)| *uri == ns_uri)
+                    {
+                        let index = flattener.add_schema(&ns_uri, &properties, 0);
+                        flattener.entries[index].parent_end = flattener.entries.len();
+                    }
+                }
+                (None, _) => {
+                    let mut schemas = Vec::new();
+                    for (ns_uri, properties) in group_by_schema(root) {
+                        schemas.push(flattener.add_schema(&ns_uri, &properties, 1));
+                    }
+                    let end = flattener.entries.len();
+                    for index in schemas {
+                        flattener.entries[index].parent_end = end;
+                    }
+                }
+            }
+            flattener.entries
+        });
+
+        XmpIterator {
+            entries,
+            pos: 0,
+            last: None,
+            options,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::namespace::ns;
+
+    fn sample() -> XmpMeta {
+        let mut meta = XmpMeta::new();
+        meta.set_property(ns::XMP, "CreatorTool", "xmpkit".into())
+            .unwrap();
+        meta.append_array_item(ns::DC, "creator", "Alice".into())
+            .unwrap();
+        meta.append_array_item(ns::DC, "creator", "Bob".into())
+            .unwrap();
+        meta.set_localized_text(ns::DC, "title", "", "x-default", "Title")
+            .unwrap();
+        meta.set_struct_field(ns::EXIF, "Flash", "Fired", "True".into())
+            .unwrap();
+        meta
+    }
+
+    #[test]
+    fn test_iterate_all() {
+        let items: Vec<XmpIterItem> = sample().iter(IterOptions::default()).collect();
+        let paths: Vec<&str> = items.iter().map(|i| i.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "",
+                "xmp:CreatorTool",
+                "",
+                "dc:creator",
+                "dc:creator[1]",
+                "dc:creator[2]",
+                "dc:title",
+                "dc:title[1]",
+                "dc:title[1]/?xml:lang",
+                "",
+                "exif:Flash",
+                "exif:Flash/exif:Fired",
+            ]
+        );
+        assert_eq!(items[0].kind, XmpNodeKind::Schema);
+        assert_eq!(items[0].namespace_uri, ns::XMP);
+        assert_eq!(items[3].kind, XmpNodeKind::Array(ArrayType::Ordered));
+        assert_eq!(items[4].value.as_deref(), Some("Alice"));
+        assert!(items[7].has_lang && items[7].has_qualifiers);
+        assert!(items[8].is_qualifier);
+        assert_eq!(items[8].value.as_deref(), Some("x-default"));
+        assert_eq!(items[10].kind, XmpNodeKind::Structure);
+    }
+
+    #[test]
+    fn test_iterate_options() {
+        let meta = sample();
+
+        let schemas: Vec<String> = meta
+            .iter(IterOptions::default().just_children())
+            .map(|i| i.namespace_uri)
+            .collect();
+        assert_eq!(schemas, vec![ns::XMP, ns::DC, ns::EXIF]);
+
+        let leaves: Vec<String> = meta
+            .iter(IterOptions::default().schema("dc").just_leaf_nodes())
+            .map(|i| i.path)
+            .collect();
+        assert_eq!(
+            leaves,
+            vec![
+                "dc:creator[1]",
+                "dc:creator[2]",
+                "dc:title[1]",
+                "dc:title[1]/?xml:lang"
+            ]
+        );
+
+        let names: Vec<String> = meta
+            .iter(
+                IterOptions::default()
+                    .property(ns::DC, "title")
+                    .just_leaf_name(),
+            )
+            .map(|i| i.path)
+            .collect();
+        assert_eq!(names, vec!["dc:title", "[1]", "?xml:lang"]);
+
+        let children: Vec<String> = meta
+            .iter(
+                IterOptions::default()
+                    .property(ns::DC, "creator")
+                    .just_children(),
+            )
+            .map(|i| i.path)
+            .collect();
+        assert_eq!(children, vec!["dc:creator[1]", "dc:creator[2]"]);
+
+        // Nested starting points
+        let paths = |path: &str| -> Vec<String> {
+            meta.iter(IterOptions::default().property(ns::EXIF, path))
+                .map(|i| i.path)
+                .collect()
+        };
+        assert_eq!(paths("Flash/exif:Fired"), vec!["exif:Flash/exif:Fired"]);
+        let items: Vec<String> = meta
+            .iter(IterOptions::default().property(ns::DC, "title[1]"))
+            .map(|i| i.path)
+            .collect();
+        assert_eq!(items, vec!["dc:title[1]", "dc:title[1]/?xml:lang"]);
+
+        assert_eq!(
+            meta.iter(IterOptions::default().property(ns::DC, "missing"))
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_iterate_skip() {
+        let meta = sample();
+
+        let mut iter = meta.iter(IterOptions::default().schema(ns::DC));
+        let mut paths = Vec::new();
+        while let Some(item) = iter.next() {
+            if item.path == "dc:creator" {
+                iter.skip_subtree();
+            }
+            paths.push(item.path);
+        }
+        assert_eq!(
+            paths,
+            vec![
+                "",
+                "dc:creator",
+                "dc:title",
+                "dc:title[1]",
+                "dc:title[1]/?xml:lang"
+            ]
+        );
+
+        let mut iter = meta.iter(IterOptions::default());
+        let mut paths = Vec::new();
+        while let Some(item) = iter.next() {
+            if item.path == "dc:creator[1]" {
+                iter.skip_siblings();
+            }
+            paths.push(item.path);
+        }
+        assert!(paths.contains(&"dc:creator[1]".to_string()));
+        assert!(!paths.contains(&"dc:creator[2]".to_string()));
+        assert!(paths.contains(&"dc:title".to_string()));
+    }
+}
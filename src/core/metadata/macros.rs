@@ -26,19 +26,21 @@ macro_rules! root_read {
 #[cfg(not(feature = "mutli-thread"))]
 #[doc(hidden)]
 macro_rules! root_write {
-    ($root:expr) => {
+    ($root:expr) => {{
+        $crate::core::metadata::node::make_root_unique(&mut $root);
         $crate::core::metadata::node::root_write(&$root)
-    };
+    }};
 }
 
 #[cfg(feature = "mutli-thread")]
 #[doc(hidden)]
 macro_rules! root_write {
-    ($root:expr) => {
+    ($root:expr) => {{
+        $crate::core::metadata::node::make_root_unique(&mut $root);
         $crate::core::metadata::node::root_write(&$root).map_err(|_| {
             $crate::core::error::XmpError::InternalError("Lock poisoned".to_string())
         })?
-    };
+    }};
 }
 
 #[cfg(not(feature = "mutli-thread"))]
@@ -5,19 +5,43 @@
 use crate::core::error::{XmpError, XmpResult};
 use crate::core::namespace::NamespaceMap;
 use crate::core::node::{Node, StructureNode};
-use crate::core::parser::XmpParser;
+use crate::core::parser::{ParseOptions, ParseWarning, XmpParser};
 use crate::core::serializer::XmpSerializer;
-use crate::core::xpath::PathComponent;
+use crate::core::xpath::{PathComponent, PathComponents};
+use crate::types::qualifier::Qualifier;
 use crate::types::value::XmpValue;
+use std::io::Read;
 use std::str::FromStr;
 
+mod iterator;
 mod node;
+mod shared;
 #[macro_use]
 mod macros;
+mod array_text;
+mod diff;
+mod remove;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod template;
+mod validate;
+
+pub use array_text::SeparateOptions;
+pub use diff::{XmpChange, XmpChangeKind, XmpDiff, XmpDiffNode};
+pub use iterator::{IterOptions, XmpIterItem, XmpIterator, XmpNodeKind};
+pub use remove::RemoveOptions;
+pub use shared::{SharedReadGuard, SharedWriteGuard, SharedXmpMeta};
+pub use template::TemplateOptions;
+pub use validate::{SchemaViolation, ViolationKind};
 
 use node::{new_root_node, root_read_with, RootNode};
 
 /// Main structure for working with XMP metadata
+///
+/// `XmpMeta` has value semantics: a clone is an independent copy. Clones
+/// share their data until one of them is modified (copy-on-write), so taking
+/// a snapshot is cheap. Use [`SharedXmpMeta`] for a handle whose changes are
+/// visible to all owners.
 #[derive(Debug, Clone)]
 pub struct XmpMeta {
     /// Root structure node containing all properties
@@ -144,6 +168,25 @@ impl XmpMeta {
         }
     }
 
+    /// Redirect a path that starts with an alias (e.g., `tiff:Artist`) to its base node
+    ///
+    /// Returns the namespace and path components to use instead. Paths that
+    /// don't start with a registered alias are returned unchanged.
+    fn follow_alias(&self, namespace: &str, parsed: PathComponents) -> (String, PathComponents) {
+        let alias = match (parsed.first_name(), self.resolve_namespace_uri(namespace)) {
+            (Some(name), Some(ns_uri)) => crate::core::alias::resolve_alias(&ns_uri, name),
+            _ => None,
+        };
+        match alias {
+            Some(info) => {
+                let mut components = info.base_components().components;
+                components.extend(parsed.components.into_iter().skip(1));
+                (info.namespace_uri, PathComponents { components })
+            }
+            None => (namespace.to_string(), parsed),
+        }
+    }
+
     /// Parse XMP metadata from a string
     ///
     /// The string should contain a complete XMP Packet (with or without
@@ -151,13 +194,125 @@ impl XmpMeta {
     pub fn parse(s: &str) -> XmpResult<Self> {
         let mut parser = XmpParser::new();
         let root_node = parser.parse_packet(s)?;
-        let namespaces = parser.namespace_map();
+        Ok(Self::from_parsed(&parser, root_node))
+    }
+
+    /// Parse XMP metadata from bytes
+    ///
+    /// The bytes should contain a complete XMP Packet (with or without the
+    /// `<?xpacket>` wrapper) encoded in UTF-8, UTF-16 or UTF-32. The encoding
+    /// is detected from the byte order mark, which XMP writers put in the
+    /// `begin` attribute of the `<?xpacket>` header, or from the first
+    /// characters of the packet.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use xmpkit::XmpMeta;
+    ///
+    /// let packet = r#"<?xpacket begin="\u{FEFF}" id="W5M0MpCehiHzreSzNTczkc9d"?>
+    /// <x:xmpmeta xmlns:x="adobe:ns:meta/">
+    ///   <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+    ///     <rdf:Description rdf:about="" xmlns:xmp="http://ns.adobe.com/xap/1.0/"
+    ///       xmp:Rating="5"/>
+    ///   </rdf:RDF>
+    /// </x:xmpmeta>
+    /// <?xpacket end="w"?>"#;
+    ///
+    /// // A UTF-16LE packet, as written by some Windows applications
+    /// let bytes: Vec<u8> = packet.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    /// let meta = XmpMeta::parse_bytes(&bytes).unwrap();
+    /// assert_eq!(meta.get_property("xmp", "Rating").unwrap().to_string(), "5");
+    /// ```
+    pub fn parse_bytes(bytes: &[u8]) -> XmpResult<Self> {
+        let mut parser = XmpParser::new();
+        let root_node = parser.parse_bytes(bytes)?;
+        Ok(Self::from_parsed(&parser, root_node))
+    }
+
+    /// Parse XMP metadata from a reader
+    ///
+    /// Like [`parse_bytes`](Self::parse_bytes), but the packet is decoded and
+    /// parsed while it is read, without holding its text in memory.
+    pub fn parse_reader(reader: impl Read) -> XmpResult<Self> {
+        let mut parser = XmpParser::new();
+        let root_node = parser.parse_reader(reader)?;
+        Ok(Self::from_parsed(&parser, root_node))
+    }
+
+    /// Parse XMP metadata from a string with options
+    ///
+    /// Returns the metadata and the problems worked around in lenient mode.
+    /// See [`ParseOptions`] for what strict and lenient mode do.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use xmpkit::core::parser::{ParseOptions, ParseWarningKind};
+    /// use xmpkit::XmpMeta;
+    ///
+    /// // A bare '&' and an undeclared prefix, as written by some cameras
+    /// let packet = r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+    ///   <rdf:Description rdf:about="">
+    ///     <photoshop:Credit>Smith & Sons</photoshop:Credit>
+    ///   </rdf:Description>
+    /// </rdf:RDF>"#;
+    /// assert!(XmpMeta::parse(packet).is_err());
+    ///
+    /// let options = ParseOptions::default().lenient();
+    /// let (meta, warnings) = XmpMeta::parse_with_options(packet, &options).unwrap();
+    /// assert_eq!(
+    ///     meta.get_property("photoshop", "Credit").unwrap().to_string(),
+    ///     "Smith & Sons"
+    /// );
+    /// assert_eq!(warnings[0].kind, ParseWarningKind::UndeclaredPrefix);
+    /// assert_eq!(warnings[1].kind, ParseWarningKind::BareAmpersand);
+    /// ```
+    pub fn parse_with_options(
+        s: &str,
+        options: &ParseOptions,
+    ) -> XmpResult<(Self, Vec<ParseWarning>)> {
+        let mut parser = XmpParser::with_options(options.clone());
+        let root_node = parser.parse_packet(s)?;
+        let meta = Self::from_parsed(&parser, root_node);
+        Ok((meta, parser.take_warnings()))
+    }
+
+    /// Parse XMP metadata from bytes with options
+    ///
+    /// See [`parse_bytes`](Self::parse_bytes) and
+    /// [`parse_with_options`](Self::parse_with_options).
+    pub fn parse_bytes_with_options(
+        bytes: &[u8],
+        options: &ParseOptions,
+    ) -> XmpResult<(Self, Vec<ParseWarning>)> {
+        let mut parser = XmpParser::with_options(options.clone());
+        let root_node = parser.parse_bytes(bytes)?;
+        let meta = Self::from_parsed(&parser, root_node);
+        Ok((meta, parser.take_warnings()))
+    }
 
-        Ok(Self {
+    /// Parse XMP metadata from a reader with options
+    ///
+    /// See [`parse_reader`](Self::parse_reader) and
+    /// [`parse_with_options`](Self::parse_with_options).
+    pub fn parse_reader_with_options(
+        reader: impl Read,
+        options: &ParseOptions,
+    ) -> XmpResult<(Self, Vec<ParseWarning>)> {
+        let mut parser = XmpParser::with_options(options.clone());
+        let root_node = parser.parse_reader(reader)?;
+        let meta = Self::from_parsed(&parser, root_node);
+        Ok((meta, parser.take_warnings()))
+    }
+
+    fn from_parsed(parser: &XmpParser, mut root_node: StructureNode) -> Self {
+        crate::core::alias::normalize_aliases(&mut root_node);
+        Self {
             root: new_root_node(root_node),
-            namespaces,
-            about_uri: None,
-        })
+            namespaces: parser.namespace_map(),
+            about_uri: parser.about_uri().map(String::from),
+        }
     }
 
     /// Check if a property exists
@@ -168,22 +323,52 @@ impl XmpMeta {
     /// * `path` - The property path
     pub fn has_property(&self, namespace: &str, path: &str) -> bool {
         root_read_with(&self.root, |root| {
-            self.get_node_by_path(root, namespace, path).is_some()
+            let Ok(parsed) = crate::core::xpath::parse_path(path) else {
+                return false;
+            };
+            let (namespace, parsed) = self.follow_alias(namespace, parsed);
+            let namespace = namespace.as_str();
+            match split_qualifier_step(&parsed.components) {
+                (components, Some(qual_name)) => self
+                    .get_node_by_components(root, namespace, components)
+                    .and_then(|(node, ns_uri)| {
+                        let (qual_ns, qual_local) =
+                            self.resolve_qualifier_name(&ns_uri, qual_name)?;
+                        node.get_qualifier(&qual_ns, &qual_local)
+                    })
+                    .is_some(),
+                (components, None) => self
+                    .get_node_by_components(root, namespace, components)
+                    .is_some(),
+            }
         })
     }
 
-    /// Get a property value. It will return an `XmpValue::Array(_)`,
-    /// `XmpValue::Structure(_)` or an `XmpValue::String(_)`.
+    /// Get a property value. It will return an `XmpValue::Array(_, _)`,
+    /// `XmpValue::AltText(_)`, `XmpValue::Structure(_)` or an `XmpValue::String(_)`.
+    ///
+    /// A path ending in a qualifier step (e.g., "creator\[1\]/?role") returns
+    /// the qualifier value as an `XmpValue::String(_)`.
     ///
     /// # Arguments
     ///
     /// * `namespace` - The namespace URI or prefix
     /// * `path` - The property path (e.g., "CreatorTool" or "creator\[1\]")
     pub fn get_property(&self, namespace: &str, path: &str) -> Option<XmpValue> {
+        let parsed = crate::core::xpath::parse_path(path).ok()?;
+        let (namespace, parsed) = self.follow_alias(namespace, parsed);
         let root = root_read_opt!(self.root);
-        let (node, _) = self.get_node_by_path(&root, namespace, path)?;
-
-        Some(node.into())
+        let (components, qual_name) = split_qualifier_step(&parsed.components);
+        let (node, ns_uri) = self.get_node_by_components(&root, &namespace, components)?;
+
+        match qual_name {
+            Some(qual_name) => {
+                let (qual_ns, qual_local) = self.resolve_qualifier_name(&ns_uri, qual_name)?;
+                let qualifier = node.get_qualifier(&qual_ns, &qual_local)?;
+                Some(XmpValue::String(qualifier.value.clone()))
+            }
+            None => Some(node.into()),
+        }
     }
 
     /// Set a property value
@@ -194,12 +379,40 @@ impl XmpMeta {
     /// * `path` - The property path
     /// * `value` - The value to set
     pub fn set_property(&mut self, namespace: &str, path: &str, value: XmpValue) -> XmpResult<()> {
-        let ns_uri = self.resolve_namespace_uri_or_error(namespace)?;
         let parsed = crate::core::xpath::parse_path(path)?;
+        let (namespace, parsed) = self.follow_alias(namespace, parsed);
+        let namespace = namespace.as_str();
+        let ns_uri = self.resolve_namespace_uri_or_error(namespace)?;
+        if let (components, Some(qual_name)) = split_qualifier_step(&parsed.components) {
+            let value = match value_to_node(value, &ns_uri, &self.namespaces)? {
+                Node::Simple(simple) => simple.value,
+                _ => {
+                    return Err(XmpError::BadValue(
+                        "Qualifier values must be simple".to_string(),
+                    ))
+                }
+            };
+            return self.set_qualifier_at(namespace, components, qual_name, None, &value);
+        }
+        self.put_node(ns_uri, &parsed.components, |ns_uri, namespaces| {
+            value_to_node(value, ns_uri, namespaces)
+        })
+    }
+
+    /// Store a node at a parsed path, creating missing parents
+    ///
+    /// `make_node` receives the namespace URI the node will live in (which
+    /// differs from `ns_uri` for prefixed structure fields).
+    fn put_node(
+        &mut self,
+        ns_uri: String,
+        components: &[PathComponent],
+        make_node: impl FnOnce(&str, &NamespaceMap) -> XmpResult<Node>,
+    ) -> XmpResult<()> {
         let mut root = root_write!(self.root);
 
-        if parsed.components.len() == 1 {
-            let name = match &parsed.components[0] {
+        if components.len() == 1 {
+            let name = match &components[0] {
                 PathComponent::Name(name) => name,
                 _ => {
                     return Err(XmpError::BadXPath(
@@ -208,13 +421,13 @@ impl XmpMeta {
                 }
             };
             let key = format!("{}:{}", ns_uri, name);
-            let node = value_to_node(value, &ns_uri, &self.namespaces)?;
+            let node = make_node(&ns_uri, &self.namespaces)?;
             root.set_field(key, node);
         } else {
-            let parent_components = &parsed.components[..parsed.components.len() - 1];
+            let parent_components = &components[..components.len() - 1];
             let parent_node =
                 get_or_create_node(&mut root, &self.namespaces, &ns_uri, parent_components)?;
-            let last_comp = parsed.components.last().unwrap();
+            let last_comp = components.last().unwrap();
             match last_comp {
                 PathComponent::Name(name) => {
                     let structure = parent_node.as_structure_mut().ok_or_else(|| {
@@ -233,26 +446,40 @@ impl XmpMeta {
                         resolved_uri
                     };
                     let key = self.resolve_field_key(&parent_ns_uri, name);
-                    let node = value_to_node(value, &parent_ns_uri, &self.namespaces)?;
+                    let node = make_node(&parent_ns_uri, &self.namespaces)?;
                     structure.set_field(key, node);
                 }
-                PathComponent::Index(idx) => {
-                    if *idx == 0 {
-                        return Err(XmpError::BadXPath(
-                            "Array index must be 1 or greater".to_string(),
-                        ));
+                PathComponent::Index(_)
+                | PathComponent::Last
+                | PathComponent::QualifierSelector { .. }
+                | PathComponent::FieldSelector { .. } => {
+                    // A parent created just now for this path is an empty structure
+                    if matches!(&*parent_node, Node::Structure(s) if s.fields.is_empty()) {
+                        *parent_node = default_container(Some(last_comp));
                     }
-                    let idx_0 = *idx - 1;
                     let array = parent_node
                         .as_array_mut()
                         .ok_or_else(|| XmpError::BadValue("Parent is not an array".to_string()))?;
-                    let node = value_to_node(value, &ns_uri, &self.namespaces)?;
-                    while array.len() <= idx_0 {
-                        array.append(Node::simple(""));
-                    }
-                    if let Some(item) = array.get_mut(idx_0) {
-                        *item = node;
+                    let mut node = make_node(&ns_uri, &self.namespaces)?;
+                    let index = find_or_append_array_item(
+                        array,
+                        last_comp,
+                        &self.namespaces,
+                        &ns_uri,
+                        Node::simple(""),
+                    )?;
+                    let item = array.get_mut(index).unwrap();
+                    // Keep the qualifiers that selected the item (e.g., xml:lang)
+                    if !matches!(last_comp, PathComponent::Index(_)) && node.qualifiers().is_empty()
+                    {
+                        *node.qualifiers_mut() = item.qualifiers().to_vec();
                     }
+                    *item = node;
+                }
+                PathComponent::Qualifier(_) => {
+                    return Err(XmpError::BadXPath(
+                        "Qualifier must be the last path step".to_string(),
+                    ))
                 }
             }
         }
@@ -266,8 +493,13 @@ impl XmpMeta {
     /// * `namespace` - The namespace URI or prefix
     /// * `path` - The property path
     pub fn delete_property(&mut self, namespace: &str, path: &str) -> XmpResult<()> {
-        let ns_uri = self.resolve_namespace_uri_or_error(namespace)?;
         let parsed = crate::core::xpath::parse_path(path)?;
+        let (namespace, parsed) = self.follow_alias(namespace, parsed);
+        let namespace = namespace.as_str();
+        let ns_uri = self.resolve_namespace_uri_or_error(namespace)?;
+        if let (components, Some(qual_name)) = split_qualifier_step(&parsed.components) {
+            return self.delete_qualifier_at(namespace, components, qual_name, None);
+        }
         let mut root = root_write!(self.root);
 
         if parsed.components.is_empty() {
@@ -310,21 +542,189 @@ impl XmpMeta {
                             structure.remove_field(&key);
                         }
                     }
-                    PathComponent::Index(idx) => {
-                        if *idx > 0 {
-                            if let Some(array) = parent_node.as_array_mut() {
-                                if array.len() >= *idx {
-                                    let _ = array.remove(*idx - 1);
-                                }
+                    PathComponent::Index(_)
+                    | PathComponent::Last
+                    | PathComponent::QualifierSelector { .. }
+                    | PathComponent::FieldSelector { .. } => {
+                        if let Some(array) = parent_node.as_array_mut() {
+                            if let Some(index) =
+                                find_array_item(array, last_comp, &self.namespaces, &ns_uri)
+                            {
+                                let _ = array.remove(index);
                             }
                         }
                     }
+                    PathComponent::Qualifier(_) => {}
                 }
             }
         }
         Ok(())
     }
 
+    /// Copy a property subtree from another metadata object
+    ///
+    /// Unlike reading the source with [`get_property`](Self::get_property) and
+    /// setting it again, the node is copied as-is: array forms, qualifiers and
+    /// nested namespaces are kept. Missing parents of `dest_path` are created
+    /// and an existing destination is replaced. Prefixes the source uses for
+    /// namespaces unknown to this object are registered as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The metadata to copy from
+    /// * `src_ns` - The namespace URI or prefix of the source property
+    /// * `src_path` - The source property path
+    /// * `dest_ns` - The namespace URI or prefix of the destination property
+    /// * `dest_path` - The destination property path
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use xmpkit::XmpMeta;
+    ///
+    /// let mut source = XmpMeta::new();
+    /// source.append_array_item("dc", "subject", "sunset".into()).unwrap();
+    /// source.set_qualifier("dc", "subject[1]", "xmp", "Rating", "5").unwrap();
+    ///
+    /// let mut dest = XmpMeta::new();
+    /// dest.copy_subtree(&source, "dc", "subject", "dc", "subject").unwrap();
+    /// assert!(dest.has_qualifier("dc", "subject[1]", "xmp", "Rating"));
+    /// ```
+    pub fn copy_subtree(
+        &mut self,
+        source: &XmpMeta,
+        src_ns: &str,
+        src_path: &str,
+        dest_ns: &str,
+        dest_path: &str,
+    ) -> XmpResult<()> {
+        let src_ns_uri = source.resolve_namespace_uri_or_error(src_ns)?;
+        let node = root_read_with(&source.root, |root| {
+            source
+                .get_node_by_path(root, src_ns, src_path)
+                .map(|(node, _)| node.clone())
+        })
+        .ok_or_else(|| {
+            XmpError::BadValue(format!("Property '{}:{}' not found", src_ns, src_path))
+        })?;
+
+        let mut uris = vec![src_ns_uri];
+        collect_namespace_uris(&node, &mut uris);
+        self.adopt_namespaces(source, &uris);
+
+        let dest_ns_uri = self.resolve_namespace_uri_or_error(dest_ns)?;
+        let parsed = crate::core::xpath::parse_path(dest_path)?;
+        if split_qualifier_step(&parsed.components).1.is_some() {
+            return Err(XmpError::BadXPath(
+                "Cannot copy a subtree onto a qualifier".to_string(),
+            ));
+        }
+        self.put_node(dest_ns_uri, &parsed.components, |_, _| Ok(node))
+    }
+
+    /// Copy all properties of a schema from another metadata object
+    ///
+    /// Properties are copied as nodes (see [`copy_subtree`](Self::copy_subtree)).
+    /// Properties of the schema that exist here but not in `source` are kept;
+    /// properties present in both are replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The metadata to copy from
+    /// * `namespace` - The namespace URI or prefix of the schema
+    pub fn copy_schema(&mut self, source: &XmpMeta, namespace: &str) -> XmpResult<()> {
+        let ns_uri = source.resolve_namespace_uri_or_error(namespace)?;
+        let fields: Vec<(String, Node)> = root_read_with(&source.root, |root| {
+            root.fields
+                .iter()
+                .filter(|(key, _)| key.rfind(':').map(|pos| &key[..pos]) == Some(&ns_uri))
+                .map(|(key, node)| (key.clone(), node.clone()))
+                .collect()
+        });
+
+        let mut uris = vec![ns_uri];
+        for (_, node) in &fields {
+            collect_namespace_uris(node, &mut uris);
+        }
+        self.adopt_namespaces(source, &uris);
+
+        let mut root = root_write!(self.root);
+        for (key, node) in fields {
+            root.set_field(key, node);
+        }
+        Ok(())
+    }
+
+    /// Register the prefixes `source` uses for namespaces unknown to this object
+    fn adopt_namespaces(&mut self, source: &XmpMeta, uris: &[String]) {
+        for uri in uris {
+            if self.namespaces.has_uri(uri) {
+                continue;
+            }
+            if let Some(prefix) = source.namespaces.get_prefix(uri) {
+                // A prefix clash leaves the URI to be resolved through the global registry
+                let _ = self.namespaces.register(uri, prefix);
+            }
+        }
+    }
+
+    /// Build a metadata object whose top-level properties are the fields of
+    /// the structure at `path` (e.g., an `xmpMM:Pantry` entry)
+    pub(crate) fn structure_as_meta(&self, namespace: &str, path: &str) -> Option<XmpMeta> {
+        let root = root_read_opt!(self.root);
+        let (node, _) = self.get_node_by_path(&root, namespace, path)?;
+        let structure = node.as_structure()?.clone();
+        Some(Self {
+            root: new_root_node(structure),
+            namespaces: self.namespaces.clone(),
+            about_uri: None,
+        })
+    }
+
+    /// Check whether two metadata objects mean the same thing
+    ///
+    /// Unlike a comparison of serialized packets, this ignores the order of
+    /// `rdf:Bag` items, structure fields and qualifiers, the namespace prefixes
+    /// in use, and whitespace around values. Array forms and qualifiers are
+    /// compared. The about URI is not part of the comparison.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use xmpkit::XmpMeta;
+    ///
+    /// let mut a = XmpMeta::new();
+    /// a.append_array_item("dc", "subject", "sunset".into()).unwrap();
+    /// a.append_array_item("dc", "subject", "beach".into()).unwrap();
+    ///
+    /// let b = XmpMeta::parse(&a.serialize_packet().unwrap()).unwrap();
+    /// assert!(a.semantically_eq(&b));
+    /// ```
+    pub fn semantically_eq(&self, other: &XmpMeta) -> bool {
+        self.canonical_bytes() == other.canonical_bytes()
+    }
+
+    /// Get the canonical encoding of the metadata
+    ///
+    /// Metadata objects that are [`semantically_eq`](Self::semantically_eq)
+    /// have the same canonical bytes, so they can be fed to any digest.
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        root_read_with(&self.root, |root| {
+            let mut out = Vec::new();
+            crate::core::canonical::encode_fields(root, &mut out);
+            out
+        })
+    }
+
+    /// Get a stable 128-bit content hash of the metadata
+    ///
+    /// The hash is an FNV-1a digest of [`canonical_bytes`](Self::canonical_bytes).
+    /// It does not depend on the Rust version or platform, so it can be stored
+    /// (e.g., for deduplication or to skip rewriting unchanged metadata).
+    pub fn canonical_hash(&self) -> u128 {
+        crate::core::canonical::fnv1a_128(&self.canonical_bytes())
+    }
+
     /// Get the about URI
     pub fn about_uri(&self) -> Option<&str> {
         self.about_uri.as_deref()
@@ -335,16 +735,21 @@ impl XmpMeta {
         self.about_uri = Some(uri.into());
     }
 
+    fn serializer(&self) -> XmpSerializer {
+        XmpSerializer::with_namespaces(self.namespaces.clone())
+            .with_about_uri(self.about_uri.as_deref().unwrap_or_default())
+    }
+
     /// Serialize to RDF/XML string
     pub fn serialize(&self) -> XmpResult<String> {
-        let serializer = XmpSerializer::with_namespaces(self.namespaces.clone());
+        let serializer = self.serializer();
         let root = root_read!(self.root);
         serializer.serialize_rdf(&root)
     }
 
     /// Serialize to XMP Packet format
     pub fn serialize_packet(&self) -> XmpResult<String> {
-        let serializer = XmpSerializer::with_namespaces(self.namespaces.clone());
+        let serializer = self.serializer();
         let root = root_read!(self.root);
         serializer.serialize_packet(&root)
     }
@@ -363,7 +768,7 @@ impl XmpMeta {
     /// * `Ok(String)` - The serialized packet with padding
     /// * `Err(XmpError)` - If the serialized packet exceeds target_length
     pub fn serialize_packet_with_padding(&self, target_length: usize) -> XmpResult<String> {
-        let serializer = XmpSerializer::with_namespaces(self.namespaces.clone());
+        let serializer = self.serializer();
         let root = root_read!(self.root);
         serializer.serialize_packet_with_padding(&root, target_length)
     }
@@ -617,28 +1022,40 @@ impl XmpMeta {
         namespace: &str,
         path: &str,
     ) -> Option<(&'a Node, String)> {
-        let ns_uri = self.resolve_namespace_uri(namespace)?;
         let parsed = crate::core::xpath::parse_path(path).ok()?;
-        if parsed.components.is_empty() {
+        self.get_node_by_components(root, namespace, &parsed.components)
+    }
+
+    /// Navigate the path components and return the node and the last resolved namespace URI
+    fn get_node_by_components<'a>(
+        &self,
+        root: &'a StructureNode,
+        namespace: &str,
+        components: &[PathComponent],
+    ) -> Option<(&'a Node, String)> {
+        let ns_uri = self.resolve_namespace_uri(namespace)?;
+        if components.is_empty() {
             return None;
         }
 
         let mut current_ns_uri = ns_uri;
-        let first_name = match &parsed.components[0] {
+        let first_name = match &components[0] {
             PathComponent::Name(name) => name,
             _ => return None,
         };
         let first_key = format!("{}:{}", current_ns_uri, first_name);
         let mut current = root.get_field(&first_key)?;
 
-        for i in 1..parsed.components.len() {
-            match &parsed.components[i] {
-                PathComponent::Index(idx) => {
-                    if *idx == 0 {
-                        return None;
-                    }
+        for component in components.iter().skip(1) {
+            match component {
+                PathComponent::Index(_)
+                | PathComponent::Last
+                | PathComponent::QualifierSelector { .. }
+                | PathComponent::FieldSelector { .. } => {
                     let array = current.as_array()?;
-                    current = array.get(*idx - 1)?;
+                    let index =
+                        find_array_item(array, component, &self.namespaces, &current_ns_uri)?;
+                    current = array.get(index)?;
                 }
                 PathComponent::Name(name) => {
                     let structure = current.as_structure()?;
@@ -656,11 +1073,12 @@ impl XmpMeta {
                     };
                     current = structure.get_field(&key)?;
                 }
+                PathComponent::Qualifier(_) => return None,
             }
         }
 
         let mut resolved_uri = current_ns_uri;
-        if let Some(PathComponent::Name(name)) = parsed.components.last() {
+        if let Some(PathComponent::Name(name)) = components.last() {
             if let Some(colon_pos) = name.find(':') {
                 let prefix = &name[..colon_pos];
                 if let Some(uri) = self.resolve_namespace_uri(prefix) {
@@ -694,12 +1112,14 @@ impl XmpMeta {
 
         for component in components.iter().skip(1) {
             let next = match component {
-                PathComponent::Index(idx) => {
-                    if *idx == 0 {
-                        return None;
-                    }
+                PathComponent::Index(_)
+                | PathComponent::Last
+                | PathComponent::QualifierSelector { .. }
+                | PathComponent::FieldSelector { .. } => {
                     let array = current.as_array_mut()?;
-                    array.get_mut(*idx - 1)?
+                    let index =
+                        find_array_item(array, component, &self.namespaces, &current_ns_uri)?;
+                    array.get_mut(index)?
                 }
                 PathComponent::Name(name) => {
                     let structure = current.as_structure_mut()?;
@@ -717,6 +1137,7 @@ impl XmpMeta {
                     };
                     structure.get_field_mut(&key)?
                 }
+                PathComponent::Qualifier(_) => return None,
             };
             current = next;
         }
@@ -746,11 +1167,243 @@ impl XmpMeta {
         format!("{}:{}", struct_ns_uri, field_name)
     }
 
+    /// Resolve a qualifier name (`name` or `prefix:name`) into its namespace URI and local name
+    fn resolve_qualifier_name(
+        &self,
+        default_ns_uri: &str,
+        qual_name: &str,
+    ) -> Option<(String, String)> {
+        match qual_name.find(':') {
+            Some(colon_pos) => {
+                let uri = self.resolve_namespace_uri(&qual_name[..colon_pos])?;
+                Some((uri, qual_name[colon_pos + 1..].to_string()))
+            }
+            None => Some((default_ns_uri.to_string(), qual_name.to_string())),
+        }
+    }
+
+    /// Set a qualifier on the node at `components`.
+    ///
+    /// The qualifier namespace comes from `qual_namespace` if given, otherwise
+    /// from the prefix of `qual_name` or the namespace of the property itself.
+    fn set_qualifier_at(
+        &mut self,
+        namespace: &str,
+        components: &[PathComponent],
+        qual_name: &str,
+        qual_namespace: Option<&str>,
+        value: &str,
+    ) -> XmpResult<()> {
+        let qual_ns_uri = match qual_namespace {
+            Some(qual_namespace) => Some(self.resolve_namespace_uri_or_error(qual_namespace)?),
+            None => match qual_name.find(':') {
+                Some(colon_pos) => {
+                    Some(self.resolve_namespace_uri_or_error(&qual_name[..colon_pos])?)
+                }
+                None => None,
+            },
+        };
+        let qual_local = qual_name.rsplit(':').next().unwrap_or(qual_name);
+        if qual_local.is_empty() {
+            return Err(XmpError::BadXPath("Empty qualifier name".to_string()));
+        }
+
+        let mut root = root_write!(self.root);
+        let (node, node_ns_uri) = self
+            .get_node_by_components_mut(&mut root, namespace, components)
+            .ok_or_else(|| {
+                XmpError::BadValue(format!(
+                    "Property '{}:{}' not found",
+                    namespace,
+                    crate::core::xpath::build_path(&crate::core::xpath::PathComponents {
+                        components: components.to_vec(),
+                    })
+                ))
+            })?;
+        let qual_ns_uri = qual_ns_uri.unwrap_or(node_ns_uri);
+        node.set_qualifier(Qualifier::new(qual_ns_uri, qual_local, value));
+        Ok(())
+    }
+
+    /// Delete a qualifier from the node at `components`, if both exist
+    fn delete_qualifier_at(
+        &mut self,
+        namespace: &str,
+        components: &[PathComponent],
+        qual_name: &str,
+        qual_namespace: Option<&str>,
+    ) -> XmpResult<()> {
+        let qual_ns_uri = match qual_namespace {
+            Some(qual_namespace) => Some(self.resolve_namespace_uri_or_error(qual_namespace)?),
+            None => match qual_name.find(':') {
+                Some(colon_pos) => {
+                    Some(self.resolve_namespace_uri_or_error(&qual_name[..colon_pos])?)
+                }
+                None => None,
+            },
+        };
+        let qual_local = qual_name.rsplit(':').next().unwrap_or(qual_name);
+
+        let mut root = root_write!(self.root);
+        if let Some((node, node_ns_uri)) =
+            self.get_node_by_components_mut(&mut root, namespace, components)
+        {
+            let qual_ns_uri = qual_ns_uri.unwrap_or(node_ns_uri);
+            node.remove_qualifier(&qual_ns_uri, qual_local);
+        }
+        Ok(())
+    }
+
+    /// Get a qualifier value
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - The namespace URI or prefix of the property
+    /// * `path` - The property path (e.g., "creator\[1\]")
+    /// * `qual_namespace` - The namespace URI or prefix of the qualifier
+    /// * `qual_name` - The qualifier name
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use xmpkit::{XmpMeta, XmpValue};
+    ///
+    /// let mut meta = XmpMeta::new();
+    /// meta.append_array_item("dc", "creator", "Alice".into()).unwrap();
+    /// meta.set_qualifier("dc", "creator[1]", "xmp", "Role", "author").unwrap();
+    ///
+    /// assert_eq!(
+    ///     meta.get_qualifier("dc", "creator[1]", "xmp", "Role"),
+    ///     Some(XmpValue::String("author".to_string()))
+    /// );
+    /// assert_eq!(
+    ///     meta.get_property("dc", "creator[1]/?xmp:Role"),
+    ///     Some(XmpValue::String("author".to_string()))
+    /// );
+    /// ```
+    pub fn get_qualifier(
+        &self,
+        namespace: &str,
+        path: &str,
+        qual_namespace: &str,
+        qual_name: &str,
+    ) -> Option<XmpValue> {
+        let qual_ns_uri = self.resolve_namespace_uri(qual_namespace)?;
+        let root = root_read_opt!(self.root);
+        let (node, _) = self.get_node_by_path(&root, namespace, path)?;
+        node.get_qualifier(&qual_ns_uri, qual_name)
+            .map(|q| XmpValue::String(q.value.clone()))
+    }
+
+    /// Set a qualifier on an existing property
+    ///
+    /// An existing qualifier with the same name is replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - The namespace URI or prefix of the property
+    /// * `path` - The property path
+    /// * `qual_namespace` - The namespace URI or prefix of the qualifier
+    /// * `qual_name` - The qualifier name
+    /// * `value` - The qualifier value
+    pub fn set_qualifier(
+        &mut self,
+        namespace: &str,
+        path: &str,
+        qual_namespace: &str,
+        qual_name: &str,
+        value: &str,
+    ) -> XmpResult<()> {
+        self.resolve_namespace_uri_or_error(namespace)?;
+        let parsed = crate::core::xpath::parse_path(path)?;
+        self.set_qualifier_at(
+            namespace,
+            &parsed.components,
+            qual_name,
+            Some(qual_namespace),
+            value,
+        )
+    }
+
+    /// Delete a qualifier
+    ///
+    /// Does nothing if the property or the qualifier does not exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - The namespace URI or prefix of the property
+    /// * `path` - The property path
+    /// * `qual_namespace` - The namespace URI or prefix of the qualifier
+    /// * `qual_name` - The qualifier name
+    pub fn delete_qualifier(
+        &mut self,
+        namespace: &str,
+        path: &str,
+        qual_namespace: &str,
+        qual_name: &str,
+    ) -> XmpResult<()> {
+        self.resolve_namespace_uri_or_error(namespace)?;
+        let parsed = crate::core::xpath::parse_path(path)?;
+        self.delete_qualifier_at(
+            namespace,
+            &parsed.components,
+            qual_name,
+            Some(qual_namespace),
+        )
+    }
+
+    /// Check if a qualifier exists
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - The namespace URI or prefix of the property
+    /// * `path` - The property path
+    /// * `qual_namespace` - The namespace URI or prefix of the qualifier
+    /// * `qual_name` - The qualifier name
+    pub fn has_qualifier(
+        &self,
+        namespace: &str,
+        path: &str,
+        qual_namespace: &str,
+        qual_name: &str,
+    ) -> bool {
+        self.get_qualifier(namespace, path, qual_namespace, qual_name)
+            .is_some()
+    }
+
+    /// Get all qualifiers of a property, in document order
+    ///
+    /// Returns an empty list if the property does not exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - The namespace URI or prefix of the property
+    /// * `path` - The property path
+    pub fn qualifiers(&self, namespace: &str, path: &str) -> Vec<Qualifier> {
+        root_read_with(&self.root, |root| {
+            self.get_node_by_path(root, namespace, path)
+                .map(|(node, _)| node.qualifiers().to_vec())
+                .unwrap_or_default()
+        })
+    }
+
     /// Set a localized text property
     ///
     /// Localized text properties are stored as `rdf:Alt` arrays, where each item
     /// has an `xml:lang` qualifier indicating its language.
     ///
+    /// This follows the Adobe XMP Toolkit rules:
+    /// - An item matching `specific_lang` is updated, along with the
+    ///   `x-default` item if it held the same value. Setting `x-default`
+    ///   updates every item that duplicated the old default value.
+    /// - Otherwise, if exactly one item matches `generic_lang`, that item is
+    ///   updated, along with a duplicated `x-default`.
+    /// - Otherwise a new item is added for `specific_lang`.
+    /// - An `x-default` item is added when the array had no items, and is
+    ///   always kept first.
+    ///
+    /// Language tags are normalized (e.g., `EN-us` becomes `en-US`).
+    ///
     /// # Arguments
     ///
     /// * `namespace` - The namespace URI or prefix
@@ -777,14 +1430,19 @@ impl XmpMeta {
         &mut self,
         namespace: &str,
         property: &str,
-        _generic_lang: &str,
+        generic_lang: &str,
         specific_lang: &str,
         value: &str,
     ) -> XmpResult<()> {
-        use crate::core::namespace::ns;
         use crate::core::node::{ArrayNode, ArrayType, Node};
-        use crate::types::qualifier::Qualifier;
 
+        if specific_lang.is_empty() {
+            return Err(XmpError::BadParam(
+                "Specific language must not be empty".to_string(),
+            ));
+        }
+        let generic_lang = normalize_lang(generic_lang);
+        let specific_lang = normalize_lang(specific_lang);
         let ns_uri = self.resolve_namespace_uri_or_error(namespace)?;
 
         let full_path = format!("{}:{}", ns_uri, property);
@@ -815,31 +1473,56 @@ impl XmpMeta {
                 })?
         };
 
-        // Find existing item with matching specific_lang
-        let mut found = false;
-        for item in &mut array.items {
-            let Some(simple) = item.as_simple_mut() else {
-                continue;
-            };
-            let Some(lang_qual) = simple.get_qualifier(ns::XML, "lang") else {
-                continue;
-            };
-            if lang_qual.value == specific_lang {
-                // Update existing item
-                simple.value = value.to_string();
-                found = true;
-                break;
+        // Make sure the x-default item, if any, is first
+        if let Some(pos) = array
+            .items
+            .iter()
+            .position(|item| item_lang(item).as_deref() == Some(X_DEFAULT))
+        {
+            let item = array.items.remove(pos);
+            array.items.insert(0, item);
+        }
+        let x_default = match array.items.first() {
+            Some(item) if item_lang(item).as_deref() == Some(X_DEFAULT) => {
+                item.as_simple().map(|simple| simple.value.clone())
+            }
+            _ => None,
+        };
+        let mut have_x_default = x_default.is_some();
+        let is_x_default = specific_lang == X_DEFAULT;
+
+        match choose_localized_item(array, &generic_lang, &specific_lang) {
+            Some((LangMatch::Specific, index)) if is_x_default => {
+                // Update all items that duplicated the old x-default value
+                let old_value = x_default.unwrap_or_default();
+                for item in array.items.iter_mut().skip(1) {
+                    if let Some(simple) = item.as_simple_mut() {
+                        if simple.value == old_value {
+                            simple.value = value.to_string();
+                        }
+                    }
+                }
+                set_item_text(&mut array.items[index], value);
+            }
+            Some((LangMatch::Specific | LangMatch::SingleGeneric, index)) => {
+                // Update the item, and x-default if it duplicated the item
+                let old_value = array.items[index].as_simple().map(|s| s.value.clone());
+                if index != 0 && have_x_default && x_default == old_value {
+                    set_item_text(&mut array.items[0], value);
+                }
+                set_item_text(&mut array.items[index], value);
+            }
+            Some((LangMatch::MultipleGeneric | LangMatch::XDefault | LangMatch::FirstItem, _))
+            | None => {
+                // Add the specific language, without touching x-default
+                add_lang_item(array, &specific_lang, value);
+                have_x_default |= is_x_default;
             }
         }
 
-        // If not found, create new item
-        if !found {
-            let mut simple_node = Node::simple(value.to_string());
-            if let Node::Simple(ref mut sn) = simple_node {
-                let lang_qualifier = Qualifier::new(ns::XML, "lang", specific_lang.to_string());
-                sn.add_qualifier(lang_qualifier);
-            }
-            array.append(simple_node);
+        // A lone item gets a matching x-default in front of it
+        if !have_x_default && array.items.len() == 1 {
+            add_lang_item(array, X_DEFAULT, value);
         }
 
         Ok(())
@@ -850,8 +1533,11 @@ impl XmpMeta {
     /// This method searches for a localized text value matching the specified
     /// language codes. It follows XMP language matching rules:
     /// 1. Exact match for specific_lang
-    /// 2. Match for generic_lang if specific_lang not found
+    /// 2. Match for generic_lang, either exactly or as a prefix (`en` matches `en-GB`)
     /// 3. Fallback to "x-default" if neither found
+    /// 4. Fallback to the first item
+    ///
+    /// Language tags are compared case-insensitively.
     ///
     /// # Arguments
     ///
@@ -866,7 +1552,7 @@ impl XmpMeta {
     /// - `value` is the text value
     /// - `actual_lang` is the actual language code used (may differ from requested)
     ///
-    /// Returns `None` if the property doesn't exist or no matching language found.
+    /// Returns `None` if the property doesn't exist or has no items.
     ///
     /// # Example
     ///
@@ -881,15 +1567,12 @@ impl XmpMeta {
     ///     "x-default",
     ///     "Default Title"
     /// ).unwrap();
+    /// meta.set_localized_text("dc", "title", "en", "en-US", "Title").unwrap();
     ///
-    /// let (value, lang) = meta.get_localized_text(
-    ///     "http://purl.org/dc/elements/1.1/",
-    ///     "title",
-    ///     "",
-    ///     "x-default"
-    /// ).unwrap();
-    /// assert_eq!(value, "Default Title");
-    /// assert_eq!(lang, "x-default");
+    /// // en-GB falls back to the generic "en" match
+    /// let (value, lang) = meta.get_localized_text("dc", "title", "en", "en-GB").unwrap();
+    /// assert_eq!(value, "Title");
+    /// assert_eq!(lang, "en-US");
     /// ```
     pub fn get_localized_text(
         &self,
@@ -898,8 +1581,6 @@ impl XmpMeta {
         generic_lang: &str,
         specific_lang: &str,
     ) -> Option<(String, String)> {
-        use crate::core::namespace::ns;
-
         let ns_uri = self.resolve_namespace_uri(namespace)?;
 
         let full_path = format!("{}:{}", ns_uri, property);
@@ -914,55 +1595,14 @@ impl XmpMeta {
             return None;
         }
 
-        // Try exact match for specific_lang
-        for item in &array.items {
-            let Some(simple) = item.as_simple() else {
-                continue;
-            };
-            let Some(lang_qual) = simple.get_qualifier(ns::XML, "lang") else {
-                continue;
-            };
-            if lang_qual.value == specific_lang {
-                return Some((simple.value.clone(), lang_qual.value.clone()));
-            }
-        }
-
-        // Try match for generic_lang (if provided)
-        if !generic_lang.is_empty() {
-            for item in &array.items {
-                let Some(simple) = item.as_simple() else {
-                    continue;
-                };
-                let Some(lang_qual) = simple.get_qualifier(ns::XML, "lang") else {
-                    continue;
-                };
-                if lang_qual.value.starts_with(generic_lang) {
-                    return Some((simple.value.clone(), lang_qual.value.clone()));
-                }
-            }
-        }
-
-        // Fallback to x-default
-        for item in &array.items {
-            let Some(simple) = item.as_simple() else {
-                continue;
-            };
-            let Some(lang_qual) = simple.get_qualifier(ns::XML, "lang") else {
-                continue;
-            };
-            if lang_qual.value == "x-default" {
-                return Some((simple.value.clone(), lang_qual.value.clone()));
-            }
-        }
-
-        // If no x-default, return first item
-        let first_item = array.items.first()?;
-        let simple = first_item.as_simple()?;
-        let lang = simple
-            .get_qualifier(ns::XML, "lang")
-            .map(|q| q.value.clone())
-            .unwrap_or_else(|| "".to_string());
-        Some((simple.value.clone(), lang))
+        let (_, index) = choose_localized_item(
+            array,
+            &normalize_lang(generic_lang),
+            &normalize_lang(specific_lang),
+        )?;
+        let item = &array.items[index];
+        let simple = item.as_simple()?;
+        Some((simple.value.clone(), item_lang(item).unwrap_or_default()))
     }
 
     /// Set a date/time property
@@ -1002,9 +1642,7 @@ impl XmpMeta {
         path: &str,
         dt: &crate::utils::datetime::XmpDateTime,
     ) -> XmpResult<()> {
-        dt.validate()?;
-        let formatted = dt.format();
-        self.set_property(namespace, path, XmpValue::DateTime(formatted))
+        self.set_property(namespace, path, XmpValue::DateTime(dt.clone()))
     }
 
     /// Get a date/time property
@@ -1031,7 +1669,7 @@ impl XmpMeta {
     /// meta.set_property(
     ///     "http://ns.adobe.com/xap/1.0/",
     ///     "ModifyDate",
-    ///     XmpValue::DateTime("2023-12-25T10:30:00Z".to_string())
+    ///     XmpValue::String("2023-12-25T10:30:00Z".to_string())
     /// ).unwrap();
     ///
     /// let dt = meta.get_date_time("http://ns.adobe.com/xap/1.0/", "ModifyDate").unwrap();
@@ -1044,13 +1682,246 @@ impl XmpMeta {
         namespace: &str,
         path: &str,
     ) -> Option<crate::utils::datetime::XmpDateTime> {
-        self.get_property(namespace, path)
-            .and_then(|v| match v {
-                XmpValue::DateTime(s) => Some(s),
-                XmpValue::String(s) => Some(s),
-                _ => None,
+        self.get_property(namespace, path)?.as_date_time()
+    }
+}
+
+/// Split a trailing qualifier step (`?name`) off the path components
+fn split_qualifier_step(components: &[PathComponent]) -> (&[PathComponent], Option<&str>) {
+    match components.split_last() {
+        Some((PathComponent::Qualifier(name), parent)) => (parent, Some(name.as_str())),
+        _ => (components, None),
+    }
+}
+
+/// The `xml:lang` value of the default item of a localized text array
+const X_DEFAULT: &str = "x-default";
+
+/// How an item of a localized text array matched the requested languages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LangMatch {
+    /// The item has the specific language
+    Specific,
+    /// The item is the only one with the generic language
+    SingleGeneric,
+    /// The item is the first of several with the generic language
+    MultipleGeneric,
+    /// The item is the `x-default` item
+    XDefault,
+    /// The item is the first item, nothing else matched
+    FirstItem,
+}
+
+/// Normalize the case of an RFC 3066 language tag
+///
+/// The tag is lowercased, except for a two-letter second subtag (the
+/// country code), which is uppercased: `EN-us` becomes `en-US`.
+fn normalize_lang(lang: &str) -> String {
+    lang.split('-')
+        .enumerate()
+        .map(|(i, subtag)| {
+            if i == 1 && subtag.len() == 2 {
+                subtag.to_ascii_uppercase()
+            } else {
+                subtag.to_ascii_lowercase()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Get the `xml:lang` qualifier value of an array item
+fn item_lang(item: &Node) -> Option<String> {
+    use crate::core::namespace::ns;
+    item.get_qualifier(ns::XML, "lang").map(|q| q.value.clone())
+}
+
+/// Add a localized text item, keeping the `x-default` item first
+fn add_lang_item(array: &mut crate::core::node::ArrayNode, lang: &str, value: &str) {
+    use crate::core::namespace::ns;
+    let mut item = Node::simple(value);
+    item.set_qualifier(Qualifier::new(ns::XML, "lang", lang));
+    if lang == X_DEFAULT {
+        array.items.insert(0, item);
+    } else {
+        array.append(item);
+    }
+}
+
+/// Set the text of a localized text item
+fn set_item_text(item: &mut Node, value: &str) {
+    if let Some(simple) = item.as_simple_mut() {
+        simple.value = value.to_string();
+    }
+}
+
+/// Choose the item of a localized text array for the given languages
+///
+/// The languages must already be normalized. Items match on, in order: the
+/// specific language, the generic language (exactly or as a prefix followed
+/// by `-`), `x-default`, and finally the first item.
+fn choose_localized_item(
+    array: &crate::core::node::ArrayNode,
+    generic_lang: &str,
+    specific_lang: &str,
+) -> Option<(LangMatch, usize)> {
+    let langs: Vec<Option<String>> = array
+        .items
+        .iter()
+        .map(|item| item.as_simple().and_then(|_| item_lang(item)))
+        .collect();
+    let find = |pred: &dyn Fn(&str) -> bool| {
+        langs
+            .iter()
+            .enumerate()
+            .filter(|(_, lang)| lang.as_deref().is_some_and(pred))
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>()
+    };
+
+    if let Some(&index) = find(&|lang| lang.eq_ignore_ascii_case(specific_lang)).first() {
+        return Some((LangMatch::Specific, index));
+    }
+
+    if !generic_lang.is_empty() {
+        let generic_matches = find(&|lang| {
+            lang.len() >= generic_lang.len()
+                && lang.is_char_boundary(generic_lang.len())
+                && lang[..generic_lang.len()].eq_ignore_ascii_case(generic_lang)
+                && (lang.len() == generic_lang.len() || lang.as_bytes()[generic_lang.len()] == b'-')
+        });
+        match generic_matches.as_slice() {
+            [] => {}
+            [index] => return Some((LangMatch::SingleGeneric, *index)),
+            [index, ..] => return Some((LangMatch::MultipleGeneric, *index)),
+        }
+    }
+
+    if let Some(&index) = find(&|lang| lang == X_DEFAULT).first() {
+        return Some((LangMatch::XDefault, index));
+    }
+
+    if array.items.is_empty() {
+        None
+    } else {
+        Some((LangMatch::FirstItem, 0))
+    }
+}
+
+/// Resolve a `name` or `prefix:name` path step into a `namespace_uri:name` key
+fn resolve_step_key(namespaces: &NamespaceMap, default_ns_uri: &str, name: &str) -> String {
+    use crate::core::namespace::get_global_namespace_uri;
+    if let Some(colon_pos) = name.find(':') {
+        let prefix = &name[..colon_pos];
+        let uri = namespaces
+            .get_uri(prefix)
+            .map(|uri| uri.to_string())
+            .or_else(|| get_global_namespace_uri(prefix));
+        if let Some(uri) = uri {
+            return format!("{}:{}", uri, &name[colon_pos + 1..]);
+        }
+    }
+    format!("{}:{}", default_ns_uri, name)
+}
+
+/// Find the index of the array item selected by an array step
+///
+/// Language values are compared case-insensitively, as required by RFC 3066.
+fn find_array_item(
+    array: &crate::core::node::ArrayNode,
+    step: &PathComponent,
+    namespaces: &NamespaceMap,
+    ns_uri: &str,
+) -> Option<usize> {
+    use crate::core::namespace::ns;
+    match step {
+        PathComponent::Index(idx) => idx.checked_sub(1).filter(|i| *i < array.len()),
+        PathComponent::Last => array.len().checked_sub(1),
+        PathComponent::QualifierSelector { name, value } => {
+            let key = resolve_step_key(namespaces, ns_uri, name);
+            let is_lang = key == format!("{}:lang", ns::XML);
+            array.items.iter().position(|item| {
+                item.qualifiers().iter().any(|q| {
+                    q.path() == key
+                        && (q.value == *value || is_lang && q.value.eq_ignore_ascii_case(value))
+                })
+            })
+        }
+        PathComponent::FieldSelector { name, value } => {
+            let key = resolve_step_key(namespaces, ns_uri, name);
+            array.items.iter().position(|item| {
+                item.as_structure()
+                    .and_then(|s| s.get_field(&key))
+                    .and_then(Node::as_simple)
+                    .is_some_and(|field| field.value == *value)
             })
-            .and_then(|s| crate::utils::datetime::XmpDateTime::parse(&s).ok())
+        }
+        PathComponent::Name(_) | PathComponent::Qualifier(_) => None,
+    }
+}
+
+/// Find the array item selected by an array step, appending a new item if there is none
+///
+/// Index steps pad the array with `default_item`. Selector steps append an
+/// item carrying the selecting qualifier or field.
+fn find_or_append_array_item(
+    array: &mut crate::core::node::ArrayNode,
+    step: &PathComponent,
+    namespaces: &NamespaceMap,
+    ns_uri: &str,
+    default_item: Node,
+) -> XmpResult<usize> {
+    if let Some(index) = find_array_item(array, step, namespaces, ns_uri) {
+        return Ok(index);
+    }
+    match step {
+        PathComponent::Index(0) => Err(XmpError::BadXPath(
+            "Array index must be 1 or greater".to_string(),
+        )),
+        PathComponent::Index(idx) => {
+            while array.len() < *idx {
+                array.append(default_item.clone());
+            }
+            Ok(*idx - 1)
+        }
+        PathComponent::Last => {
+            array.append(default_item);
+            Ok(array.len() - 1)
+        }
+        PathComponent::QualifierSelector { name, value } => {
+            let key = resolve_step_key(namespaces, ns_uri, name);
+            let colon_pos = key.rfind(':').unwrap();
+            let mut item = default_item;
+            item.set_qualifier(Qualifier::new(
+                &key[..colon_pos],
+                &key[colon_pos + 1..],
+                value.as_str(),
+            ));
+            array.append(item);
+            Ok(array.len() - 1)
+        }
+        PathComponent::FieldSelector { name, value } => {
+            let key = resolve_step_key(namespaces, ns_uri, name);
+            let mut item = StructureNode::new();
+            item.set_field(key, Node::simple(value.as_str()));
+            array.append(Node::Structure(item));
+            Ok(array.len() - 1)
+        }
+        PathComponent::Name(_) | PathComponent::Qualifier(_) => {
+            Err(XmpError::BadXPath("Expected an array step".to_string()))
+        }
+    }
+}
+
+/// Node to create for a missing path step, given the step that follows it
+fn default_container(next: Option<&PathComponent>) -> Node {
+    use crate::core::node::ArrayType;
+    match next {
+        Some(PathComponent::QualifierSelector { name, .. }) if name == "xml:lang" => {
+            Node::array(ArrayType::Alternative)
+        }
+        Some(step) if step.is_array_step() => Node::array(ArrayType::Ordered),
+        _ => Node::structure(),
     }
 }
 
@@ -1064,19 +1935,39 @@ fn value_to_node(
         XmpValue::String(s) => Ok(Node::simple(s)),
         XmpValue::Integer(i) => Ok(Node::simple(i.to_string())),
         XmpValue::Boolean(b) => Ok(Node::simple(if b { "True" } else { "False" })),
-        XmpValue::DateTime(dt) => Ok(Node::simple(dt)),
-        XmpValue::Array(arr) => {
-            use crate::core::node::{ArrayNode, ArrayType};
-            // Note: XmpValue::Array does not carry the specific array type (Seq, Bag, or Alt).
-            // We default to ArrayType::Unordered (Bag) here. As a limitation, round-trips of
-            // Seq or Alt arrays parsed and then set/serialized via XmpValue may lose their type info.
-            let mut array_node = ArrayNode::new(ArrayType::Unordered);
+        XmpValue::Real(r) => {
+            if !r.is_finite() {
+                return Err(XmpError::BadValue(format!("Invalid real value: {}", r)));
+            }
+            Ok(Node::simple(r.to_string()))
+        }
+        XmpValue::Rational(n, d) => Ok(Node::simple(format!("{}/{}", n, d))),
+        XmpValue::Uri(uri) => Ok(Node::Simple(crate::core::node::SimpleNode::uri(uri))),
+        XmpValue::DateTime(dt) => {
+            dt.validate()?;
+            Ok(Node::simple(dt.format()))
+        }
+        XmpValue::Array(array_type, arr) => {
+            let mut array_node = crate::core::node::ArrayNode::new(array_type);
             for item in arr {
                 let item_node = value_to_node(item, default_ns_uri, namespaces)?;
                 array_node.append(item_node);
             }
             Ok(Node::Array(array_node))
         }
+        XmpValue::AltText(items) => {
+            use crate::core::namespace::ns;
+            use crate::core::node::{ArrayNode, ArrayType, SimpleNode};
+            use crate::types::qualifier::Qualifier;
+
+            let mut array_node = ArrayNode::new(ArrayType::Alternative);
+            for (lang, text) in items {
+                let mut item_node = SimpleNode::new(text);
+                item_node.add_qualifier(Qualifier::new(ns::XML, "lang", lang));
+                array_node.append(Node::Simple(item_node));
+            }
+            Ok(Node::Array(array_node))
+        }
         XmpValue::Structure(structure) => {
             let mut structure_node = crate::core::node::StructureNode::new();
             for (key, val) in structure {
@@ -1105,6 +1996,33 @@ fn value_to_node(
     }
 }
 
+/// Collect the namespace URIs of the fields and qualifiers below a node
+fn collect_namespace_uris(node: &Node, uris: &mut Vec<String>) {
+    for qualifier in node.qualifiers() {
+        if !uris.contains(&qualifier.namespace) {
+            uris.push(qualifier.namespace.clone());
+        }
+    }
+    match node {
+        Node::Simple(_) => {}
+        Node::Array(array) => {
+            for item in &array.items {
+                collect_namespace_uris(item, uris);
+            }
+        }
+        Node::Structure(structure) => {
+            for (key, field) in &structure.fields {
+                if let Some(pos) = key.rfind(':') {
+                    if !uris.iter().any(|uri| uri == &key[..pos]) {
+                        uris.push(key[..pos].to_string());
+                    }
+                }
+                collect_namespace_uris(field, uris);
+            }
+        }
+    }
+}
+
 /// Helper function to traverse or create nested nodes
 fn get_or_create_node<'a>(
     root: &'a mut StructureNode,
@@ -1132,45 +2050,32 @@ fn get_or_create_node<'a>(
     let first_key = format!("{}:{}", current_ns_uri, first_name);
 
     // Determine default node
-    let default_node = if components.len() > 1 {
-        match &components[1] {
-            PathComponent::Index(_) => Node::array(crate::core::node::ArrayType::Ordered),
-            _ => Node::structure(),
-        }
-    } else {
-        Node::structure()
-    };
+    let default_node = default_container(components.get(1));
 
     let mut current = root.fields.entry(first_key).or_insert(default_node);
 
     // Handle remaining components
     for i in 1..components.len() {
-        let next_is_index =
-            i + 1 < components.len() && matches!(&components[i + 1], PathComponent::Index(_));
-        let default_child = if next_is_index {
-            Node::array(crate::core::node::ArrayType::Ordered)
-        } else {
-            Node::structure()
-        };
+        let default_child = default_container(components.get(i + 1));
 
         match &components[i] {
-            PathComponent::Index(idx) => {
-                if *idx == 0 {
-                    return Err(XmpError::BadXPath(
-                        "Array index must be 1 or greater".to_string(),
-                    ));
-                }
-                let idx_0 = *idx - 1;
-
+            step @ (PathComponent::Index(_)
+            | PathComponent::Last
+            | PathComponent::QualifierSelector { .. }
+            | PathComponent::FieldSelector { .. }) => {
                 if !matches!(current, Node::Array(_)) {
-                    *current = Node::array(crate::core::node::ArrayType::Ordered);
+                    *current = default_container(Some(step));
                 }
 
                 let array = current.as_array_mut().unwrap();
-                while array.len() <= idx_0 {
-                    array.append(default_child.clone());
-                }
-                current = array.get_mut(idx_0).unwrap();
+                let index = find_or_append_array_item(
+                    array,
+                    step,
+                    namespaces,
+                    &current_ns_uri,
+                    default_child,
+                )?;
+                current = array.get_mut(index).unwrap();
             }
             PathComponent::Name(name) => {
                 if !matches!(current, Node::Structure(_)) {
@@ -1201,6 +2106,11 @@ fn get_or_create_node<'a>(
 
                 current = structure.fields.entry(key).or_insert(default_child);
             }
+            PathComponent::Qualifier(_) => {
+                return Err(XmpError::BadXPath(
+                    "Qualifier must be the last path step".to_string(),
+                ))
+            }
         }
     }
 
@@ -1224,6 +2134,7 @@ impl FromStr for XmpMeta {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::datetime::XmpDateTime;
 
     #[test]
     fn test_xmp_meta_new() {
@@ -1363,8 +2274,8 @@ mod tests {
 
         // Test that get_property return an array.
         let bag = meta.get_property("http://purl.org/dc/elements/1.1/", "creator");
-        assert!(matches!(bag, Some(XmpValue::Array(_))));
-        if let Some(XmpValue::Array(bag)) = bag {
+        assert!(matches!(bag, Some(XmpValue::Array(_, _))));
+        if let Some(XmpValue::Array(_, bag)) = bag {
             assert_eq!(bag.len(), 2);
             assert_eq!(bag[0], XmpValue::String("Author1".to_string()));
             assert_eq!(bag[1], XmpValue::String("Author2".to_string()));
@@ -1468,6 +2379,88 @@ mod tests {
         assert_eq!(value, "Updated Title");
     }
 
+    #[test]
+    fn test_localized_text_language_fallback() {
+        let mut meta = XmpMeta::new();
+
+        // The first item gets a matching x-default, and tags are normalized
+        meta.set_localized_text("dc", "title", "en", "EN-us", "Colour")
+            .unwrap();
+        assert_eq!(
+            meta.get_localized_text("dc", "title", "", "x-default"),
+            Some(("Colour".to_string(), "x-default".to_string()))
+        );
+        assert_eq!(
+            meta.get_localized_text("dc", "title", "", "en-us"),
+            Some(("Colour".to_string(), "en-US".to_string()))
+        );
+
+        // en-GB falls back to the generic match, not x-default
+        meta.set_localized_text("dc", "title", "de", "de-DE", "Farbe")
+            .unwrap();
+        meta.set_property("dc", "title[?xml:lang=\"x-default\"]", "Default".into())
+            .unwrap();
+        assert_eq!(
+            meta.get_localized_text("dc", "title", "en", "en-GB"),
+            Some(("Colour".to_string(), "en-US".to_string()))
+        );
+        assert_eq!(
+            meta.get_localized_text("dc", "title", "", "en-GB"),
+            Some(("Default".to_string(), "x-default".to_string()))
+        );
+        // "e" is not a prefix of the "en" subtag
+        assert_eq!(
+            meta.get_localized_text("dc", "title", "e", "e-XX"),
+            Some(("Default".to_string(), "x-default".to_string()))
+        );
+
+        // A single generic match is updated in place
+        meta.set_localized_text("dc", "title", "en", "en-GB", "Color")
+            .unwrap();
+        assert_eq!(meta.get_array_size("dc", "title"), Some(3));
+        assert_eq!(
+            meta.get_localized_text("dc", "title", "", "en-US"),
+            Some(("Color".to_string(), "en-US".to_string()))
+        );
+
+        // x-default follows the item it duplicates
+        meta.set_property("dc", "title[?xml:lang=\"x-default\"]", "Farbe".into())
+            .unwrap();
+        meta.set_localized_text("dc", "title", "de", "de-DE", "Die Farbe")
+            .unwrap();
+        assert_eq!(
+            meta.get_localized_text("dc", "title", "", "x-default"),
+            Some(("Die Farbe".to_string(), "x-default".to_string()))
+        );
+        // ... and setting x-default updates its duplicates
+        meta.set_localized_text("dc", "title", "", "x-default", "Colors")
+            .unwrap();
+        assert_eq!(
+            meta.get_localized_text("dc", "title", "", "de-DE"),
+            Some(("Colors".to_string(), "de-DE".to_string()))
+        );
+        assert_eq!(
+            meta.get_localized_text("dc", "title", "", "en-US"),
+            Some(("Color".to_string(), "en-US".to_string()))
+        );
+
+        // Without x-default, the first item is used
+        let mut meta = XmpMeta::new();
+        meta.set_property(
+            "dc",
+            "rights",
+            XmpValue::AltText(vec![
+                ("fr-FR".to_string(), "Droits".to_string()),
+                ("it-IT".to_string(), "Diritti".to_string()),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(
+            meta.get_localized_text("dc", "rights", "en", "en-US"),
+            Some(("Droits".to_string(), "fr-FR".to_string()))
+        );
+    }
+
     #[test]
     fn test_localized_text_serialize_round_trip() {
         let mut meta = XmpMeta::new();
@@ -1534,7 +2527,7 @@ mod tests {
         meta.set_property(
             "xmp",
             "ModifyDate",
-            XmpValue::DateTime("2024-01-01T00:00:00Z".to_string()),
+            XmpValue::DateTime(XmpDateTime::parse("2024-01-01T00:00:00Z").unwrap()),
         )
         .unwrap();
 
@@ -1595,7 +2588,7 @@ mod tests {
         meta.set_property(
             ns,
             property,
-            XmpValue::DateTime("2023-12-25T10:30:00Z".to_string()),
+            XmpValue::DateTime(XmpDateTime::parse("2023-12-25T10:30:00Z").unwrap()),
         )
         .unwrap();
 
@@ -1674,6 +2667,463 @@ mod tests {
         let value = meta.get_property("xmp", "CreatorTool");
         assert!(matches!(value, Some(XmpValue::String(s)) if s == "NIKON Z 7 Ver.03.40"));
         let value = meta.get_property("lightroom", "hierarchicalSubject");
-        assert!(matches!(value, Some(XmpValue::Array(v)) if v.len() == 2));
+        assert!(matches!(value, Some(XmpValue::Array(_, v)) if v.len() == 2));
+    }
+
+    #[test]
+    fn test_qualifier_operations() {
+        let mut meta = XmpMeta::new();
+        meta.append_array_item("dc", "creator", "Alice".into())
+            .unwrap();
+
+        assert!(!meta.has_qualifier("dc", "creator[1]", "xmp", "Role"));
+        meta.set_qualifier("dc", "creator[1]", "xmp", "Role", "author")
+            .unwrap();
+        assert!(meta.has_qualifier("dc", "creator[1]", "xmp", "Role"));
+        assert_eq!(
+            meta.get_qualifier("dc", "creator[1]", "xmp", "Role"),
+            Some(XmpValue::String("author".to_string()))
+        );
+
+        // Qualifier steps in paths
+        assert!(meta.has_property("dc", "creator[1]/?xmp:Role"));
+        meta.set_property("dc", "creator[1]/?xmp:Role", "editor".into())
+            .unwrap();
+        assert_eq!(
+            meta.get_property("dc", "creator[1]/?xmp:Role"),
+            Some(XmpValue::String("editor".to_string()))
+        );
+        let qualifiers = meta.qualifiers("dc", "creator[1]");
+        assert_eq!(qualifiers.len(), 1);
+        assert_eq!(qualifiers[0].namespace, "http://ns.adobe.com/xap/1.0/");
+        assert_eq!(qualifiers[0].name, "Role");
+
+        // Qualifiers survive serialization
+        let reparsed = XmpMeta::parse(&meta.serialize_packet().unwrap()).unwrap();
+        assert_eq!(
+            reparsed.get_array_item("dc", "creator", 0),
+            Some(XmpValue::String("Alice".to_string()))
+        );
+        assert_eq!(
+            reparsed.get_qualifier("dc", "creator[1]", "xmp", "Role"),
+            Some(XmpValue::String("editor".to_string()))
+        );
+
+        meta.delete_property("dc", "creator[1]/?xmp:Role").unwrap();
+        assert!(!meta.has_qualifier("dc", "creator[1]", "xmp", "Role"));
+        meta.set_qualifier("dc", "creator[1]", "xmp", "Role", "author")
+            .unwrap();
+        meta.delete_qualifier("dc", "creator[1]", "xmp", "Role")
+            .unwrap();
+        assert!(meta.qualifiers("dc", "creator[1]").is_empty());
+
+        // The property must exist before it can be qualified
+        assert!(meta
+            .set_qualifier("dc", "creator[2]", "xmp", "Role", "author")
+            .is_err());
+    }
+
+    #[test]
+    fn test_typed_values_round_trip() {
+        let mut meta = XmpMeta::new();
+        meta.set_property("exif", "ExposureTime", XmpValue::Rational(1, 125))
+            .unwrap();
+        meta.set_property("exif", "FNumber", XmpValue::Rational(28, 10))
+            .unwrap();
+        meta.set_property("xmp", "Rating", XmpValue::Real(3.5))
+            .unwrap();
+        meta.set_property(
+            "xmpRights",
+            "WebStatement",
+            XmpValue::Uri("https://example.com/license".to_string()),
+        )
+        .unwrap();
+        assert!(meta
+            .set_property("xmp", "Rating", XmpValue::Real(f64::NAN))
+            .is_err());
+
+        let serialized = meta.serialize_packet().unwrap();
+        assert!(serialized.contains("rdf:resource=\"https://example.com/license\""));
+
+        let reparsed = XmpMeta::parse(&serialized).unwrap();
+        let exposure = reparsed.get_property("exif", "ExposureTime").unwrap();
+        assert_eq!(exposure.as_rational(), Some((1, 125)));
+        let f_number = reparsed.get_property("exif", "FNumber").unwrap();
+        assert_eq!(f_number.as_f64(), Some(2.8));
+        let rating = reparsed.get_property("xmp", "Rating").unwrap();
+        assert_eq!(rating.as_f64(), Some(3.5));
+        assert_eq!(
+            reparsed.get_property("xmpRights", "WebStatement"),
+            Some(XmpValue::Uri("https://example.com/license".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_semantic_equality() {
+        let a = XmpMeta::parse(
+            r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+ <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+  <rdf:Description rdf:about="" xmlns:dc="http://purl.org/dc/elements/1.1/"
+    xmlns:xmp="http://ns.adobe.com/xap/1.0/" xmlns:exif="http://ns.adobe.com/exif/1.0/"
+    xmp:CreatorTool="xmpkit">
+   <dc:subject><rdf:Bag><rdf:li>sunset</rdf:li><rdf:li>beach</rdf:li></rdf:Bag></dc:subject>
+   <exif:Flash rdf:parseType="Resource">
+    <exif:Fired>True</exif:Fired>
+    <exif:Mode>2</exif:Mode>
+   </exif:Flash>
+   <dc:title><rdf:Alt><rdf:li xml:lang="x-default">Title</rdf:li></rdf:Alt></dc:title>
+  </rdf:Description>
+ </rdf:RDF>
+</x:xmpmeta>"#,
+        )
+        .unwrap();
+        // Different prefixes, property and field order, Bag order and whitespace
+        let b = XmpMeta::parse(
+            r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+ <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+  <rdf:Description rdf:about="" xmlns:d="http://purl.org/dc/elements/1.1/"
+    xmlns:basic="http://ns.adobe.com/xap/1.0/" xmlns:ex="http://ns.adobe.com/exif/1.0/">
+   <d:title><rdf:Alt><rdf:li xml:lang="x-default">  Title
+   </rdf:li></rdf:Alt></d:title>
+   <ex:Flash rdf:parseType="Resource">
+    <ex:Mode>2</ex:Mode>
+    <ex:Fired>True</ex:Fired>
+   </ex:Flash>
+   <basic:CreatorTool>xmpkit</basic:CreatorTool>
+   <d:subject><rdf:Bag><rdf:li>beach</rdf:li><rdf:li>sunset</rdf:li></rdf:Bag></d:subject>
+  </rdf:Description>
+ </rdf:RDF>
+</x:xmpmeta>"#,
+        )
+        .unwrap();
+        assert!(a.semantically_eq(&b));
+        assert_eq!(a.canonical_hash(), b.canonical_hash());
+
+        // Array kinds are compared
+        let mut c = b.clone();
+        c.set_property(
+            "dc",
+            "subject",
+            XmpValue::Array(
+                crate::core::node::ArrayType::Ordered,
+                vec!["beach".into(), "sunset".into()],
+            ),
+        )
+        .unwrap();
+        assert!(!a.semantically_eq(&c));
+
+        // So are qualifiers
+        let mut d = b.clone();
+        d.set_qualifier("dc", "subject[1]", "xmp", "Rating", "5")
+            .unwrap();
+        assert!(!a.semantically_eq(&d));
+        assert_ne!(a.canonical_hash(), d.canonical_hash());
+    }
+
+    #[test]
+    fn test_copy_subtree_and_schema() {
+        let mut source = XmpMeta::parse(
+            r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+ <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+  <rdf:Description rdf:about=""
+    xmlns:Iptc4xmpExt="http://iptc.org/std/Iptc4xmpExt/2008-02-29/"
+    xmlns:cust="urn:example:custom:">
+   <Iptc4xmpExt:LocationShown>
+    <rdf:Bag>
+     <rdf:li rdf:parseType="Resource">
+      <Iptc4xmpExt:City>Paris</Iptc4xmpExt:City>
+      <cust:Note>copied</cust:Note>
+     </rdf:li>
+    </rdf:Bag>
+   </Iptc4xmpExt:LocationShown>
+   <cust:Tags><rdf:Seq><rdf:li>a</rdf:li></rdf:Seq></cust:Tags>
+   <cust:Label>keep</cust:Label>
+  </rdf:Description>
+ </rdf:RDF>
+</x:xmpmeta>"#,
+        )
+        .unwrap();
+        source
+            .set_qualifier("cust", "Tags[1]", "cust", "source", "camera")
+            .unwrap();
+
+        let mut dest = XmpMeta::new();
+        dest.copy_subtree(
+            &source,
+            "Iptc4xmpExt",
+            "LocationShown",
+            "Iptc4xmpExt",
+            "LocationCreated",
+        )
+        .unwrap();
+        assert_eq!(
+            dest.get_property("Iptc4xmpExt", "LocationCreated[1]/City"),
+            Some(XmpValue::String("Paris".to_string()))
+        );
+        // The custom namespace of the nested field came along
+        assert_eq!(
+            dest.get_property("Iptc4xmpExt", "LocationCreated[1]/cust:Note"),
+            Some(XmpValue::String("copied".to_string()))
+        );
+
+        dest.copy_schema(&source, "cust").unwrap();
+        assert!(dest.has_property("cust", "Label"));
+        assert_eq!(
+            dest.get_qualifier("cust", "Tags[1]", "cust", "source"),
+            Some(XmpValue::String("camera".to_string()))
+        );
+
+        // Array forms survive a round trip through the serializer
+        let reparsed = XmpMeta::parse(&dest.serialize_packet().unwrap()).unwrap();
+        assert!(reparsed.semantically_eq(&dest));
+        assert!(matches!(
+            reparsed.get_property("Iptc4xmpExt", "LocationCreated"),
+            Some(XmpValue::Array(crate::core::node::ArrayType::Unordered, _))
+        ));
+
+        assert!(dest
+            .copy_subtree(&source, "cust", "Missing", "cust", "Missing")
+            .is_err());
+    }
+
+    #[test]
+    fn test_clone_is_independent() {
+        let mut meta = XmpMeta::new();
+        meta.set_property("xmp", "CreatorTool", "original".into())
+            .unwrap();
+
+        let mut copy = meta.clone();
+        copy.set_property("xmp", "CreatorTool", "copy".into())
+            .unwrap();
+        copy.append_array_item("dc", "creator", "Alice".into())
+            .unwrap();
+        assert_eq!(
+            meta.get_property("xmp", "CreatorTool"),
+            Some(XmpValue::String("original".to_string()))
+        );
+        assert!(!meta.has_property("dc", "creator"));
+
+        // Writing to the original leaves the copy alone as well
+        meta.delete_property("xmp", "CreatorTool").unwrap();
+        assert_eq!(
+            copy.get_property("xmp", "CreatorTool"),
+            Some(XmpValue::String("copy".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_path_selectors() {
+        use crate::core::node::ArrayType;
+
+        let mut meta = XmpMeta::new();
+        meta.set_localized_text("dc", "title", "", "x-default", "Title")
+            .unwrap();
+        meta.set_localized_text("dc", "title", "", "fr-FR", "Titre")
+            .unwrap();
+
+        // Language selectors, case-insensitive
+        assert_eq!(
+            meta.get_property("dc", "title[?xml:lang=\"fr-fr\"]"),
+            Some(XmpValue::String("Titre".to_string()))
+        );
+        assert_eq!(
+            meta.get_property("dc", "title[last()]/@xml:lang"),
+            Some(XmpValue::String("fr-FR".to_string()))
+        );
+        meta.set_property("dc", "title[?xml:lang=\"fr-FR\"]", "Le titre".into())
+            .unwrap();
+        assert_eq!(
+            meta.get_localized_text("dc", "title", "", "fr-FR"),
+            Some(("Le titre".to_string(), "fr-FR".to_string()))
+        );
+        meta.set_property("dc", "title[?xml:lang=\"de-DE\"]", "Titel".into())
+            .unwrap();
+        assert_eq!(meta.get_array_size("dc", "title"), Some(3));
+        assert_eq!(
+            meta.get_property("dc", "title[3]/?xml:lang"),
+            Some(XmpValue::String("de-DE".to_string()))
+        );
+
+        // A missing array is created with the form its selector implies
+        meta.set_property("dc", "rights[?xml:lang=\"x-default\"]", "CC0".into())
+            .unwrap();
+        assert_eq!(
+            meta.get_property("dc", "rights").unwrap().array_type(),
+            Some(ArrayType::Alternative)
+        );
+
+        // Field selectors on arrays of structures
+        crate::core::namespace::register_namespace(
+            "http://ns.adobe.com/xap/1.0/sType/ResourceEvent#",
+            "stEvt",
+        )
+        .unwrap();
+        meta.set_property(
+            "xmpMM",
+            "History[stEvt:action=\"created\"]/stEvt:when",
+            "2024-01-01".into(),
+        )
+        .unwrap();
+        meta.set_property(
+            "xmpMM",
+            "History[stEvt:action=\"saved\"]/stEvt:when",
+            "2024-01-02".into(),
+        )
+        .unwrap();
+        assert_eq!(meta.get_array_size("xmpMM", "History"), Some(2));
+        assert_eq!(
+            meta.get_property("xmpMM", "History[stEvt:action=\"saved\"]/stEvt:when"),
+            Some(XmpValue::String("2024-01-02".to_string()))
+        );
+        assert_eq!(
+            meta.get_property("xmpMM", "History[last()]/stEvt:action"),
+            Some(XmpValue::String("saved".to_string()))
+        );
+        assert!(!meta.has_property("xmpMM", "History[stEvt:action=\"deleted\"]"));
+
+        meta.delete_property("xmpMM", "History[stEvt:action=\"created\"]")
+            .unwrap();
+        assert_eq!(meta.get_array_size("xmpMM", "History"), Some(1));
+        meta.delete_property("dc", "title[last()]").unwrap();
+        assert_eq!(meta.get_array_size("dc", "title"), Some(2));
+    }
+
+    #[test]
+    fn test_array_form_round_trip() {
+        use crate::core::node::ArrayType;
+
+        let xml = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+ <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+  <rdf:Description rdf:about="" xmlns:dc="http://purl.org/dc/elements/1.1/">
+   <dc:creator><rdf:Seq><rdf:li>Alice</rdf:li><rdf:li>Bob</rdf:li></rdf:Seq></dc:creator>
+   <dc:subject><rdf:Bag><rdf:li>sunset</rdf:li></rdf:Bag></dc:subject>
+   <dc:title><rdf:Alt>
+    <rdf:li xml:lang="x-default">Title</rdf:li>
+    <rdf:li xml:lang="fr-FR">Titre</rdf:li>
+   </rdf:Alt></dc:title>
+  </rdf:Description>
+ </rdf:RDF>
+</x:xmpmeta>"#;
+        let meta = XmpMeta::parse(xml).unwrap();
+
+        let creator = meta.get_property("dc", "creator").unwrap();
+        assert_eq!(creator.array_type(), Some(ArrayType::Ordered));
+        let subject = meta.get_property("dc", "subject").unwrap();
+        assert_eq!(subject.array_type(), Some(ArrayType::Unordered));
+        let title = meta.get_property("dc", "title").unwrap();
+        assert_eq!(
+            title,
+            XmpValue::AltText(vec![
+                ("x-default".to_string(), "Title".to_string()),
+                ("fr-FR".to_string(), "Titre".to_string()),
+            ])
+        );
+
+        let mut copy = XmpMeta::new();
+        copy.set_property("dc", "creator", creator.clone()).unwrap();
+        copy.set_property("dc", "subject", subject.clone()).unwrap();
+        copy.set_property("dc", "title", title.clone()).unwrap();
+
+        let reparsed = XmpMeta::parse(&copy.serialize_packet().unwrap()).unwrap();
+        assert_eq!(reparsed.get_property("dc", "creator"), Some(creator));
+        assert_eq!(reparsed.get_property("dc", "subject"), Some(subject));
+        assert_eq!(reparsed.get_property("dc", "title"), Some(title));
+        assert_eq!(
+            reparsed.get_localized_text("dc", "title", "fr", "fr-FR"),
+            Some(("Titre".to_string(), "fr-FR".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_aliases() {
+        let xmp = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+<rdf:Description rdf:about=""
+    xmlns:tiff="http://ns.adobe.com/tiff/1.0/"
+    xmlns:xmp="http://ns.adobe.com/xap/1.0/"
+    tiff:Artist="Jane Doe"
+    tiff:ImageDescription="A red barn"
+    tiff:Software="Editor 2.0"
+    xmp:CreatorTool="Editor 1.0"/>
+</rdf:RDF>
+</x:xmpmeta>"#;
+        let mut meta = XmpMeta::parse(xmp).unwrap();
+
+        // Aliases are stored as their base properties
+        assert_eq!(
+            meta.get_array_item("dc", "creator", 0),
+            Some(XmpValue::String("Jane Doe".to_string()))
+        );
+        assert_eq!(
+            meta.get_localized_text("dc", "description", "", "x-default"),
+            Some(("A red barn".to_string(), "x-default".to_string()))
+        );
+        // The base property wins over a conflicting alias
+        assert_eq!(
+            meta.get_property("xmp", "CreatorTool"),
+            Some(XmpValue::String("Editor 1.0".to_string()))
+        );
+        let serialized = meta.serialize().unwrap();
+        assert!(!serialized.contains("tiff:Artist"));
+
+        // Accessors follow aliases
+        assert_eq!(
+            meta.get_property("tiff", "Artist"),
+            Some(XmpValue::String("Jane Doe".to_string()))
+        );
+        assert!(meta.has_property("tiff", "ImageDescription"));
+        meta.set_property("photoshop", "Caption", "A blue barn".into())
+            .unwrap();
+        assert_eq!(
+            meta.get_localized_text("dc", "description", "", "x-default"),
+            Some(("A blue barn".to_string(), "x-default".to_string()))
+        );
+        meta.set_property("pdf", "ModDate", "2024-05-01".into())
+            .unwrap();
+        assert_eq!(
+            meta.get_property("xmp", "ModifyDate"),
+            Some(XmpValue::String("2024-05-01".to_string()))
+        );
+        meta.delete_property("tiff", "Software").unwrap();
+        assert!(!meta.has_property("xmp", "CreatorTool"));
+        assert!(!meta.has_property("tiff", "Software"));
+    }
+
+    #[test]
+    fn test_array_form_aliases() {
+        // As written by ExifTool
+        let xmp = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+<rdf:Description rdf:about=""
+    xmlns:tiff="http://ns.adobe.com/tiff/1.0/">
+  <tiff:ImageDescription>
+    <rdf:Alt><rdf:li xml:lang="x-default">Hello</rdf:li></rdf:Alt>
+  </tiff:ImageDescription>
+  <tiff:Artist>
+    <rdf:Seq><rdf:li>Jane Doe</rdf:li><rdf:li>John Doe</rdf:li></rdf:Seq>
+  </tiff:Artist>
+</rdf:Description>
+</rdf:RDF>
+</x:xmpmeta>"#;
+        let meta = XmpMeta::parse(xmp).unwrap();
+
+        assert_eq!(
+            meta.get_localized_text("dc", "description", "", "x-default"),
+            Some(("Hello".to_string(), "x-default".to_string()))
+        );
+        assert_eq!(meta.get_array_size("dc", "description"), Some(1));
+        assert_eq!(
+            meta.get_property("dc", "creator"),
+            Some(XmpValue::Array(
+                crate::core::node::ArrayType::Ordered,
+                vec!["Jane Doe".into(), "John Doe".into()]
+            ))
+        );
+
+        let serialized = meta.serialize().unwrap();
+        assert!(!serialized.contains("tiff:"));
+        assert_eq!(serialized.matches("<rdf:Alt>").count(), 1);
+        let reparsed = XmpMeta::parse(&serialized).unwrap();
+        assert!(reparsed.semantically_eq(&meta));
     }
 }
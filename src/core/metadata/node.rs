@@ -2,6 +2,9 @@
 //!
 //! This module provides type aliases and helper methods that abstract over
 //! single-threaded (Rc<RefCell<>>) and multi-threaded (Arc<RwLock<>>) implementations.
+//!
+//! The root node is shared between clones of an `XmpMeta` and copied on the
+//! first write, so clones behave as independent values.
 
 use crate::core::node::StructureNode;
 
@@ -35,6 +38,11 @@ mod impl_ {
         root.borrow_mut()
     }
 
+    /// Detach the root node from other handles before writing (copy-on-write)
+    pub fn make_root_unique(root: &mut RootNode) {
+        Rc::make_mut(root);
+    }
+
     /// Execute a closure with read access to the root node
     /// Always succeeds in single-threaded mode
     pub fn root_read_with<F, R>(root: &RootNode, f: F) -> R
@@ -80,6 +88,20 @@ mod impl_ {
         root.write()
     }
 
+    /// Detach the root node from other handles before writing (copy-on-write)
+    ///
+    /// A handle that is not shared cannot become shared while it is borrowed
+    /// mutably, so the check does not race with clones made on other threads.
+    pub fn make_root_unique(root: &mut RootNode) {
+        if Arc::strong_count(root) > 1 {
+            let copy = match root.read() {
+                Ok(guard) => guard.clone(),
+                Err(poisoned) => poisoned.into_inner().clone(),
+            };
+            *root = new_root_node(copy);
+        }
+    }
+
     /// Execute a closure with read access to the root node
     /// Returns default value if lock acquisition fails
     pub fn root_read_with<F, R>(root: &RootNode, f: F) -> R
@@ -94,4 +116,4 @@ mod impl_ {
     }
 }
 
-pub use impl_::{new_root_node, root_read, root_read_with, root_write, RootNode};
+pub use impl_::{make_root_unique, new_root_node, root_read, root_read_with, root_write, RootNode};
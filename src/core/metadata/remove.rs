@@ -0,0 +1,194 @@
+//! Bulk property removal
+//!
+//! This module provides [`XmpMeta::remove_properties`], the equivalent of
+//! the Adobe SDK's `XMPUtils::RemoveProperties`, and a predicate-based
+//! variant, [`XmpMeta::remove_properties_where`].
+
+use super::template::is_internal_key;
+use super::XmpMeta;
+use crate::core::error::{XmpError, XmpResult};
+
+/// Options for [`XmpMeta::remove_properties`].
+///
+/// Use the builder pattern to configure options. By default, all properties
+/// except the internal ones are removed.
+///
+/// # Example
+///
+/// ```rust
+/// use xmpkit::core::metadata::RemoveOptions;
+/// use xmpkit::core::namespace::ns;
+///
+/// // Drop the whole Camera Raw schema
+/// let options = RemoveOptions::default().schema(ns::CAMERA_RAW).include_internal();
+/// ```
+#[derive(Default, Clone, Debug)]
+pub struct RemoveOptions {
+    /// Namespace URI or prefix of the schema to remove (default: all schemas)
+    pub schema_ns: Option<String>,
+    /// Also remove internal properties
+    pub include_internal: bool,
+}
+
+impl RemoveOptions {
+    /// Only remove properties of the given schema (namespace URI or prefix).
+    pub fn schema(mut self, namespace: impl Into<String>) -> Self {
+        self.schema_ns = Some(namespace.into());
+        self
+    }
+
+    /// Also remove internal properties.
+    ///
+    /// Internal properties are the ones maintained by applications and file
+    /// handlers rather than users, such as `xmp:ModifyDate`, `xmpMM:*`,
+    /// `crs:*` and `exif:*`.
+    pub fn include_internal(mut self) -> Self {
+        self.include_internal = true;
+        self
+    }
+}
+
+impl XmpMeta {
+    /// Remove properties by schema, keeping internal ones unless asked otherwise.
+    ///
+    /// Returns the number of top-level properties removed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use xmpkit::core::metadata::RemoveOptions;
+    /// use xmpkit::XmpMeta;
+    ///
+    /// let mut meta = XmpMeta::new();
+    /// meta.set_property("dc", "format", "image/jpeg".into()).unwrap();
+    /// meta.set_property("photoshop", "City", "Paris".into()).unwrap();
+    ///
+    /// // dc:format is internal, so only photoshop:City goes
+    /// assert_eq!(meta.remove_properties(RemoveOptions::default()).unwrap(), 1);
+    /// assert!(meta.has_property("dc", "format"));
+    /// ```
+    pub fn remove_properties(&mut self, options: RemoveOptions) -> XmpResult<usize> {
+        let schema_uri = match &options.schema_ns {
+            Some(namespace) => Some(self.resolve_namespace_uri(namespace).ok_or_else(|| {
+                XmpError::BadSchema(format!("Unknown namespace prefix '{}'", namespace))
+            })?),
+            None => None,
+        };
+        let include_internal = options.include_internal;
+        self.remove_keys(|key, namespace_uri| {
+            schema_uri.as_deref().is_none_or(|uri| uri == namespace_uri)
+                && (include_internal || !is_internal_key(key))
+        })
+    }
+
+    /// Remove the top-level properties for which `predicate` returns `true`.
+    ///
+    /// The predicate gets the namespace URI and the name of each property.
+    /// Internal properties are not treated specially. Returns the number of
+    /// properties removed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use xmpkit::core::namespace::ns;
+    /// use xmpkit::XmpMeta;
+    ///
+    /// let mut meta = XmpMeta::new();
+    /// meta.set_property(ns::CAMERA_RAW, "Exposure2012", "+0.50".into()).unwrap();
+    /// meta.set_property("xmp", "Rating", "4".into()).unwrap();
+    ///
+    /// let removed = meta
+    ///     .remove_properties_where(|namespace_uri, _| namespace_uri == ns::CAMERA_RAW)
+    ///     .unwrap();
+    /// assert_eq!(removed, 1);
+    /// assert!(meta.has_property("xmp", "Rating"));
+    /// ```
+    pub fn remove_properties_where<F>(&mut self, mut predicate: F) -> XmpResult<usize>
+    where
+        F: FnMut(&str, &str) -> bool,
+    {
+        self.remove_keys(|key, namespace_uri| {
+            predicate(namespace_uri, &key[namespace_uri.len() + 1..])
+        })
+    }
+
+    /// Remove the root fields for which `remove(key, namespace_uri)` is true
+    fn remove_keys<F>(&mut self, mut remove: F) -> XmpResult<usize>
+    where
+        F: FnMut(&str, &str) -> bool,
+    {
+        let mut root = root_write!(self.root);
+        let before = root.fields.len();
+        root.fields.retain(|key, _| match key.rfind(':') {
+            Some(pos) => !remove(key, &key[..pos]),
+            None => true,
+        });
+        Ok(before - root.fields.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::namespace::ns;
+
+    fn sample() -> XmpMeta {
+        let mut meta = XmpMeta::new();
+        meta.set_property(ns::CAMERA_RAW, "Exposure2012", "+0.50".into())
+            .unwrap();
+        meta.set_property(ns::CAMERA_RAW, "Contrast2012", "+10".into())
+            .unwrap();
+        meta.set_property("xmp", "ModifyDate", "2024-01-01".into())
+            .unwrap();
+        meta.set_property("xmp", "Rating", "4".into()).unwrap();
+        meta.set_property("photoshop", "City", "Paris".into())
+            .unwrap();
+        meta
+    }
+
+    #[test]
+    fn test_remove_properties() {
+        let mut meta = sample();
+        // Camera Raw settings are internal
+        let removed = meta
+            .remove_properties(RemoveOptions::default().schema("crs"))
+            .unwrap();
+        assert_eq!(removed, 0);
+        let removed = meta
+            .remove_properties(RemoveOptions::default().schema("crs").include_internal())
+            .unwrap();
+        assert_eq!(removed, 2);
+        assert!(!meta.has_property(ns::CAMERA_RAW, "Exposure2012"));
+
+        let mut meta = sample();
+        assert_eq!(meta.remove_properties(RemoveOptions::default()).unwrap(), 2);
+        assert!(meta.has_property("xmp", "ModifyDate"));
+        assert!(meta.has_property(ns::CAMERA_RAW, "Contrast2012"));
+        assert!(!meta.has_property("xmp", "Rating"));
+
+        let mut meta = sample();
+        let options = RemoveOptions::default().include_internal();
+        assert_eq!(meta.remove_properties(options).unwrap(), 5);
+
+        assert!(sample()
+            .remove_properties(RemoveOptions::default().schema("nope"))
+            .is_err());
+    }
+
+    #[test]
+    fn test_remove_properties_where() {
+        let mut meta = sample();
+        let removed = meta
+            .remove_properties_where(|namespace_uri, name| {
+                namespace_uri == ns::CAMERA_RAW && name.ends_with("2012")
+            })
+            .unwrap();
+        assert_eq!(removed, 2);
+        assert_eq!(
+            meta.remove_properties_where(|_, name| name == "Rating")
+                .unwrap(),
+            1
+        );
+        assert!(meta.has_property("photoshop", "City"));
+    }
+}
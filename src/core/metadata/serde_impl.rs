@@ -0,0 +1,273 @@
+//! Serde support for [`XmpMeta`]
+//!
+//! `XmpMeta` serializes its node tree as is, with qualifiers and array
+//! forms, plus the prefixes of the namespaces it uses. This module also
+//! provides [`XmpMeta::write_schema`] and [`XmpMeta::read_schema`], which map
+//! a Rust struct to the properties of a schema (see [`crate::core::mapping`]).
+
+use super::node::{new_root_node, root_read_with};
+use super::{collect_namespace_uris, XmpMeta};
+use crate::core::error::XmpResult;
+use crate::core::mapping::{Deserializer, FieldsSerializer};
+use crate::core::namespace::get_global_namespace_prefix;
+use crate::core::node::StructureNode;
+use crate::types::value::XmpValue;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Serialized form of an [`XmpMeta`]
+#[derive(Serialize, Deserialize)]
+struct MetaRepr {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    about: Option<String>,
+    /// Prefixes of the namespaces used, keyed by namespace URI
+    #[serde(default)]
+    namespaces: BTreeMap<String, String>,
+    properties: StructureNode,
+}
+
+impl Serialize for XmpMeta {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let properties = root_read_with(&self.root, |root| Some(root.clone()))
+            .ok_or_else(|| serde::ser::Error::custom("Metadata is not readable"))?;
+
+        let mut uris = Vec::new();
+        for (key, node) in &properties.fields {
+            if let Some(pos) = key.rfind(':') {
+                if !uris.iter().any(|uri| uri == &key[..pos]) {
+                    uris.push(key[..pos].to_string());
+                }
+            }
+            collect_namespace_uris(node, &mut uris);
+        }
+        let namespaces = uris
+            .into_iter()
+            .filter_map(|uri| {
+                let prefix = self
+                    .namespaces
+                    .get_prefix(&uri)
+                    .map(String::from)
+                    .or_else(|| get_global_namespace_prefix(&uri))?;
+                Some((uri, prefix))
+            })
+            .collect();
+
+        MetaRepr {
+            about: self.about_uri.clone(),
+            namespaces,
+            properties,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for XmpMeta {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = MetaRepr::deserialize(deserializer)?;
+        let mut meta = XmpMeta::new();
+        for (uri, prefix) in &repr.namespaces {
+            // Nodes are keyed by URI, so a prefix that is taken only changes
+            // how the namespace is written
+            if meta.resolve_namespace_uri(prefix).is_none() {
+                meta.namespaces
+                    .register(uri, prefix)
+                    .map_err(serde::de::Error::custom)?;
+            }
+        }
+        meta.root = new_root_node(repr.properties);
+        meta.about_uri = repr.about;
+        Ok(meta)
+    }
+}
+
+impl XmpMeta {
+    /// Write a Rust value as the properties of a schema.
+    ///
+    /// The value must serialize to a struct or a map. Each field becomes a
+    /// top-level property in `namespace`, or in the namespace of its prefix
+    /// for names like `dc:creator`. The properties named by the fields are
+    /// replaced, and deleted for fields that are `None`. Other properties
+    /// are kept.
+    ///
+    /// See [`crate::core::mapping`] for how Rust types map to XMP.
+    pub fn write_schema<T: Serialize + ?Sized>(
+        &mut self,
+        namespace: &str,
+        value: &T,
+    ) -> XmpResult<()> {
+        let ns_uri = self.resolve_namespace_uri_or_error(namespace)?;
+        let fields = value.serialize(FieldsSerializer)?;
+
+        // Resolve every name before changing anything
+        let mut properties = Vec::with_capacity(fields.len());
+        for (name, value) in fields {
+            let (uri, local) = match name.rfind(':') {
+                Some(pos) => (
+                    self.resolve_namespace_uri_or_error(&name[..pos])?,
+                    name[pos + 1..].to_string(),
+                ),
+                None => (ns_uri.clone(), name),
+            };
+            properties.push((uri, local, value));
+        }
+        properties.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+
+        self.remove_properties_where(|uri, name| {
+            properties
+                .iter()
+                .any(|(field_uri, local, _)| field_uri == uri && local == name)
+        })?;
+        for (uri, local, value) in properties {
+            if let Some(value) = value {
+                self.set_property(&uri, &local, value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Read the properties of a schema into a Rust value.
+    ///
+    /// The counterpart of [`write_schema`](Self::write_schema): each field is
+    /// read from the property of the same name in `namespace`, or in the
+    /// namespace of its prefix. Missing properties are treated like missing
+    /// fields, so use `Option` or `#[serde(default)]` for optional ones.
+    pub fn read_schema<T: DeserializeOwned>(&self, namespace: &str) -> XmpResult<T> {
+        let ns_uri = self.resolve_namespace_uri_or_error(namespace)?;
+        let properties: HashMap<_, _> = self
+            .all_properties()
+            .into_iter()
+            .map(|property| {
+                (
+                    format!("{}:{}", property.namespace_uri, property.name),
+                    property.value,
+                )
+            })
+            .collect();
+        T::deserialize(Deserializer::for_schema(
+            XmpValue::Structure(properties),
+            &ns_uri,
+            &self.namespaces,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::mapping::{Bag, LangAlt};
+    use crate::core::namespace::ns;
+
+    #[test]
+    fn test_meta_serde_round_trip() {
+        let mut meta = XmpMeta::parse(
+            r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+<rdf:Description rdf:about=""
+    xmlns:dc="http://purl.org/dc/elements/1.1/"
+    xmlns:my="http://example.com/my/">
+  <dc:subject><rdf:Bag><rdf:li>a</rdf:li><rdf:li>b</rdf:li></rdf:Bag></dc:subject>
+  <dc:title><rdf:Alt><rdf:li xml:lang="x-default">T</rdf:li></rdf:Alt></dc:title>
+  <my:Point rdf:parseType="Resource"><my:x>1</my:x></my:Point>
+</rdf:Description>
+</rdf:RDF>
+</x:xmpmeta>"#,
+        )
+        .unwrap();
+        meta.set_qualifier("dc", "subject[1]", ns::XMP, "Role", "main")
+            .unwrap();
+
+        let json = serde_json::to_string(&meta).unwrap();
+        assert!(json.contains(r#""http://example.com/my/":"my""#));
+        let copy: XmpMeta = serde_json::from_str(&json).unwrap();
+        assert!(copy.semantically_eq(&meta));
+        assert_eq!(
+            copy.get_qualifier("dc", "subject[1]", ns::XMP, "Role"),
+            Some(XmpValue::String("main".to_string()))
+        );
+        assert!(copy.serialize().unwrap().contains("xmlns:my="));
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Event {
+        #[serde(rename = "stEvt:action")]
+        action: String,
+        #[serde(rename = "stEvt:when")]
+        when: Option<String>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Asset {
+        #[serde(rename = "CreatorTool")]
+        tool: String,
+        #[serde(rename = "Rating")]
+        rating: Option<f64>,
+        #[serde(rename = "dc:subject", default)]
+        subjects: Bag<String>,
+        #[serde(rename = "dc:title")]
+        title: Option<LangAlt>,
+        #[serde(rename = "xmpMM:History", default)]
+        history: Vec<Event>,
+    }
+
+    #[test]
+    fn test_schema_mapping() {
+        let asset = Asset {
+            tool: "Editor 1.0".to_string(),
+            rating: Some(4.5),
+            subjects: Bag(vec!["sea".to_string(), "sky".to_string()]),
+            title: Some(LangAlt(vec![(
+                "x-default".to_string(),
+                "Coast".to_string(),
+            )])),
+            history: vec![Event {
+                action: "saved".to_string(),
+                when: None,
+            }],
+        };
+
+        let mut meta = XmpMeta::new();
+        meta.set_property("xmp", "Label", "Red".into()).unwrap();
+        meta.set_property("xmp", "Rating", "1".into()).unwrap();
+        meta.set_property("dc", "subject", "old".into()).unwrap();
+        meta.write_schema("xmp", &asset).unwrap();
+        // Properties not in the struct are kept
+        assert_eq!(
+            meta.get_property("xmp", "Label"),
+            Some(XmpValue::String("Red".to_string()))
+        );
+        assert_eq!(meta.get_array_size("dc", "subject"), Some(2));
+        assert_eq!(
+            meta.get_property("dc", "subject[2]"),
+            Some(XmpValue::String("sky".to_string()))
+        );
+        assert!(meta.has_property(ns::XMP_MM, "History[1]/stEvt:action"));
+
+        // Through XML, where every value is text
+        let reparsed = XmpMeta::parse(&meta.serialize().unwrap()).unwrap();
+        assert_eq!(reparsed.read_schema::<Asset>("xmp").unwrap(), asset);
+
+        // Fields that are None delete their property
+        meta.write_schema(
+            "xmp",
+            &Asset {
+                rating: None,
+                ..asset
+            },
+        )
+        .unwrap();
+        assert!(!meta.has_property("xmp", "Rating"));
+        assert!(meta.has_property("xmp", "Label"));
+
+        let empty: Asset = {
+            let mut meta = XmpMeta::new();
+            meta.set_property("xmp", "CreatorTool", "x".into()).unwrap();
+            meta.read_schema("xmp").unwrap()
+        };
+        assert_eq!(empty.rating, None);
+        assert!(empty.history.is_empty());
+
+        assert!(XmpMeta::new().read_schema::<Asset>("xmp").is_err());
+        assert!(XmpMeta::new().write_schema("xmp", &[1, 2]).is_err());
+    }
+}
@@ -0,0 +1,168 @@
+//! Shared mutable XMP metadata handles
+//!
+//! `XmpMeta` has value semantics: clones are independent copies. When several
+//! owners need to see each other's changes, wrap the metadata in a
+//! [`SharedXmpMeta`] instead. It uses `Arc<RwLock<>>` with the `mutli-thread`
+//! feature and `Rc<RefCell<>>` otherwise.
+
+use super::XmpMeta;
+use crate::core::error::{XmpError, XmpResult};
+
+#[cfg(not(feature = "mutli-thread"))]
+mod impl_ {
+    use super::XmpMeta;
+    use std::cell::{Ref, RefCell, RefMut};
+    use std::rc::Rc;
+
+    /// Single-threaded shared handle
+    pub type Handle = Rc<RefCell<XmpMeta>>;
+
+    /// Read guard for shared metadata
+    pub type SharedReadGuard<'a> = Ref<'a, XmpMeta>;
+
+    /// Write guard for shared metadata
+    pub type SharedWriteGuard<'a> = RefMut<'a, XmpMeta>;
+
+    pub fn new_handle(meta: XmpMeta) -> Handle {
+        Rc::new(RefCell::new(meta))
+    }
+
+    pub fn read(handle: &Handle) -> Option<SharedReadGuard<'_>> {
+        handle.try_borrow().ok()
+    }
+
+    pub fn write(handle: &Handle) -> Option<SharedWriteGuard<'_>> {
+        handle.try_borrow_mut().ok()
+    }
+
+    pub fn ptr_eq(a: &Handle, b: &Handle) -> bool {
+        Rc::ptr_eq(a, b)
+    }
+}
+
+#[cfg(feature = "mutli-thread")]
+mod impl_ {
+    use super::XmpMeta;
+    use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+    /// Multi-threaded shared handle
+    pub type Handle = Arc<RwLock<XmpMeta>>;
+
+    /// Read guard for shared metadata
+    pub type SharedReadGuard<'a> = RwLockReadGuard<'a, XmpMeta>;
+
+    /// Write guard for shared metadata
+    pub type SharedWriteGuard<'a> = RwLockWriteGuard<'a, XmpMeta>;
+
+    pub fn new_handle(meta: XmpMeta) -> Handle {
+        Arc::new(RwLock::new(meta))
+    }
+
+    pub fn read(handle: &Handle) -> Option<SharedReadGuard<'_>> {
+        handle.read().ok()
+    }
+
+    pub fn write(handle: &Handle) -> Option<SharedWriteGuard<'_>> {
+        handle.write().ok()
+    }
+
+    pub fn ptr_eq(a: &Handle, b: &Handle) -> bool {
+        Arc::ptr_eq(a, b)
+    }
+}
+
+pub use impl_::{SharedReadGuard, SharedWriteGuard};
+
+/// A shared, mutable handle to XMP metadata
+///
+/// Cloning a `SharedXmpMeta` creates another handle to the same metadata, so
+/// changes made through one handle are visible through all of them. Use
+/// [`snapshot`](Self::snapshot) to get an independent [`XmpMeta`].
+///
+/// # Example
+///
+/// ```rust
+/// use xmpkit::{SharedXmpMeta, XmpMeta};
+///
+/// let shared = SharedXmpMeta::new(XmpMeta::new());
+/// let other = shared.clone();
+///
+/// other.write()?.set_property("xmp", "CreatorTool", "xmpkit".into())?;
+/// assert!(shared.read()?.has_property("xmp", "CreatorTool"));
+/// # Ok::<(), xmpkit::XmpError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct SharedXmpMeta {
+    handle: impl_::Handle,
+}
+
+impl SharedXmpMeta {
+    /// Wrap metadata in a shared handle
+    pub fn new(meta: XmpMeta) -> Self {
+        Self {
+            handle: impl_::new_handle(meta),
+        }
+    }
+
+    /// Get read access to the metadata
+    ///
+    /// Fails if the metadata is being written (single-threaded mode) or the
+    /// lock is poisoned (multi-threaded mode).
+    pub fn read(&self) -> XmpResult<SharedReadGuard<'_>> {
+        impl_::read(&self.handle)
+            .ok_or_else(|| XmpError::InternalError("Shared metadata is not readable".to_string()))
+    }
+
+    /// Get write access to the metadata
+    ///
+    /// Fails if the metadata is being read or written (single-threaded mode)
+    /// or the lock is poisoned (multi-threaded mode).
+    pub fn write(&self) -> XmpResult<SharedWriteGuard<'_>> {
+        impl_::write(&self.handle)
+            .ok_or_else(|| XmpError::InternalError("Shared metadata is not writable".to_string()))
+    }
+
+    /// Get an independent copy of the current metadata
+    pub fn snapshot(&self) -> XmpResult<XmpMeta> {
+        Ok(self.read()?.clone())
+    }
+
+    /// Check whether two handles refer to the same metadata
+    pub fn ptr_eq(&self, other: &SharedXmpMeta) -> bool {
+        impl_::ptr_eq(&self.handle, &other.handle)
+    }
+}
+
+impl From<XmpMeta> for SharedXmpMeta {
+    fn from(meta: XmpMeta) -> Self {
+        Self::new(meta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shared_handles_see_changes() {
+        let shared = SharedXmpMeta::new(XmpMeta::new());
+        let other = shared.clone();
+        assert!(shared.ptr_eq(&other));
+
+        other
+            .write()
+            .unwrap()
+            .set_property("xmp", "CreatorTool", "xmpkit".into())
+            .unwrap();
+        assert!(shared.read().unwrap().has_property("xmp", "CreatorTool"));
+
+        let snapshot = shared.snapshot().unwrap();
+        shared
+            .write()
+            .unwrap()
+            .delete_property("xmp", "CreatorTool")
+            .unwrap();
+        assert!(snapshot.has_property("xmp", "CreatorTool"));
+        assert!(!other.read().unwrap().has_property("xmp", "CreatorTool"));
+    }
+}
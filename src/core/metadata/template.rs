@@ -0,0 +1,306 @@
+//! Template application for XMP metadata
+//!
+//! This module provides [`XmpMeta::apply_template`], the equivalent of the
+//! Adobe SDK's `XMPUtils::ApplyTemplate`.
+
+use super::node::root_read_with;
+use super::{collect_namespace_uris, item_lang, XmpMeta, X_DEFAULT};
+use crate::core::canonical::encode_node;
+use crate::core::error::XmpResult;
+use crate::core::namespace::ns;
+use crate::core::node::{Node, StructureNode};
+
+/// Options for applying a template with [`XmpMeta::apply_template`].
+///
+/// Use the builder pattern to configure options. With no option set,
+/// applying a template does nothing.
+///
+/// # Example
+///
+/// ```rust
+/// use xmpkit::core::metadata::TemplateOptions;
+///
+/// // Fill in what is missing and extend arrays, but keep existing values
+/// let options = TemplateOptions::default().add_new().merge_arrays();
+/// ```
+#[derive(Default, Clone, Copy, Debug)]
+pub struct TemplateOptions {
+    /// Delete properties that are not in the template
+    pub clear_unnamed: bool,
+    /// Replace properties that exist in both with the template's value
+    pub replace_existing: bool,
+    /// Add template properties that do not exist yet
+    pub add_new: bool,
+    /// Also apply the other options to internal properties
+    pub include_internal: bool,
+    /// Merge structures and arrays that exist in both
+    pub merge_arrays: bool,
+}
+
+impl TemplateOptions {
+    /// Delete properties that are not in the template.
+    ///
+    /// Internal properties are kept unless
+    /// [`include_internal`](Self::include_internal) is set.
+    pub fn clear_unnamed(mut self) -> Self {
+        self.clear_unnamed = true;
+        self
+    }
+
+    /// Replace properties that exist in both with the template's value.
+    pub fn replace_existing(mut self) -> Self {
+        self.replace_existing = true;
+        self
+    }
+
+    /// Add template properties that do not exist yet.
+    pub fn add_new(mut self) -> Self {
+        self.add_new = true;
+        self
+    }
+
+    /// Also apply the other options to internal properties.
+    ///
+    /// Internal properties are the ones maintained by applications and file
+    /// handlers rather than users, such as `xmp:ModifyDate`, `xmpMM:*`,
+    /// `exif:*` and most of `tiff:*`.
+    pub fn include_internal(mut self) -> Self {
+        self.include_internal = true;
+        self
+    }
+
+    /// Merge structures and arrays that exist in both.
+    ///
+    /// Missing structure fields are added. Array items are added unless an
+    /// equal item (see [`XmpMeta::semantically_eq`]) already exists; items
+    /// of language alternatives are matched by `xml:lang`. Ignored when
+    /// [`replace_existing`](Self::replace_existing) is set.
+    pub fn merge_arrays(mut self) -> Self {
+        self.merge_arrays = true;
+        self
+    }
+}
+
+impl XmpMeta {
+    /// Apply a template to the metadata.
+    ///
+    /// Works on whole nodes, so array forms, qualifiers and nested values
+    /// are kept. See [`TemplateOptions`] for what gets added, replaced,
+    /// merged or removed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use xmpkit::core::metadata::TemplateOptions;
+    /// use xmpkit::XmpMeta;
+    ///
+    /// let mut template = XmpMeta::new();
+    /// template.set_localized_text("dc", "rights", "", "x-default", "(c) Example").unwrap();
+    ///
+    /// let mut meta = XmpMeta::new();
+    /// meta.apply_template(&template, TemplateOptions::default().add_new()).unwrap();
+    /// assert!(meta.has_property("dc", "rights"));
+    /// ```
+    pub fn apply_template(
+        &mut self,
+        template: &XmpMeta,
+        options: TemplateOptions,
+    ) -> XmpResult<()> {
+        let template_root = root_read_with(&template.root, |root| root.clone());
+        let applies = |key: &str| options.include_internal || !is_internal_key(key);
+
+        let mut uris = Vec::new();
+        for (key, node) in &template_root.fields {
+            if let Some(pos) = key.rfind(':') {
+                uris.push(key[..pos].to_string());
+            }
+            collect_namespace_uris(node, &mut uris);
+        }
+        self.adopt_namespaces(template, &uris);
+
+        let mut root = root_write!(self.root);
+        if options.clear_unnamed {
+            root.fields
+                .retain(|key, _| template_root.fields.contains_key(key) || !applies(key));
+        }
+
+        for (key, node) in template_root.fields {
+            if !applies(&key) {
+                continue;
+            }
+            match root.get_field_mut(&key) {
+                Some(existing) if options.replace_existing => *existing = node,
+                Some(existing) if options.merge_arrays => merge_node(existing, node),
+                Some(_) => {}
+                None if options.add_new => root.set_field(key, node),
+                None => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Merge a template node into an existing node of the same kind
+fn merge_node(dest: &mut Node, source: Node) {
+    match (dest, source) {
+        (Node::Structure(dest), Node::Structure(source)) => merge_fields(dest, source),
+        (Node::Array(dest), Node::Array(source)) => {
+            let encode = |node: &Node| {
+                let mut out = Vec::new();
+                encode_node(node, &mut out);
+                out
+            };
+            let mut existing: Vec<Vec<u8>> = dest.items.iter().map(encode).collect();
+            for item in source.items {
+                match item_lang(&item) {
+                    Some(lang) => {
+                        if dest
+                            .items
+                            .iter()
+                            .any(|i| item_lang(i).is_some_and(|l| l.eq_ignore_ascii_case(&lang)))
+                        {
+                            continue;
+                        }
+                        if lang == X_DEFAULT {
+                            dest.items.insert(0, item);
+                        } else {
+                            dest.items.push(item);
+                        }
+                    }
+                    None => {
+                        let encoded = encode(&item);
+                        if !existing.contains(&encoded) {
+                            existing.push(encoded);
+                            dest.items.push(item);
+                        }
+                    }
+                }
+            }
+        }
+        // Simple values and mismatched kinds keep the existing value
+        _ => {}
+    }
+}
+
+fn merge_fields(dest: &mut StructureNode, source: StructureNode) {
+    for (key, field) in source.fields {
+        match dest.get_field_mut(&key) {
+            Some(existing) => merge_node(existing, field),
+            None => dest.set_field(key, field),
+        }
+    }
+}
+
+/// Whether a top-level property key (`namespace_uri:name`) is internal
+///
+/// Follows the Adobe SDK: internal properties are maintained by applications
+/// and file handlers, and are left alone by template and removal utilities
+/// unless asked otherwise.
+pub(crate) fn is_internal_key(key: &str) -> bool {
+    let Some(pos) = key.rfind(':') else {
+        return false;
+    };
+    let (schema, name) = (&key[..pos], &key[pos + 1..]);
+    match schema {
+        ns::DC => matches!(name, "format" | "language"),
+        ns::XMP => matches!(
+            name,
+            "BaseURL" | "CreatorTool" | "Format" | "Locale" | "MetadataDate" | "ModifyDate"
+        ),
+        ns::PDF => matches!(
+            name,
+            "BaseURL" | "Creator" | "ModDate" | "PDFVersion" | "Producer"
+        ),
+        ns::TIFF => !matches!(name, "ImageDescription" | "Artist" | "Copyright"),
+        ns::EXIF => name != "UserComment",
+        ns::PHOTOSHOP => matches!(name, "ICCProfile" | "TextLayers"),
+        ns::EXIF_EX | ns::EXIF_AUX | ns::CAMERA_RAW | ns::XMP_MM | ns::XMP_DM => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::value::XmpValue;
+
+    fn subjects(meta: &XmpMeta) -> Vec<String> {
+        let size = meta.get_array_size("dc", "subject").unwrap_or(0);
+        (1..=size)
+            .filter_map(|i| meta.get_property("dc", &format!("subject[{}]", i)))
+            .filter_map(|v| v.as_str().map(String::from))
+            .collect()
+    }
+
+    fn template() -> XmpMeta {
+        let mut template = XmpMeta::new();
+        template
+            .set_localized_text("dc", "rights", "", "x-default", "(c) Example")
+            .unwrap();
+        template
+            .append_array_item("dc", "subject", "stock".into())
+            .unwrap();
+        template
+            .append_array_item("dc", "subject", "beach".into())
+            .unwrap();
+        template
+            .set_property("xmp", "CreatorTool", "Template".into())
+            .unwrap();
+        template
+    }
+
+    #[test]
+    fn test_apply_template_merge() {
+        let mut meta = XmpMeta::new();
+        meta.append_array_item("dc", "subject", "beach".into())
+            .unwrap();
+        meta.set_property("photoshop", "City", "Paris".into())
+            .unwrap();
+        meta.set_property("xmp", "ModifyDate", "2024-01-01".into())
+            .unwrap();
+
+        let options = TemplateOptions::default()
+            .clear_unnamed()
+            .add_new()
+            .merge_arrays();
+        meta.apply_template(&template(), options).unwrap();
+
+        assert_eq!(subjects(&meta), vec!["beach", "stock"]);
+        assert!(matches!(
+            meta.get_property("dc", "rights"),
+            Some(XmpValue::AltText(_))
+        ));
+        // Unnamed properties are removed, internal ones are kept
+        assert!(!meta.has_property("photoshop", "City"));
+        assert!(meta.has_property("xmp", "ModifyDate"));
+        // Internal template properties are skipped
+        assert!(!meta.has_property("xmp", "CreatorTool"));
+    }
+
+    #[test]
+    fn test_apply_template_replace() {
+        let mut meta = XmpMeta::new();
+        meta.append_array_item("dc", "subject", "city".into())
+            .unwrap();
+        meta.set_property("xmp", "CreatorTool", "Camera".into())
+            .unwrap();
+
+        meta.apply_template(&template(), TemplateOptions::default().replace_existing())
+            .unwrap();
+        assert_eq!(subjects(&meta), vec!["stock", "beach"]);
+        assert!(!meta.has_property("dc", "rights"));
+        assert_eq!(
+            meta.get_property("xmp", "CreatorTool"),
+            Some(XmpValue::String("Camera".to_string()))
+        );
+
+        let options = TemplateOptions::default()
+            .replace_existing()
+            .include_internal();
+        meta.apply_template(&template(), options).unwrap();
+        assert_eq!(
+            meta.get_property("xmp", "CreatorTool"),
+            Some(XmpValue::String("Template".to_string()))
+        );
+    }
+}
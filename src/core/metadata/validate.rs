@@ -0,0 +1,396 @@
+//! Validation of metadata against schema descriptions
+//!
+//! [`XmpMeta::validate`] checks the properties known to a
+//! [`SchemaRegistry`] and reports the ones whose form or value does not
+//! match. [`XmpMeta::validate_and_fix`] also repairs what can be repaired
+//! without guessing, such as a simple `dc:creator` that should be a `Seq`.
+
+use super::node::root_read_with;
+use super::XmpMeta;
+use crate::core::error::XmpResult;
+use crate::core::namespace::{get_global_namespace_prefix, ns, NamespaceMap};
+use crate::core::node::{ArrayNode, ArrayType, Node, StructureNode};
+use crate::core::schema::{SchemaRegistry, ValueType};
+use crate::types::qualifier::Qualifier;
+use crate::utils::datetime::XmpDateTime;
+
+/// A property that does not match its schema description
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaViolation {
+    /// Namespace URI of the top-level property
+    pub namespace_uri: String,
+    /// Path of the offending node (e.g., `creator`, `History[2]/stEvt:when`)
+    pub path: String,
+    /// The expected value type of the node
+    pub expected: ValueType,
+    /// What is wrong with the node
+    pub kind: ViolationKind,
+    /// Whether [`XmpMeta::validate_and_fix`] repaired the node
+    pub fixed: bool,
+}
+
+/// The kind of a [`SchemaViolation`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViolationKind {
+    /// The node has the wrong form (e.g., a simple value where a `Seq` is
+    /// expected, or a `Bag` where a `Seq` is expected)
+    WrongForm,
+    /// An item of a language alternative has no `xml:lang` qualifier
+    MissingLanguage,
+    /// The value does not parse as the expected type (e.g., `xmp:Rating`
+    /// set to `five`)
+    InvalidValue,
+}
+
+impl XmpMeta {
+    /// Check the properties known to `registry` against their descriptions
+    ///
+    /// Properties that are not in the registry, and unknown structure fields,
+    /// are not checked.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use xmpkit::core::schema::SchemaRegistry;
+    /// use xmpkit::XmpMeta;
+    ///
+    /// let mut meta = XmpMeta::new();
+    /// meta.set_property("xmp", "Rating", "five".into()).unwrap();
+    ///
+    /// let violations = meta.validate(&SchemaRegistry::new());
+    /// assert_eq!(violations.len(), 1);
+    /// assert_eq!(violations[0].path, "Rating");
+    /// ```
+    pub fn validate(&self, registry: &SchemaRegistry) -> Vec<SchemaViolation> {
+        let mut root = root_read_with(&self.root, |root| Some(root.clone()))
+            .unwrap_or_else(StructureNode::new);
+        Validator::new(registry, &self.namespaces, false).run(&mut root)
+    }
+
+    /// Like [`validate`](Self::validate), but also repair what can be repaired
+    ///
+    /// Simple values are wrapped into the expected array form (language
+    /// alternatives get an `x-default` item), arrays of the wrong form are
+    /// converted, and the only item of a language alternative gets
+    /// `x-default` if it has no language. Invalid values are left alone.
+    /// All violations are returned, with [`SchemaViolation::fixed`] set for
+    /// the repaired ones.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use xmpkit::core::schema::SchemaRegistry;
+    /// use xmpkit::{XmpMeta, XmpValue};
+    ///
+    /// let mut meta = XmpMeta::new();
+    /// meta.set_property("dc", "creator", "Jane Doe".into()).unwrap();
+    ///
+    /// let violations = meta.validate_and_fix(&SchemaRegistry::new()).unwrap();
+    /// assert!(violations[0].fixed);
+    /// assert_eq!(
+    ///     meta.get_array_item("dc", "creator", 0),
+    ///     Some(XmpValue::String("Jane Doe".to_string()))
+    /// );
+    /// ```
+    pub fn validate_and_fix(
+        &mut self,
+        registry: &SchemaRegistry,
+    ) -> XmpResult<Vec<SchemaViolation>> {
+        let mut root = root_write!(self.root);
+        Ok(Validator::new(registry, &self.namespaces, true).run(&mut root))
+    }
+}
+
+/// Walks a tree and records the violations
+struct Validator<'a> {
+    registry: &'a SchemaRegistry,
+    namespaces: &'a NamespaceMap,
+    fix: bool,
+    violations: Vec<SchemaViolation>,
+}
+
+impl<'a> Validator<'a> {
+    fn new(registry: &'a SchemaRegistry, namespaces: &'a NamespaceMap, fix: bool) -> Self {
+        Self {
+            registry,
+            namespaces,
+            fix,
+            violations: Vec::new(),
+        }
+    }
+
+    fn run(mut self, root: &mut StructureNode) -> Vec<SchemaViolation> {
+        for (key, node) in root.fields.iter_mut() {
+            let Some(pos) = key.rfind(':') else {
+                continue;
+            };
+            let (ns_uri, name) = (&key[..pos], &key[pos + 1..]);
+            if let Some(expected) = self.registry.get(ns_uri, name) {
+                self.check(ns_uri, name.to_string(), node, expected);
+            }
+        }
+        self.violations
+    }
+
+    fn report(
+        &mut self,
+        ns_uri: &str,
+        path: &str,
+        expected: &ValueType,
+        kind: ViolationKind,
+        fixed: bool,
+    ) {
+        self.violations.push(SchemaViolation {
+            namespace_uri: ns_uri.to_string(),
+            path: path.to_string(),
+            expected: expected.clone(),
+            kind,
+            fixed,
+        });
+    }
+
+    fn check(&mut self, ns_uri: &str, path: String, node: &mut Node, expected: &ValueType) {
+        match expected {
+            ValueType::LangAlt => self.check_lang_alt(ns_uri, &path, node),
+            ValueType::Seq(item) => {
+                self.check_array(ns_uri, path, node, expected, ArrayType::Ordered, item)
+            }
+            ValueType::Bag(item) => {
+                self.check_array(ns_uri, path, node, expected, ArrayType::Unordered, item)
+            }
+            ValueType::Alt(item) => {
+                self.check_array(ns_uri, path, node, expected, ArrayType::Alternative, item)
+            }
+            ValueType::Struct(fields) => {
+                let Node::Structure(structure) = node else {
+                    self.report(ns_uri, &path, expected, ViolationKind::WrongForm, false);
+                    return;
+                };
+                for field in fields {
+                    let key = format!("{}:{}", field.namespace_uri, field.name);
+                    if let Some(child) = structure.fields.get_mut(&key) {
+                        let prefix = self.prefix(&field.namespace_uri);
+                        let child_path = format!("{}/{}:{}", path, prefix, field.name);
+                        self.check(ns_uri, child_path, child, &field.value_type);
+                    }
+                }
+            }
+            _ => match node {
+                Node::Simple(simple) => {
+                    if !is_valid_value(expected, &simple.value) {
+                        self.report(ns_uri, &path, expected, ViolationKind::InvalidValue, false);
+                    }
+                }
+                _ => self.report(ns_uri, &path, expected, ViolationKind::WrongForm, false),
+            },
+        }
+    }
+
+    fn check_array(
+        &mut self,
+        ns_uri: &str,
+        path: String,
+        node: &mut Node,
+        expected: &ValueType,
+        array_type: ArrayType,
+        item_type: &ValueType,
+    ) {
+        match node {
+            Node::Simple(_) => {
+                self.report(ns_uri, &path, expected, ViolationKind::WrongForm, self.fix);
+                if !self.fix {
+                    return;
+                }
+                wrap_in_array(node, array_type);
+            }
+            Node::Structure(_) => {
+                self.report(ns_uri, &path, expected, ViolationKind::WrongForm, false);
+                return;
+            }
+            Node::Array(array) if array.array_type != array_type => {
+                self.report(ns_uri, &path, expected, ViolationKind::WrongForm, self.fix);
+                if self.fix {
+                    array.array_type = array_type;
+                }
+            }
+            Node::Array(_) => {}
+        }
+
+        if let Node::Array(array) = node {
+            for (index, item) in array.items.iter_mut().enumerate() {
+                self.check(ns_uri, format!("{}[{}]", path, index + 1), item, item_type);
+            }
+        }
+    }
+
+    fn check_lang_alt(&mut self, ns_uri: &str, path: &str, node: &mut Node) {
+        let expected = &ValueType::LangAlt;
+        match node {
+            Node::Simple(_) => {
+                self.report(ns_uri, path, expected, ViolationKind::WrongForm, self.fix);
+                if !self.fix {
+                    return;
+                }
+                wrap_in_array(node, ArrayType::Alternative);
+            }
+            Node::Structure(_) => {
+                self.report(ns_uri, path, expected, ViolationKind::WrongForm, false);
+                return;
+            }
+            Node::Array(array) if array.array_type != ArrayType::Alternative => {
+                self.report(ns_uri, path, expected, ViolationKind::WrongForm, self.fix);
+                if self.fix {
+                    array.array_type = ArrayType::Alternative;
+                }
+            }
+            Node::Array(_) => {}
+        }
+
+        let Node::Array(array) = node else {
+            return;
+        };
+        let single = array.items.len() == 1;
+        for (index, item) in array.items.iter_mut().enumerate() {
+            let item_path = format!("{}[{}]", path, index + 1);
+            if !item.is_simple() {
+                self.report(
+                    ns_uri,
+                    &item_path,
+                    &ValueType::Text,
+                    ViolationKind::WrongForm,
+                    false,
+                );
+            } else if item.get_qualifier(ns::XML, "lang").is_none() {
+                let fixed = self.fix && single;
+                self.report(
+                    ns_uri,
+                    &item_path,
+                    expected,
+                    ViolationKind::MissingLanguage,
+                    fixed,
+                );
+                if fixed {
+                    item.set_qualifier(Qualifier::new(ns::XML, "lang", "x-default"));
+                }
+            }
+        }
+    }
+
+    /// Prefix used for a namespace in violation paths
+    fn prefix(&self, uri: &str) -> String {
+        self.namespaces
+            .get_prefix(uri)
+            .map(String::from)
+            .or_else(|| get_global_namespace_prefix(uri))
+            .unwrap_or_else(|| uri.to_string())
+    }
+}
+
+/// Replace a simple node with an array holding it as its only item
+///
+/// The item of a language alternative gets `x-default` if it has no language.
+fn wrap_in_array(node: &mut Node, array_type: ArrayType) {
+    let mut item = std::mem::replace(node, Node::array(array_type));
+    if array_type == ArrayType::Alternative && item.get_qualifier(ns::XML, "lang").is_none() {
+        item.set_qualifier(Qualifier::new(ns::XML, "lang", "x-default"));
+    }
+    let mut array = ArrayNode::new(array_type);
+    array.append(item);
+    *node = Node::Array(array);
+}
+
+/// Whether a simple value parses as the expected simple type
+fn is_valid_value(expected: &ValueType, value: &str) -> bool {
+    let value = value.trim();
+    let is_integer = |v: &str| v.strip_prefix('+').unwrap_or(v).parse::<i64>().is_ok();
+    match expected {
+        ValueType::Integer => is_integer(value),
+        ValueType::Real => value.parse::<f64>().is_ok_and(f64::is_finite),
+        ValueType::Boolean => {
+            value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false")
+        }
+        ValueType::Date => XmpDateTime::parse(value).is_ok(),
+        ValueType::Rational => value
+            .split_once('/')
+            .is_some_and(|(num, den)| is_integer(num) && is_integer(den)),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::value::XmpValue;
+
+    const MALFORMED: &str = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+<rdf:Description rdf:about=""
+    xmlns:dc="http://purl.org/dc/elements/1.1/"
+    xmlns:xmp="http://ns.adobe.com/xap/1.0/"
+    xmlns:exif="http://ns.adobe.com/exif/1.0/"
+    dc:creator="Jane Doe"
+    dc:title="Sunset"
+    xmp:Rating="five"
+    xmp:CreateDate="2024-03-01T10:00:00Z"
+    exif:FNumber="28/10">
+  <dc:subject>
+    <rdf:Seq><rdf:li>beach</rdf:li><rdf:li>sunset</rdf:li></rdf:Seq>
+  </dc:subject>
+</rdf:Description>
+</rdf:RDF>
+</x:xmpmeta>"#;
+
+    fn find<'a>(violations: &'a [SchemaViolation], path: &str) -> &'a SchemaViolation {
+        violations.iter().find(|v| v.path == path).unwrap()
+    }
+
+    #[test]
+    fn test_validate() {
+        let meta = XmpMeta::parse(MALFORMED).unwrap();
+        let violations = meta.validate(&SchemaRegistry::new());
+        assert_eq!(violations.len(), 4);
+        assert_eq!(find(&violations, "creator").kind, ViolationKind::WrongForm);
+        assert_eq!(find(&violations, "title").expected, ValueType::LangAlt);
+        assert_eq!(find(&violations, "subject").kind, ViolationKind::WrongForm);
+        let rating = find(&violations, "Rating");
+        assert_eq!(rating.kind, ViolationKind::InvalidValue);
+        assert_eq!(rating.namespace_uri, ns::XMP);
+        assert!(violations.iter().all(|v| !v.fixed));
+        // Validation does not touch the metadata
+        assert_eq!(
+            meta.get_property("dc", "creator"),
+            Some(XmpValue::String("Jane Doe".to_string()))
+        );
+
+        let mut meta = XmpMeta::new();
+        meta.set_struct_field("xmpMM", "History[1]", "stEvt:when", "yesterday".into())
+            .unwrap();
+        let violations = meta.validate(&SchemaRegistry::new());
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].path, "History[1]/stEvt:when");
+        assert_eq!(violations[0].expected, ValueType::Date);
+    }
+
+    #[test]
+    fn test_validate_and_fix() {
+        let mut meta = XmpMeta::parse(MALFORMED).unwrap();
+        let violations = meta.validate_and_fix(&SchemaRegistry::new()).unwrap();
+        assert_eq!(violations.len(), 4);
+        assert!(violations.iter().all(|v| v.fixed == (v.path != "Rating")));
+
+        assert_eq!(
+            meta.get_array_item("dc", "creator", 0),
+            Some(XmpValue::String("Jane Doe".to_string()))
+        );
+        assert_eq!(
+            meta.get_localized_text("dc", "title", "", "x-default"),
+            Some(("Sunset".to_string(), "x-default".to_string()))
+        );
+        assert!(meta.serialize().unwrap().contains("<rdf:Bag>"));
+
+        // Only the invalid value is left
+        let violations = meta.validate(&SchemaRegistry::new());
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].path, "Rating");
+    }
+}
@@ -3,21 +3,35 @@
 //! This module contains the core functionality for XMP metadata processing,
 //! including parsing, manipulation, and serialization.
 
+pub mod alias;
+mod canonical;
+pub mod encoding;
 pub mod error;
+#[cfg(feature = "serde")]
+pub mod mapping;
 pub mod metadata;
 pub mod namespace;
 pub mod node;
 pub mod parser;
+pub mod schema;
 pub mod serializer;
+pub mod views;
 pub mod xpath;
 
-pub use error::{XmpError, XmpResult};
-pub use metadata::XmpMeta;
+pub use alias::{get_all_aliases, register_alias, resolve_alias, AliasForm, AliasInfo, AliasMap};
+pub use encoding::PacketEncoding;
+pub use error::{ParseErrorInfo, ParseLocation, XmpError, XmpResult};
+pub use metadata::{
+    IterOptions, RemoveOptions, SchemaViolation, SeparateOptions, SharedXmpMeta, TemplateOptions,
+    XmpDiff, XmpIterItem, XmpIterator, XmpMeta, XmpNodeKind,
+};
 pub use namespace::{
     get_all_registered_namespaces, get_builtin_namespace_uris, get_global_namespace_prefix,
     get_global_namespace_uri, register_namespace, NamespaceMap,
 };
 pub use node::{ArrayNode, ArrayType, Node, SimpleNode, StructureNode};
-pub use parser::XmpParser;
+pub use parser::{ParseLimits, ParseOptions, ParseWarning, ParseWarningKind, XmpParser};
+pub use schema::{SchemaRegistry, ValueType};
 pub use serializer::XmpSerializer;
+pub use views::{DublinCore, Iptc, MediaManagement, XmpBasic, XmpRights};
 pub use xpath::{build_path, parse_path, PathComponent, PathComponents};
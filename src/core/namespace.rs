@@ -52,6 +52,12 @@ pub mod ns {
     pub const XMP_GRAPHICS: &str = "http://ns.adobe.com/xap/1.0/g/";
     /// XMP Image namespace
     pub const XMP_IMAGE: &str = "http://ns.adobe.com/xap/1.0/g/img/";
+    /// Resource Event structure namespace (xmpMM:History)
+    pub const ST_EVT: &str = "http://ns.adobe.com/xap/1.0/sType/ResourceEvent#";
+    /// Resource Reference structure namespace (xmpMM:DerivedFrom, xmpMM:Ingredients)
+    pub const ST_REF: &str = "http://ns.adobe.com/xap/1.0/sType/ResourceRef#";
+    /// Version structure namespace (xmpMM:Versions)
+    pub const ST_VER: &str = "http://ns.adobe.com/xap/1.0/sType/Version#";
     /// RDF namespace
     pub const RDF: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
     /// XML namespace (for xml:lang, etc.)
@@ -100,6 +106,12 @@ pub mod ns {
     pub const XMP_GRAPHICS_PREFIX: &str = "xmpG";
     /// XMP Image prefix
     pub const XMP_IMAGE_PREFIX: &str = "xmpGImg";
+    /// Resource Event structure namespace prefix
+    pub const ST_EVT_PREFIX: &str = "stEvt";
+    /// Resource Reference structure namespace prefix
+    pub const ST_REF_PREFIX: &str = "stRef";
+    /// Version structure namespace prefix
+    pub const ST_VER_PREFIX: &str = "stVer";
 }
 
 /// Map of namespace URI to prefix
@@ -204,6 +216,9 @@ impl NamespaceMap {
         self.register(ns::XMP_GRAPHICS, ns::XMP_GRAPHICS_PREFIX)
             .unwrap();
         self.register(ns::XMP_IMAGE, ns::XMP_IMAGE_PREFIX).unwrap();
+        self.register(ns::ST_EVT, ns::ST_EVT_PREFIX).unwrap();
+        self.register(ns::ST_REF, ns::ST_REF_PREFIX).unwrap();
+        self.register(ns::ST_VER, ns::ST_VER_PREFIX).unwrap();
     }
 }
 
@@ -288,6 +303,9 @@ pub fn get_builtin_namespace_uris() -> Vec<String> {
         ns::XMP_PAGED.to_string(),
         ns::XMP_GRAPHICS.to_string(),
         ns::XMP_IMAGE.to_string(),
+        ns::ST_EVT.to_string(),
+        ns::ST_REF.to_string(),
+        ns::ST_VER.to_string(),
         ns::RDF.to_string(),
         ns::XML.to_string(),
     ]
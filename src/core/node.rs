@@ -6,11 +6,13 @@
 //! - StructureNode: A structure containing named fields
 
 use crate::core::error::{XmpError, XmpResult};
+use crate::core::namespace::ns;
 use crate::types::qualifier::Qualifier;
 use indexmap::IndexMap;
 
 /// Type of array node
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ArrayType {
     /// Ordered array (rdf:Seq)
     Ordered,
@@ -33,11 +35,22 @@ impl ArrayType {
 
 /// A simple value node
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SimpleNode {
     /// The value of the node
     pub value: String,
     /// Qualifiers attached to this node
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
     pub qualifiers: Vec<Qualifier>,
+    /// Whether the value is a URI (serialized as `rdf:resource`)
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "std::ops::Not::not")
+    )]
+    pub is_uri: bool,
 }
 
 impl SimpleNode {
@@ -46,6 +59,15 @@ impl SimpleNode {
         Self {
             value: value.into(),
             qualifiers: Vec::new(),
+            is_uri: false,
+        }
+    }
+
+    /// Create a new simple node holding a URI
+    pub fn uri(value: impl Into<String>) -> Self {
+        Self {
+            is_uri: true,
+            ..Self::new(value)
         }
     }
 
@@ -72,12 +94,17 @@ impl SimpleNode {
 
 /// An array node containing multiple child nodes
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ArrayNode {
     /// The items in the array
     pub items: Vec<Node>,
     /// The type of array
     pub array_type: ArrayType,
     /// Qualifiers attached to this node
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
     pub qualifiers: Vec<Qualifier>,
 }
 
@@ -152,14 +179,48 @@ impl ArrayNode {
             .iter()
             .find(|q| q.namespace == namespace && q.name == name)
     }
+
+    /// Remove a qualifier
+    pub fn remove_qualifier(&mut self, namespace: &str, name: &str) -> bool {
+        let initial_len = self.qualifiers.len();
+        self.qualifiers
+            .retain(|q| !(q.namespace == namespace && q.name == name));
+        self.qualifiers.len() < initial_len
+    }
+
+    /// Check if this is a language alternative (an `rdf:Alt` whose items
+    /// are all simple values carrying an `xml:lang` qualifier)
+    pub fn is_alt_text(&self) -> bool {
+        self.alt_text_items().is_some()
+    }
+
+    /// Collect the `(language, text)` pairs of a language alternative
+    fn alt_text_items(&self) -> Option<Vec<(String, String)>> {
+        if self.array_type != ArrayType::Alternative || self.items.is_empty() {
+            return None;
+        }
+        self.items
+            .iter()
+            .map(|item| {
+                let simple = item.as_simple()?;
+                let lang = simple.get_qualifier(ns::XML, "lang")?;
+                Some((lang.value.clone(), simple.value.clone()))
+            })
+            .collect()
+    }
 }
 
 /// A structure node containing named fields
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StructureNode {
     /// The fields in the structure
     pub fields: IndexMap<String, Node>,
     /// Qualifiers attached to this node
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
     pub qualifiers: Vec<Qualifier>,
 }
 
@@ -213,6 +274,14 @@ impl StructureNode {
             .iter()
             .find(|q| q.namespace == namespace && q.name == name)
     }
+
+    /// Remove a qualifier
+    pub fn remove_qualifier(&mut self, namespace: &str, name: &str) -> bool {
+        let initial_len = self.qualifiers.len();
+        self.qualifiers
+            .retain(|q| !(q.namespace == namespace && q.name == name));
+        self.qualifiers.len() < initial_len
+    }
 }
 
 impl Default for StructureNode {
@@ -223,6 +292,7 @@ impl Default for StructureNode {
 
 /// A node in the XMP data model
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Node {
     /// A simple value node
     Simple(SimpleNode),
@@ -235,10 +305,14 @@ pub enum Node {
 impl From<&Node> for crate::XmpValue {
     fn from(node: &Node) -> Self {
         match node {
+            Node::Simple(node) if node.is_uri => Self::Uri(node.value.clone()),
             Node::Simple(node) => Self::String(node.value.clone()),
             Node::Array(node) => {
+                if let Some(items) = node.alt_text_items() {
+                    return Self::AltText(items);
+                }
                 let array = node.items.iter().map(|item| item.into()).collect();
-                Self::Array(array)
+                Self::Array(node.array_type, array)
             }
             Node::Structure(node) => {
                 let values = node
@@ -329,6 +403,51 @@ impl Node {
             _ => None,
         }
     }
+
+    /// Get the qualifiers attached to this node
+    pub fn qualifiers(&self) -> &[Qualifier] {
+        match self {
+            Node::Simple(node) => &node.qualifiers,
+            Node::Array(node) => &node.qualifiers,
+            Node::Structure(node) => &node.qualifiers,
+        }
+    }
+
+    /// Get a mutable reference to the qualifiers attached to this node
+    pub fn qualifiers_mut(&mut self) -> &mut Vec<Qualifier> {
+        match self {
+            Node::Simple(node) => &mut node.qualifiers,
+            Node::Array(node) => &mut node.qualifiers,
+            Node::Structure(node) => &mut node.qualifiers,
+        }
+    }
+
+    /// Get a qualifier by name
+    pub fn get_qualifier(&self, namespace: &str, name: &str) -> Option<&Qualifier> {
+        self.qualifiers()
+            .iter()
+            .find(|q| q.namespace == namespace && q.name == name)
+    }
+
+    /// Set a qualifier, replacing the value of an existing one with the same name
+    pub fn set_qualifier(&mut self, qualifier: Qualifier) {
+        let qualifiers = self.qualifiers_mut();
+        match qualifiers
+            .iter_mut()
+            .find(|q| q.namespace == qualifier.namespace && q.name == qualifier.name)
+        {
+            Some(existing) => existing.value = qualifier.value,
+            None => qualifiers.push(qualifier),
+        }
+    }
+
+    /// Remove a qualifier
+    pub fn remove_qualifier(&mut self, namespace: &str, name: &str) -> bool {
+        let qualifiers = self.qualifiers_mut();
+        let initial_len = qualifiers.len();
+        qualifiers.retain(|q| !(q.namespace == namespace && q.name == name));
+        qualifiers.len() < initial_len
+    }
 }
 
 #[cfg(test)]
@@ -425,12 +544,68 @@ mod tests {
             assert!(structure.contains_key("field1"));
             assert!(matches!(structure.get("field1"), Some(XmpValue::String(s)) if s == "value1"));
             assert!(structure.contains_key("bag1"));
-            assert!(matches!(structure.get("bag1"), Some(XmpValue::Array(a)) if a.len() == 2));
+            assert!(matches!(
+                structure.get("bag1"),
+                Some(XmpValue::Array(ArrayType::Unordered, a)) if a.len() == 2
+            ));
         } else {
             unreachable!();
         }
     }
 
+    #[test]
+    fn test_array_node_to_value_keeps_form() {
+        use crate::XmpValue;
+
+        let mut seq = ArrayNode::new(ArrayType::Ordered);
+        seq.append(Node::simple("first"));
+        seq.append(Node::simple("second"));
+        let value: XmpValue = (&Node::Array(seq)).into();
+        assert_eq!(
+            value,
+            XmpValue::Array(ArrayType::Ordered, vec!["first".into(), "second".into()])
+        );
+
+        let mut alt = ArrayNode::new(ArrayType::Alternative);
+        let mut item = SimpleNode::new("Title");
+        item.add_qualifier(Qualifier::new(ns::XML, "lang", "x-default"));
+        alt.append(Node::Simple(item));
+        assert!(alt.is_alt_text());
+        let value: XmpValue = (&Node::Array(alt)).into();
+        assert_eq!(
+            value,
+            XmpValue::AltText(vec![("x-default".to_string(), "Title".to_string())])
+        );
+
+        let mut alt = ArrayNode::new(ArrayType::Alternative);
+        alt.append(Node::simple("no-lang"));
+        assert!(!alt.is_alt_text());
+        let value: XmpValue = (&Node::Array(alt)).into();
+        assert_eq!(
+            value,
+            XmpValue::Array(ArrayType::Alternative, vec!["no-lang".into()])
+        );
+    }
+
+    #[test]
+    fn test_node_qualifiers() {
+        let mut node = Node::array(ArrayType::Unordered);
+        assert!(node.qualifiers().is_empty());
+
+        node.set_qualifier(Qualifier::new("http://example.com/ns/", "role", "author"));
+        node.set_qualifier(Qualifier::new("http://example.com/ns/", "role", "editor"));
+        assert_eq!(node.qualifiers().len(), 1);
+        assert_eq!(
+            node.get_qualifier("http://example.com/ns/", "role")
+                .map(|q| q.value.as_str()),
+            Some("editor")
+        );
+
+        assert!(node.remove_qualifier("http://example.com/ns/", "role"));
+        assert!(!node.remove_qualifier("http://example.com/ns/", "role"));
+        assert!(node.qualifiers().is_empty());
+    }
+
     #[test]
     fn test_node_creation() {
         let simple = Node::simple("test");
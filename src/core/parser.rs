@@ -1,31 +1,652 @@
 //! XMP XML/RDF parser
 //!
 //! This module provides functionality for parsing XMP Packets from XML/RDF format.
-
-use crate::core::error::{XmpError, XmpResult};
-use crate::core::namespace::{ns, NamespaceMap};
-use crate::core::node::{Node, StructureNode};
+//!
+//! Parsing happens in two passes: the XML is read into a small element tree
+//! with its namespaces resolved, and the tree is then interpreted with the
+//! XMP subset of the RDF/XML grammar (XMP Specification Part 1, 7.9).
+
+use crate::core::encoding::{PacketEncoding, PacketReader};
+use crate::core::error::{ParseErrorInfo, ParseLocation, XmpError, XmpResult};
+use crate::core::namespace::{get_global_namespace_uri, ns, NamespaceMap};
+use crate::core::node::{ArrayNode, ArrayType, Node, SimpleNode, StructureNode};
 use crate::types::qualifier::Qualifier;
-use quick_xml::escape::unescape;
-use quick_xml::events::Event;
+use quick_xml::escape::{escape, unescape};
+use quick_xml::events::{BytesStart, Event};
 use quick_xml::Reader;
+use std::borrow::Cow;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read};
+
+/// An XML element, with its namespaces resolved
+#[derive(Debug)]
+struct XmlElement {
+    /// Qualified name as written (e.g., `dc:creator`)
+    name: String,
+    /// Namespace URI, or empty if the prefix is not declared
+    ns_uri: String,
+    /// Local name
+    local: String,
+    /// Attributes, without namespace declarations
+    attrs: Vec<XmlAttribute>,
+    /// Child elements
+    children: Vec<XmlElement>,
+    /// Character data directly inside the element, unescaped
+    text: String,
+    /// Byte offset of the start tag in the packet
+    offset: u64,
+}
+
+/// An XML attribute, with its namespace resolved
+#[derive(Debug)]
+struct XmlAttribute {
+    name: String,
+    ns_uri: String,
+    local: String,
+    value: String,
+}
+
+impl XmlElement {
+    fn is_rdf(&self, local: &str) -> bool {
+        self.ns_uri == ns::RDF && self.local == local
+    }
+
+    /// Whether the element was dropped for its unknown prefix (lenient mode)
+    fn is_dropped(&self) -> bool {
+        self.ns_uri.is_empty() && self.name.contains(':')
+    }
+
+    /// Find the `x:xmpmeta` element, this one or a descendant
+    fn find_xmp_meta(&self) -> Option<&XmlElement> {
+        if self.ns_uri == XMP_META_NS && matches!(self.local.as_str(), "xmpmeta" | "xapmeta") {
+            return Some(self);
+        }
+        self.children.iter().find_map(Self::find_xmp_meta)
+    }
+
+    fn is_container(&self) -> bool {
+        self.is_rdf("Seq") || self.is_rdf("Bag") || self.is_rdf("Alt")
+    }
+
+    /// The node key of the element as a property (`namespace_uri:name`)
+    fn key(&self) -> String {
+        if self.ns_uri.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}:{}", self.ns_uri, self.local)
+        }
+    }
+
+    fn has_text(&self) -> bool {
+        !self.text.trim().is_empty()
+    }
+
+    fn lang(&self) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|attr| attr.is_lang())
+            .map(|attr| attr.value.as_str())
+    }
+
+    /// The start tag, rebuilt from the name and attributes, as a snippet
+    fn start_tag(&self) -> String {
+        let mut tag = format!("<{}", self.name);
+        for attr in &self.attrs {
+            tag.push_str(&format!(
+                " {}=\"{}\"",
+                attr.name,
+                escape(attr.value.as_str())
+            ));
+        }
+        tag.push('>');
+        truncate_snippet(&tag)
+    }
+}
+
+impl XmlAttribute {
+    fn is_lang(&self) -> bool {
+        (self.ns_uri == ns::XML && self.local == "lang") || self.name == "lang"
+    }
+
+    fn key(&self) -> String {
+        format!("{}:{}", self.ns_uri, self.local)
+    }
+
+    fn to_qualifier(&self) -> Qualifier {
+        Qualifier::new(&self.ns_uri, &self.local, &self.value)
+    }
+}
+
+/// The attributes of a property element, sorted by their RDF role
+#[derive(Default)]
+struct PropertyAttributes<'a> {
+    lang: Option<&'a str>,
+    parse_type: Option<&'a str>,
+    resource: Option<&'a str>,
+    value: Option<&'a str>,
+    node_id: bool,
+    /// Property attributes: fields of a structure, or qualifiers of a value
+    others: Vec<&'a XmlAttribute>,
+}
+
+impl<'a> PropertyAttributes<'a> {
+    fn of(element: &'a XmlElement) -> XmpResult<Self> {
+        let mut attrs = Self::default();
+        for attr in &element.attrs {
+            if attr.is_lang() {
+                attrs.lang = Some(&attr.value);
+            } else if attr.ns_uri == ns::RDF {
+                match attr.local.as_str() {
+                    // Identifiers and datatypes carry no XMP data
+                    "ID" | "datatype" => {}
+                    "nodeID" => attrs.node_id = true,
+                    "parseType" => attrs.parse_type = Some(&attr.value),
+                    "resource" => attrs.resource = Some(&attr.value),
+                    "value" => attrs.value = Some(&attr.value),
+                    _ => {
+                        return Err(rdf_error(
+                            element,
+                            &format!("invalid attribute {}", attr.name),
+                        ))
+                    }
+                }
+            } else if !attr.ns_uri.is_empty() {
+                attrs.others.push(attr);
+            }
+        }
+        if attrs.resource.is_some() && attrs.value.is_some() {
+            return Err(rdf_error(
+                element,
+                "rdf:resource and rdf:value cannot be used together",
+            ));
+        }
+        Ok(attrs)
+    }
+
+    fn lang_qualifiers(&self) -> Vec<Qualifier> {
+        self.lang
+            .map(|lang| Qualifier::new(ns::XML, "lang", lang))
+            .into_iter()
+            .collect()
+    }
+
+    /// Language and property attributes as qualifiers of a simple value
+    fn qualifiers(&self) -> Vec<Qualifier> {
+        let mut qualifiers = self.lang_qualifiers();
+        qualifiers.extend(self.others.iter().map(|attr| attr.to_qualifier()));
+        qualifiers
+    }
+
+    /// A structure holding the property attributes as fields
+    fn structure(&self) -> StructureNode {
+        let mut structure = StructureNode::new();
+        structure.qualifiers = self.lang_qualifiers();
+        for attr in &self.others {
+            structure.set_field(attr.key(), Node::simple(attr.value.clone()));
+        }
+        structure
+    }
+}
+
+/// An error in the RDF of `element`
+///
+/// The line, column and element path are filled in by
+/// [`XmpParser::locate_rdf_error`].
+fn rdf_error(element: &XmlElement, message: &str) -> XmpError {
+    XmpError::ParseError(ParseErrorInfo {
+        message: format!("Invalid RDF at {}: {}", element.name, message),
+        location: Some(Box::new(ParseLocation {
+            offset: element.offset,
+            snippet: element.start_tag(),
+            ..Default::default()
+        })),
+    })
+}
+
+fn xml_error(message: impl std::fmt::Display) -> XmpError {
+    XmpError::ParseError(format!("XML parsing error: {}", message).into())
+}
+
+/// Attach a location to an error of the XML pass, unless it has one
+fn locate_xml_error(
+    error: XmpError,
+    source: &SourceLines,
+    offset: u64,
+    path: Vec<String>,
+    snippet: Option<String>,
+) -> XmpError {
+    let XmpError::ParseError(mut info) = error else {
+        return error;
+    };
+    if info.location.is_none() {
+        let (line, column) = line_column(&source.line_starts, offset);
+        info.location = Some(Box::new(ParseLocation {
+            offset,
+            line,
+            column,
+            path,
+            snippet: source.snippet(offset).or(snippet).unwrap_or_default(),
+        }));
+    }
+    XmpError::ParseError(info)
+}
 
-struct StackFrame {
-    key: String,
-    node: Node,
-    accumulated_text: String,
+/// Locate an error in the innermost open element
+fn locate_in_element(
+    error: XmpError,
+    source: &SourceLines,
+    stack: &[(XmlElement, String, usize)],
+) -> XmpError {
+    let Some((element, _, _)) = stack.last() else {
+        return error;
+    };
+    locate_xml_error(
+        error,
+        source,
+        element.offset,
+        stack_path(stack),
+        Some(element.start_tag()),
+    )
+}
+
+/// Names of the open elements, outermost first
+fn stack_path(stack: &[(XmlElement, String, usize)]) -> Vec<String> {
+    stack
+        .iter()
+        .map(|(element, _, _)| element.name.clone())
+        .collect()
+}
+
+/// Path to the element starting at `offset`
+fn element_path(elements: &[XmlElement], offset: u64) -> Option<Vec<String>> {
+    // Children start after their parent, and before the next sibling
+    let element = elements.iter().rev().find(|e| e.offset <= offset)?;
+    if element.offset == offset {
+        return Some(vec![element.name.clone()]);
+    }
+    let mut path = element_path(&element.children, offset)?;
+    path.insert(0, element.name.clone());
+    Some(path)
+}
+
+/// Line and column (both starting at 1) of a byte offset
+fn line_column(line_starts: &[u64], offset: u64) -> (u64, u64) {
+    let line = line_starts.partition_point(|&start| start <= offset);
+    let line_start = if line == 0 { 0 } else { line_starts[line - 1] };
+    (line as u64 + 1, offset - line_start + 1)
+}
+
+/// Maximum length of a snippet in characters
+const SNIPPET_LEN: usize = 80;
+
+/// The first line of `text`, shortened to [`SNIPPET_LEN`] characters
+fn truncate_snippet(text: &str) -> String {
+    let line = text.lines().next().unwrap_or_default().trim_end();
+    match line.char_indices().nth(SNIPPET_LEN) {
+        Some((end, _)) => format!("{}...", &line[..end]),
+        None => line.to_string(),
+    }
+}
+
+/// What has been read of the XML source, to locate errors
+///
+/// The lines are recorded as the bytes are read, and only the last bytes
+/// are kept for snippets, so the packet is never held as a whole.
+#[derive(Default)]
+struct SourceLines {
+    /// Offset of the next byte in the packet
+    position: u64,
+    /// Offsets at which the lines after the first start
+    line_starts: Vec<u64>,
+    /// The last bytes read, from offset `recent_start`
+    recent: Vec<u8>,
+    recent_start: u64,
+}
+
+impl SourceLines {
+    /// Bytes kept for snippets
+    const RECENT_LEN: usize = 1024;
+
+    fn record(&mut self, bytes: &[u8]) {
+        for (i, byte) in bytes.iter().enumerate() {
+            if *byte == b'\n' {
+                self.line_starts.push(self.position + i as u64 + 1);
+            }
+        }
+        self.position += bytes.len() as u64;
+
+        self.recent.extend_from_slice(bytes);
+        if self.recent.len() > 2 * Self::RECENT_LEN {
+            let drop = self.recent.len() - Self::RECENT_LEN;
+            self.recent.drain(..drop);
+        }
+        self.recent_start = self.position - self.recent.len() as u64;
+    }
+
+    /// The source from `offset`, if still known
+    fn snippet(&self, offset: u64) -> Option<String> {
+        let start = offset.checked_sub(self.recent_start)? as usize;
+        let bytes = self.recent.get(start..).filter(|bytes| !bytes.is_empty())?;
+        let bytes = &bytes[..bytes.len().min(4 * SNIPPET_LEN)];
+        Some(truncate_snippet(&String::from_utf8_lossy(bytes)))
+    }
+}
+
+/// A reader recording the [`SourceLines`] of what is consumed from it
+///
+/// Reading fails once `limit` bytes of the packet have been consumed.
+struct SourceTracker<R> {
+    inner: R,
+    lines: SourceLines,
+    limit: u64,
+    /// Whether reading failed for the limit
+    exceeded: bool,
+}
+
+impl<R> SourceTracker<R> {
+    /// Track `inner`, which follows `preceding` in the packet
+    fn new(inner: R, preceding: &[u8]) -> Self {
+        let mut lines = SourceLines::default();
+        lines.record(preceding);
+        Self {
+            inner,
+            lines,
+            limit: u64::MAX,
+            exceeded: false,
+        }
+    }
+}
+
+impl<R: BufRead> Read for SourceTracker<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = buf.len().min(available.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for SourceTracker<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let remaining = self.limit.saturating_sub(self.lines.position);
+        let buf = self.inner.fill_buf()?;
+        if buf.len() as u64 <= remaining {
+            return Ok(buf);
+        }
+        if remaining == 0 {
+            self.exceeded = true;
+            return Err(io::Error::other("packet size limit exceeded"));
+        }
+        Ok(&buf[..remaining as usize])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // The consumed bytes are still buffered by the inner reader
+        if let Ok(buf) = self.inner.fill_buf() {
+            self.lines.record(&buf[..amt.min(buf.len())]);
+        }
+        self.inner.consume(amt);
+    }
+}
+
+/// Namespace of the `x:xmpmeta` wrapper element
+const XMP_META_NS: &str = "adobe:ns:meta/";
+
+/// Characters allowed in XML 1.0 documents
+fn is_xml_char(c: char) -> bool {
+    matches!(c, '\t' | '\n' | '\r' | '\u{20}'..='\u{D7FF}' | '\u{E000}'..='\u{FFFD}' | '\u{10000}'..)
+}
+
+/// Escape the `&` characters that do not start a predefined entity or
+/// character reference
+fn escape_bare_ampersands(raw: &str) -> Cow<'_, str> {
+    let is_reference = |rest: &str| {
+        let Some(end) = rest.find(';') else {
+            return false;
+        };
+        let name = &rest[1..end];
+        match name.strip_prefix('#') {
+            Some(hex) if hex.starts_with('x') => {
+                hex.len() > 1 && hex[1..].chars().all(|c| c.is_ascii_hexdigit())
+            }
+            Some(digits) => !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()),
+            None => matches!(name, "lt" | "gt" | "amp" | "apos" | "quot"),
+        }
+    };
+    if raw
+        .match_indices('&')
+        .all(|(pos, _)| is_reference(&raw[pos..]))
+    {
+        return Cow::Borrowed(raw);
+    }
+    let mut escaped = String::with_capacity(raw.len() + 8);
+    for (pos, c) in raw.char_indices() {
+        if c == '&' && !is_reference(&raw[pos..]) {
+            escaped.push_str("&amp;");
+        } else {
+            escaped.push(c);
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+/// Options for [`XmpParser`] and [`XmpMeta::parse_with_options`]
+///
+/// Use the builder pattern to configure options. By default, parsing is
+/// strict and the `x:xmpmeta` wrapper is optional.
+///
+/// In strict mode, malformed XML (e.g., a bare `&` in text or a mismatched
+/// end tag) and invalid RDF fail the parse. Some common slips are accepted
+/// all the same, as they always were: undeclared prefixes resolve with the
+/// registered namespaces (elements and attributes with unknown prefixes are
+/// dropped), references to unknown entities are kept as text, and elements
+/// left open at the end of truncated content are closed.
+///
+/// In lenient mode, the parser also repairs or skips what it can, and
+/// reports each problem, including the slips above, as a [`ParseWarning`]:
+///
+/// - bare `&` characters are escaped, and characters not allowed in XML are
+///   dropped
+/// - unclosed elements are closed, and unmatched end tags are ignored
+/// - invalid RDF properties, array items and attributes are dropped
+///
+/// [`XmpMeta::parse_with_options`]: crate::core::metadata::XmpMeta::parse_with_options
+///
+/// # Example
+///
+/// ```rust
+/// use xmpkit::core::parser::ParseOptions;
+///
+/// // Parse camera-written packets as well as possible
+/// let options = ParseOptions::default().lenient();
+/// ```
+#[derive(Default, Clone, Debug)]
+pub struct ParseOptions {
+    /// Repair or skip malformed content instead of failing
+    pub lenient: bool,
+    /// Fail if there is no `x:xmpmeta` element (like `kXMP_RequireXMPMeta`)
+    pub require_xmp_meta: bool,
+    /// Limits for untrusted input
+    pub limits: ParseLimits,
+}
+
+impl ParseOptions {
+    /// Repair or skip malformed content, reporting it as warnings.
+    pub fn lenient(mut self) -> Self {
+        self.lenient = true;
+        self
+    }
+
+    /// Require the `x:xmpmeta` wrapper element.
+    ///
+    /// Only the RDF inside the first `x:xmpmeta` (or the older `x:xapmeta`)
+    /// element is then parsed.
+    pub fn require_xmp_meta(mut self) -> Self {
+        self.require_xmp_meta = true;
+        self
+    }
+
+    /// Set the limits for untrusted input.
+    pub fn limits(mut self, limits: ParseLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+}
+
+/// Limits on what [`XmpParser`] accepts, for untrusted input
+///
+/// Breaching a limit fails the parse with [`XmpError::LimitExceeded`], in
+/// lenient mode too. The defaults are far above what real packets need;
+/// servers parsing uploads may want lower ones.
+///
+/// # Example
+///
+/// ```rust
+/// use xmpkit::core::parser::{ParseLimits, ParseOptions};
+///
+/// let limits = ParseLimits::default()
+///     .max_packet_size(1024 * 1024)
+///     .max_nodes(10_000);
+/// let options = ParseOptions::default().limits(limits);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseLimits {
+    /// Maximum nesting depth of XML elements (default 256)
+    pub max_depth: usize,
+    /// Maximum number of XML elements and attributes (default 1,000,000)
+    pub max_nodes: usize,
+    /// Maximum length of a text or attribute value in bytes, after
+    /// unescaping (default 16 MB)
+    pub max_text_len: usize,
+    /// Maximum size of the packet in bytes, as UTF-8 (default 64 MB)
+    pub max_packet_size: u64,
+    /// Accept a DOCTYPE declaration, and the entity declarations it may
+    /// hold (default `false`)
+    ///
+    /// Declared entities are never expanded; references to them fail to
+    /// parse even when this is allowed.
+    pub allow_doctype: bool,
+}
+
+impl Default for ParseLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: 256,
+            max_nodes: 1_000_000,
+            max_text_len: 16 * 1024 * 1024,
+            max_packet_size: 64 * 1024 * 1024,
+            allow_doctype: false,
+        }
+    }
+}
+
+impl ParseLimits {
+    /// No limits, and DOCTYPE declarations allowed (for trusted input).
+    pub fn unlimited() -> Self {
+        Self {
+            max_depth: usize::MAX,
+            max_nodes: usize::MAX,
+            max_text_len: usize::MAX,
+            max_packet_size: u64::MAX,
+            allow_doctype: true,
+        }
+    }
+
+    /// Set the maximum nesting depth of XML elements.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Set the maximum number of XML elements and attributes.
+    pub fn max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = max_nodes;
+        self
+    }
+
+    /// Set the maximum length of a text or attribute value in bytes.
+    pub fn max_text_len(mut self, max_text_len: usize) -> Self {
+        self.max_text_len = max_text_len;
+        self
+    }
+
+    /// Set the maximum size of the packet in bytes.
+    pub fn max_packet_size(mut self, max_packet_size: u64) -> Self {
+        self.max_packet_size = max_packet_size;
+        self
+    }
+
+    /// Accept DOCTYPE declarations.
+    pub fn allow_doctype(mut self) -> Self {
+        self.allow_doctype = true;
+        self
+    }
+}
+
+/// The kind of a [`ParseWarning`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseWarningKind {
+    /// A `&` not starting a reference was escaped
+    BareAmpersand,
+    /// A character not allowed in XML was dropped
+    InvalidCharacter,
+    /// Bytes that are not valid UTF-8 were replaced with U+FFFD
+    InvalidUtf8,
+    /// An undeclared prefix was resolved with the registered namespaces
+    UndeclaredPrefix,
+    /// An element or attribute with an unknown prefix was dropped
+    UnknownPrefix,
+    /// A malformed attribute was dropped
+    MalformedAttribute,
+    /// An element left open was closed
+    UnclosedElement,
+    /// An end tag without a matching start tag was ignored
+    UnmatchedEndTag,
+    /// Invalid RDF was dropped
+    InvalidRdf,
+}
+
+/// A problem worked around while parsing in lenient mode
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseWarning {
+    /// The kind of problem
+    pub kind: ParseWarningKind,
+    /// What was found and what was done about it
+    pub message: String,
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
 }
 
 /// Parser for XMP Packets
 pub struct XmpParser {
     namespaces: NamespaceMap,
+    about: Option<String>,
+    options: ParseOptions,
+    warnings: Vec<ParseWarning>,
+    /// Offsets at which the lines of the last packet start, for locating
+    /// RDF errors
+    line_starts: Vec<u64>,
 }
 
 impl XmpParser {
     /// Create a new XMP parser
     pub fn new() -> Self {
+        Self::with_options(ParseOptions::default())
+    }
+
+    /// Create a new XMP parser with the given options
+    pub fn with_options(options: ParseOptions) -> Self {
         Self {
             namespaces: NamespaceMap::new(),
+            about: None,
+            options,
+            warnings: Vec::new(),
+            line_starts: Vec::new(),
         }
     }
 
@@ -33,12 +654,38 @@ impl XmpParser {
     ///
     /// This function extracts the XMP Packet from the `<?xpacket>` wrapper
     /// and parses the RDF/XML content.
+    ///
+    /// Errors are located in `xml` as a whole (see [`ParseLocation`]).
     pub fn parse_packet(&mut self, xml: &str) -> XmpResult<StructureNode> {
         // Extract XMP Packet content (remove <?xpacket> wrapper)
-        let packet_content = self.extract_packet_content(xml)?;
+        let (start, packet_content) = self.extract_packet_content(xml)?;
 
         // Parse RDF/XML
-        self.parse_rdf(&packet_content)
+        self.parse_rdf(packet_content, &xml[..start])
+    }
+
+    /// Parse an XMP Packet from bytes
+    ///
+    /// The bytes may be encoded in UTF-8, UTF-16 or UTF-32; the encoding is
+    /// detected from the byte order mark or the first characters of the
+    /// packet. UTF-8 content is read in place.
+    pub fn parse_bytes(&mut self, bytes: &[u8]) -> XmpResult<StructureNode> {
+        match PacketEncoding::detect(bytes) {
+            (PacketEncoding::Utf8, bom_len) => {
+                self.parse_buffered(SourceTracker::new(&bytes[bom_len..], &bytes[..bom_len]))
+            }
+            _ => self.parse_reader(bytes),
+        }
+    }
+
+    /// Parse an XMP Packet from a reader
+    ///
+    /// Like [`parse_bytes`](Self::parse_bytes), but the packet is decoded and
+    /// parsed as it is read. Errors are located in the decoded text, without
+    /// the byte order mark.
+    pub fn parse_reader<R: Read>(&mut self, reader: R) -> XmpResult<StructureNode> {
+        let reader = PacketReader::new(reader)?;
+        self.parse_buffered(SourceTracker::new(BufReader::new(reader), &[]))
     }
 
     /// Return the namespace map accumulated while parsing.
@@ -46,8 +693,49 @@ impl XmpParser {
         self.namespaces.clone()
     }
 
-    /// Extract the XMP Packet content from the `<?xpacket>` wrapper
-    fn extract_packet_content(&self, xml: &str) -> XmpResult<String> {
+    /// Return the warnings of the last parse (always empty in strict mode).
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
+    }
+
+    /// Take the warnings of the last parse, leaving none.
+    pub fn take_warnings(&mut self) -> Vec<ParseWarning> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// Record a warning in lenient mode, unless the same one was already
+    /// recorded
+    fn warn(&mut self, kind: ParseWarningKind, message: String) {
+        if !self.options.lenient {
+            return;
+        }
+        let warning = ParseWarning { kind, message };
+        if !self.warnings.contains(&warning) {
+            self.warnings.push(warning);
+        }
+    }
+
+    /// Fail with `error`, or record it as a warning in lenient mode
+    fn recover(&mut self, kind: ParseWarningKind, error: XmpError) -> XmpResult<()> {
+        if !self.options.lenient || matches!(error, XmpError::LimitExceeded(_)) {
+            return Err(error);
+        }
+        let message = match error {
+            XmpError::ParseError(info) => info.message,
+            error => error.to_string(),
+        };
+        self.warn(kind, format!("{}; skipped", message));
+        Ok(())
+    }
+
+    /// Return the `rdf:about` URI of the last parsed packet, if not empty.
+    pub fn about_uri(&self) -> Option<&str> {
+        self.about.as_deref().filter(|about| !about.is_empty())
+    }
+
+    /// Extract the XMP Packet content from the `<?xpacket>` wrapper, with
+    /// its offset in `xml`
+    fn extract_packet_content<'a>(&self, xml: &'a str) -> XmpResult<(usize, &'a str)> {
         // Look for <?xpacket start
         let Some(start_pos) = xml.find("<?xpacket") else {
             return self.validate_and_return_xml(xml);
@@ -62,532 +750,776 @@ impl XmpParser {
             return self.validate_and_return_xml(xml);
         };
 
-        let content = xml[pi_end..pi_end + close_pos].trim().to_string();
-        Ok(content)
+        let content = &xml[pi_end..pi_end + close_pos];
+        let trimmed = content.trim_start();
+        Ok((pi_end + content.len() - trimmed.len(), trimmed.trim_end()))
     }
 
     /// Validate XML content and return it if valid
-    fn validate_and_return_xml(&self, xml: &str) -> XmpResult<String> {
-        let trimmed = xml.trim();
+    fn validate_and_return_xml<'a>(&self, xml: &'a str) -> XmpResult<(usize, &'a str)> {
+        let trimmed = xml.trim_start();
+        let start = xml.len() - trimmed.len();
+        let trimmed = trimmed.trim_end();
         if trimmed.is_empty() || (!trimmed.starts_with('<') && !trimmed.starts_with("<?xml")) {
-            return Err(XmpError::ParseError("Invalid XML content".to_string()));
+            return Err(XmpError::ParseError("Invalid XML content".into()));
         }
-        Ok(trimmed.to_string())
+        Ok((start, trimmed))
     }
 
     /// Parse RDF/XML content into a StructureNode
-    fn parse_rdf(&mut self, xml: &str) -> XmpResult<StructureNode> {
-        let mut reader = Reader::from_str(xml);
-        reader.config_mut().trim_text(false);
+    ///
+    /// `preceding` is the text before the content in the packet, counted in
+    /// error locations.
+    fn parse_rdf(&mut self, xml: &str, preceding: &str) -> XmpResult<StructureNode> {
+        let elements =
+            self.read_elements(SourceTracker::new(xml.as_bytes(), preceding.as_bytes()))?;
+        self.parse_elements(&elements)
+    }
+
+    /// Parse UTF-8 packet content, which may include the `<?xpacket>` wrapper
+    fn parse_buffered<R: BufRead>(&mut self, source: SourceTracker<R>) -> XmpResult<StructureNode> {
+        let elements = self.read_elements(source)?;
+        if elements.is_empty() {
+            return Err(XmpError::ParseError("Invalid XML content".into()));
+        }
+        self.parse_elements(&elements)
+    }
+
+    /// Interpret an element tree as RDF
+    fn parse_elements(&mut self, elements: &[XmlElement]) -> XmpResult<StructureNode> {
+        self.parse_node_elements(elements)
+            .map_err(|error| self.locate_rdf_error(error, elements))
+    }
+
+    /// Fill in the line, column and element path of an RDF error
+    fn locate_rdf_error(&self, error: XmpError, elements: &[XmlElement]) -> XmpError {
+        let XmpError::ParseError(mut info) = error else {
+            return error;
+        };
+        if let Some(location) = info.location.as_mut().filter(|location| location.line == 0) {
+            (location.line, location.column) = line_column(&self.line_starts, location.offset);
+            location.path = element_path(elements, location.offset).unwrap_or_default();
+        }
+        XmpError::ParseError(info)
+    }
+
+    /// Interpret an element tree as RDF, leaving errors partly located
+    fn parse_node_elements(&mut self, elements: &[XmlElement]) -> XmpResult<StructureNode> {
+        self.about = None;
+
+        let mut node_elements = Vec::new();
+        if self.options.require_xmp_meta {
+            let xmp_meta = elements
+                .iter()
+                .find_map(XmlElement::find_xmp_meta)
+                .ok_or_else(|| XmpError::ParseError("x:xmpmeta element not found".into()))?;
+            Self::collect_node_elements(xmp_meta, &mut node_elements);
+        } else {
+            for element in elements {
+                Self::collect_node_elements(element, &mut node_elements);
+            }
+        }
 
-        let mut buf = Vec::new();
         let mut root = StructureNode::new();
-        let mut stack: Vec<StackFrame> = Vec::new();
-        let mut current_qualifiers: Vec<Qualifier> = Vec::new();
-        let mut description_depth: usize = 0;
+        for element in node_elements {
+            self.parse_top_node_element(element, &mut root)?;
+        }
+        Ok(root)
+    }
 
+    /// Read XML into a tree of elements
+    ///
+    /// Namespace prefixes are resolved with the declarations in scope.
+    /// Declarations are also recorded in the parser's namespace map.
+    fn read_elements<R: BufRead>(
+        &mut self,
+        mut source: SourceTracker<R>,
+    ) -> XmpResult<Vec<XmlElement>> {
+        self.warnings.clear();
+        let limits = self.options.limits.clone();
+        source.limit = limits.max_packet_size;
+        let mut reader = Reader::from_reader(source);
+        let lenient = self.options.lenient;
+        reader.config_mut().trim_text(false);
+        reader.config_mut().allow_dangling_amp = lenient;
+        reader.config_mut().check_end_names = !lenient;
+        reader.config_mut().allow_unmatched_ends = lenient;
+
+        let mut buf = Vec::new();
+        let mut roots = Vec::new();
+        // Open elements, with their raw text and the size of the scope before them
+        let mut stack: Vec<(XmlElement, String, usize)> = Vec::new();
+        // Namespace declarations in scope, as (prefix, uri)
+        let mut scope: Vec<(String, String)> = Vec::new();
+        // Elements and attributes read
+        let mut nodes = 0;
+
+        // Offset of the source in the packet
+        let base = reader.get_ref().lines.position;
         loop {
+            let offset = base + reader.buffer_position();
             match reader.read_event_into(&mut buf) {
                 Ok(Event::Start(e)) => {
-                    self.handle_start_element(
-                        &e,
-                        &mut root,
-                        &mut stack,
-                        &mut current_qualifiers,
-                        &mut description_depth,
-                    )?;
+                    let lines = &reader.get_ref().lines;
+                    self.open_element(&e, offset, &mut stack, &mut scope, &mut nodes, lines)?;
+                }
+                Ok(Event::Empty(e)) => {
+                    let lines = &reader.get_ref().lines;
+                    self.open_element(&e, offset, &mut stack, &mut scope, &mut nodes, lines)?;
+                    self.close_element(&mut stack, &mut scope, &mut roots)
+                        .map_err(|error| locate_in_element(error, lines, &stack))?;
+                }
+                Ok(Event::End(e)) => {
+                    let name = e.name();
+                    let Some(depth) = stack
+                        .iter()
+                        .rposition(|(element, _, _)| element.name.as_bytes() == name.as_ref())
+                    else {
+                        self.warn(
+                            ParseWarningKind::UnmatchedEndTag,
+                            format!(
+                                "End tag </{}> has no start tag; ignored",
+                                String::from_utf8_lossy(name.as_ref())
+                            ),
+                        );
+                        continue;
+                    };
+                    let lines = &reader.get_ref().lines;
+                    // Only in lenient mode: the reader checks end tags otherwise
+                    while stack.len() > depth + 1 {
+                        self.close_unclosed(&mut stack, &mut scope, &mut roots)
+                            .map_err(|error| locate_in_element(error, lines, &stack))?;
+                    }
+                    self.close_element(&mut stack, &mut scope, &mut roots)
+                        .map_err(|error| locate_in_element(error, lines, &stack))?;
                 }
                 Ok(Event::Text(e)) => {
-                    if description_depth > 0 {
-                        if let Some(frame) = stack.last_mut() {
-                            if let Node::Simple(_) = frame.node {
-                                let raw_text = String::from_utf8_lossy(e.as_ref());
-                                frame.accumulated_text.push_str(&raw_text);
-                            }
-                        }
+                    let text = self.decode_utf8(&e).map_err(|error| {
+                        let lines = &reader.get_ref().lines;
+                        locate_xml_error(error, lines, offset, stack_path(&stack), None)
+                    })?;
+                    if let Some((_, raw_text, _)) = stack.last_mut() {
+                        raw_text.push_str(&text);
                     }
                 }
-                Ok(Event::GeneralRef(e)) => {
-                    if description_depth > 0 {
-                        if let Some(frame) = stack.last_mut() {
-                            if let Node::Simple(_) = frame.node {
-                                let name = String::from_utf8_lossy(e.as_ref());
-                                frame.accumulated_text.push('&');
-                                frame.accumulated_text.push_str(&name);
-                                frame.accumulated_text.push(';');
-                            }
-                        }
+                Ok(Event::CData(e)) => {
+                    let text = self.decode_utf8(&e).map_err(|error| {
+                        let lines = &reader.get_ref().lines;
+                        locate_xml_error(error, lines, offset, stack_path(&stack), None)
+                    })?;
+                    if let Some((_, raw_text, _)) = stack.last_mut() {
+                        raw_text.push_str(&escape(text.as_ref()));
                     }
                 }
-                Ok(Event::End(e)) => {
-                    self.handle_end_element(&e, &mut root, &mut stack, &mut description_depth)?;
+                Ok(Event::GeneralRef(e)) => {
+                    let name = self.decode_utf8(&e).map_err(|error| {
+                        let lines = &reader.get_ref().lines;
+                        locate_xml_error(error, lines, offset, stack_path(&stack), None)
+                    })?;
+                    if let Some((_, raw_text, _)) = stack.last_mut() {
+                        raw_text.push('&');
+                        raw_text.push_str(&name);
+                        raw_text.push(';');
+                    }
                 }
-                Ok(Event::Empty(e)) => {
-                    self.handle_empty_element(
-                        &e,
-                        &mut root,
-                        &mut stack,
-                        &mut current_qualifiers,
-                        description_depth,
-                    )?;
+                Ok(Event::DocType(_)) if !limits.allow_doctype => {
+                    return Err(XmpError::LimitExceeded(
+                        "DOCTYPE declarations are not allowed".to_string(),
+                    ));
                 }
                 Ok(Event::Eof) => break,
+                Err(_) if reader.get_ref().exceeded => {
+                    return Err(XmpError::LimitExceeded(format!(
+                        "packet is larger than {} bytes",
+                        limits.max_packet_size
+                    )));
+                }
                 Err(e) => {
-                    return Err(XmpError::ParseError(format!("XML parsing error: {}", e)));
+                    let offset = base + reader.error_position();
+                    return Err(locate_xml_error(
+                        xml_error(e),
+                        &reader.get_ref().lines,
+                        offset,
+                        stack_path(&stack),
+                        None,
+                    ));
                 }
                 _ => {}
             }
             buf.clear();
         }
 
-        Ok(root)
+        let lines = &reader.get_ref().lines;
+        while !stack.is_empty() {
+            self.close_unclosed(&mut stack, &mut scope, &mut roots)
+                .map_err(|error| locate_in_element(error, lines, &stack))?;
+        }
+        self.line_starts = reader.into_inner().lines.line_starts;
+        Ok(roots)
     }
 
-    /// Helper to insert a completed node into its parent or root
-    fn insert_node_into_parent(
-        root: &mut StructureNode,
-        stack: &mut [StackFrame],
-        key: String,
-        node: Node,
-    ) {
-        if let Some(frame) = stack.last_mut() {
-            let parent = &mut frame.node;
-            match parent {
-                Node::Array(arr) => {
-                    arr.append(node);
-                }
-                Node::Structure(structure) => {
-                    structure.set_field(key, node);
-                }
-                Node::Simple(simple) => {
-                    // Convert parent from Simple to Structure
-                    let mut structure = StructureNode::new();
-                    structure.qualifiers = std::mem::take(&mut simple.qualifiers);
-                    *parent = Node::Structure(structure);
-
-                    if let Node::Structure(structure) = parent {
-                        structure.set_field(key, node);
-                    }
+    /// Read a start tag and open its element, counting it and its
+    /// attributes in `nodes`
+    fn open_element(
+        &mut self,
+        e: &BytesStart<'_>,
+        offset: u64,
+        stack: &mut Vec<(XmlElement, String, usize)>,
+        scope: &mut Vec<(String, String)>,
+        nodes: &mut usize,
+        lines: &SourceLines,
+    ) -> XmpResult<()> {
+        let limits = &self.options.limits;
+        if stack.len() >= limits.max_depth {
+            return Err(XmpError::LimitExceeded(format!(
+                "elements are nested deeper than {}",
+                limits.max_depth
+            )));
+        }
+        let mark = scope.len();
+        match self.read_element(e, offset, scope) {
+            Ok(element) => {
+                *nodes += 1 + element.attrs.len();
+                if *nodes > self.options.limits.max_nodes {
+                    return Err(XmpError::LimitExceeded(format!(
+                        "more than {} elements and attributes",
+                        self.options.limits.max_nodes
+                    )));
                 }
+                stack.push((element, String::new(), mark));
+                Ok(())
+            }
+            Err(error) => {
+                let mut path = stack_path(stack);
+                path.push(String::from_utf8_lossy(e.name().as_ref()).into_owned());
+                let tag = format!("<{}>", String::from_utf8_lossy(e));
+                Err(locate_xml_error(
+                    error,
+                    lines,
+                    offset,
+                    path,
+                    Some(truncate_snippet(&tag)),
+                ))
             }
-        } else {
-            root.set_field(key, node);
         }
     }
 
-    /// Make an array node at `frame` with element `name`.
-    /// This will replace the frame node.
-    fn make_array_node(name: &str, frame: &mut StackFrame) {
-        use crate::core::node::{ArrayNode, ArrayType};
-
-        let parent = &mut frame.node;
-        let array_type = if name.contains("Seq") {
-            ArrayType::Ordered
-        } else if name.contains("Bag") {
-            ArrayType::Unordered
-        } else {
-            ArrayType::Alternative
+    /// Close the innermost open element and add it to its parent, or to
+    /// the roots
+    fn close_element(
+        &mut self,
+        stack: &mut Vec<(XmlElement, String, usize)>,
+        scope: &mut Vec<(String, String)>,
+        roots: &mut Vec<XmlElement>,
+    ) -> XmpResult<()> {
+        // Decode first, so the element is still open if this fails
+        let Some((_, raw_text, _)) = stack.last() else {
+            return Ok(());
         };
-        let mut array_node = ArrayNode::new(array_type);
-        // Preserve any qualifiers from the parent (e.g. from attributes)
-        match parent {
-            Node::Simple(sn) => {
-                array_node.qualifiers = std::mem::take(&mut sn.qualifiers);
-            }
-            Node::Structure(sn) => {
-                array_node.qualifiers = std::mem::take(&mut sn.qualifiers);
-            }
-            Node::Array(sn) => {
-                array_node.qualifiers = std::mem::take(&mut sn.qualifiers);
+        let text = self.decode_text(raw_text)?;
+        if let Some((mut element, _, mark)) = stack.pop() {
+            element.text = text;
+            scope.truncate(mark);
+            match stack.last_mut() {
+                Some((parent, _, _)) => parent.children.push(element),
+                None => roots.push(element),
             }
         }
-        *parent = Node::Array(array_node);
+        Ok(())
     }
 
-    /// Handle Start element event during RDF parsing
-    fn handle_start_element(
+    /// Close an element that has no end tag
+    fn close_unclosed(
         &mut self,
-        e: &quick_xml::events::BytesStart<'_>,
-        root: &mut StructureNode,
-        stack: &mut Vec<StackFrame>,
-        current_qualifiers: &mut Vec<Qualifier>,
-        description_depth: &mut usize,
+        stack: &mut Vec<(XmlElement, String, usize)>,
+        scope: &mut Vec<(String, String)>,
+        roots: &mut Vec<XmlElement>,
     ) -> XmpResult<()> {
-        let name = String::from_utf8_lossy(e.name().as_ref()).to_string();
-        let attrs = Self::collect_attributes(e);
-        self.process_attributes(&attrs, current_qualifiers);
-
-        // Handle RDF Description
-        if self.is_description_element(&name) {
-            *description_depth += 1;
-            if *description_depth > 1 {
-                if let Some(frame) = stack.last_mut() {
-                    self.populate_node_from_attributes(
-                        &attrs,
-                        &mut frame.node,
-                        current_qualifiers,
-                    )?;
-                }
-            } else {
-                self.handle_description_attributes(&attrs, root, current_qualifiers)?;
-            }
+        // Strict mode only gets here at the end of truncated content, which
+        // is accepted as it always was
+        if let Some((element, _, _)) = stack.last().filter(|_| self.options.lenient) {
+            let error = xml_error(format!("element {} is not closed", element.name));
+            self.recover(ParseWarningKind::UnclosedElement, error)?;
         }
-        // Handle RDF containers (Seq, Bag, Alt)
-        else if *description_depth > 0 && self.is_array_container(&name) {
-            if let Some(frame) = stack.last_mut() {
-                Self::make_array_node(&name, frame);
+        self.close_element(stack, scope, roots)
+    }
+
+    /// Decode text, a name or an attribute value as UTF-8
+    ///
+    /// Invalid bytes are an error in strict mode. In lenient mode, they are
+    /// replaced with U+FFFD.
+    fn decode_utf8<'a>(&mut self, bytes: &'a [u8]) -> XmpResult<Cow<'a, str>> {
+        match std::str::from_utf8(bytes) {
+            Ok(text) => Ok(Cow::Borrowed(text)),
+            Err(error) if !self.options.lenient => Err(xml_error(format!(
+                "invalid UTF-8 byte 0x{:02X}",
+                bytes[error.valid_up_to()]
+            ))),
+            Err(_) => {
+                self.warn(
+                    ParseWarningKind::InvalidUtf8,
+                    "Invalid UTF-8 replaced with U+FFFD".to_string(),
+                );
+                Ok(String::from_utf8_lossy(bytes))
             }
         }
-        // Handle li (list item) - add to current array
-        else if *description_depth > 0 && self.is_li_element(&name) {
-            let mut node = Node::simple("");
-            if let Node::Simple(sn) = &mut node {
-                sn.qualifiers.extend(current_qualifiers.clone());
-            }
-            self.populate_node_from_attributes(&attrs, &mut node, current_qualifiers)?;
-            stack.push(StackFrame {
-                key: "".to_string(),
-                node,
-                accumulated_text: String::new(),
-            });
-        } else if *description_depth > 0 && !self.is_rdf_element(&name) {
-            let colon_pos = name.find(':');
-            let key = if let Some(pos) = colon_pos {
-                let prefix = &name[..pos];
-                let prop_name = &name[pos + 1..];
-                if let Some(ns_uri) = self.namespaces.get_uri(prefix) {
-                    format!("{}:{}", ns_uri, prop_name)
-                } else {
-                    name.clone()
+    }
+
+    /// Unescape character data or an attribute value
+    ///
+    /// Bare `&` characters and references to unknown entities are kept as
+    /// text. In lenient mode, characters not allowed in XML are dropped.
+    fn decode_text(&mut self, raw: &str) -> XmpResult<String> {
+        let raw = escape_bare_ampersands(raw);
+        if let Cow::Owned(_) = raw {
+            self.warn(
+                ParseWarningKind::BareAmpersand,
+                "Bare '&' escaped".to_string(),
+            );
+        }
+        let text = unescape(&raw).map_err(xml_error)?;
+        if text.len() > self.options.limits.max_text_len {
+            return Err(XmpError::LimitExceeded(format!(
+                "value is longer than {} bytes",
+                self.options.limits.max_text_len
+            )));
+        }
+
+        let invalid = text.chars().find(|c| !is_xml_char(*c));
+        let Some(invalid) = invalid.filter(|_| self.options.lenient) else {
+            return Ok(text.into_owned());
+        };
+        let error = xml_error(format!("invalid character U+{:04X}", invalid as u32));
+        self.recover(ParseWarningKind::InvalidCharacter, error)?;
+        Ok(text.chars().filter(|c| is_xml_char(*c)).collect())
+    }
+
+    /// Read the name and attributes of an element, adding its namespace
+    /// declarations to `scope`
+    fn read_element(
+        &mut self,
+        e: &BytesStart<'_>,
+        offset: u64,
+        scope: &mut Vec<(String, String)>,
+    ) -> XmpResult<XmlElement> {
+        let name = self.decode_utf8(e.name().as_ref())?.into_owned();
+        let mut attrs = Vec::new();
+        for attr in e.attributes() {
+            let attr = match attr {
+                Ok(attr) => attr,
+                Err(error) => {
+                    let error = xml_error(format!("malformed attribute in {}: {}", name, error));
+                    self.recover(ParseWarningKind::MalformedAttribute, error)?;
+                    continue;
                 }
-            } else {
-                name.clone()
             };
-
-            let has_parse_type_resource = attrs
-                .iter()
-                .any(|(k, v)| k == "rdf:parseType" && v == "Resource");
-            let mut node = if has_parse_type_resource {
-                Node::structure()
+            let attr_name = self.decode_utf8(attr.key.as_ref())?.into_owned();
+            let value = self.decode_utf8(&attr.value)?;
+            let value = self.decode_text(&value)?;
+            if attr_name == "xmlns" {
+                scope.push((String::new(), value));
+            } else if let Some(prefix) = attr_name.strip_prefix("xmlns:") {
+                let _ = self.namespaces.register(&value, prefix);
+                scope.push((prefix.to_string(), value));
             } else {
-                Node::simple("")
-            };
-            match &mut node {
-                Node::Simple(sn) => sn.qualifiers.extend(current_qualifiers.clone()),
-                Node::Structure(sn) => sn.qualifiers.extend(current_qualifiers.clone()),
-                Node::Array(sn) => sn.qualifiers.extend(current_qualifiers.clone()),
+                attrs.push((attr_name, value));
             }
-            self.populate_node_from_attributes(&attrs, &mut node, current_qualifiers)?;
-            stack.push(StackFrame {
-                key,
-                node,
-                accumulated_text: String::new(),
+        }
+
+        let (ns_uri, local) = self.resolve_name(&name, scope, true);
+        let mut resolved = Vec::with_capacity(attrs.len());
+        for (name, value) in attrs {
+            let (ns_uri, local) = self.resolve_name(&name, scope, false);
+            resolved.push(XmlAttribute {
+                name,
+                ns_uri,
+                local,
+                value,
             });
         }
-        Ok(())
+
+        Ok(XmlElement {
+            name,
+            ns_uri,
+            local,
+            attrs: resolved,
+            children: Vec::new(),
+            text: String::new(),
+            offset,
+        })
     }
 
-    /// Handle End element event during RDF parsing
-    fn handle_end_element(
-        &self,
-        e: &quick_xml::events::BytesEnd<'_>,
-        root: &mut StructureNode,
-        stack: &mut Vec<StackFrame>,
-        description_depth: &mut usize,
-    ) -> XmpResult<()> {
-        let name = String::from_utf8_lossy(e.name().as_ref()).to_string();
-
-        if self.is_description_element(&name) {
-            *description_depth = description_depth.saturating_sub(1);
-        } else if *description_depth > 0
-            && !self.is_array_container(&name)
-            && !self.is_rdf_element(&name)
-        {
-            if let Some(frame) = stack.pop() {
-                let StackFrame {
-                    key,
-                    mut node,
-                    accumulated_text,
-                } = frame;
-                if let Node::Simple(ref mut simple) = node {
-                    if !accumulated_text.is_empty() {
-                        let text = match unescape(&accumulated_text) {
-                            Ok(unescaped) => unescaped.to_string(),
-                            Err(_) => accumulated_text.to_string(),
-                        };
-                        simple.value = text.trim().to_string();
-                    }
-                }
-                Self::insert_node_into_parent(root, stack, key, node);
+    /// Split a qualified name into its namespace URI and local name
+    ///
+    /// Unprefixed attributes have no namespace, and neither have unprefixed
+    /// elements without a default namespace. An undeclared prefix resolves
+    /// with the registered namespaces, or to an empty URI (so the element
+    /// or attribute is dropped) if it is unknown.
+    fn resolve_name(
+        &mut self,
+        name: &str,
+        scope: &[(String, String)],
+        is_element: bool,
+    ) -> (String, String) {
+        let (prefix, local) = match name.split_once(':') {
+            Some((prefix, local)) => (prefix, local),
+            None if is_element => ("", name),
+            None => return (String::new(), name.to_string()),
+        };
+        let declared = |prefix: &str| {
+            scope
+                .iter()
+                .rev()
+                .find(|(p, _)| p == prefix)
+                .map(|(_, uri)| uri.clone())
+        };
+        let ns_uri = declared(prefix).or_else(|| match prefix {
+            "xml" => Some(ns::XML.to_string()),
+            // Some writers declare C260 but use TC260 (and vice versa)
+            "TC260" if !is_element => declared("C260"),
+            "C260" if !is_element => declared("TC260"),
+            _ => None,
+        });
+        if let Some(ns_uri) = ns_uri {
+            return (ns_uri, local.to_string());
+        }
+        if prefix.is_empty() {
+            return (String::new(), local.to_string());
+        }
+
+        let registered = self
+            .namespaces
+            .get_uri(prefix)
+            .map(String::from)
+            .or_else(|| get_global_namespace_uri(prefix));
+        match registered {
+            Some(ns_uri) => {
+                self.warn(
+                    ParseWarningKind::UndeclaredPrefix,
+                    format!(
+                        "Namespace prefix '{}' is not declared; using {}",
+                        prefix, ns_uri
+                    ),
+                );
+                (ns_uri, local.to_string())
+            }
+            None => {
+                self.warn(
+                    ParseWarningKind::UnknownPrefix,
+                    format!("Namespace prefix '{}' is unknown; {} dropped", prefix, name),
+                );
+                (String::new(), local.to_string())
+            }
+        }
+    }
+
+    /// Collect the top-level node elements: the children of `rdf:RDF`, or
+    /// the outermost `rdf:Description` elements if there is no `rdf:RDF`
+    fn collect_node_elements<'a>(element: &'a XmlElement, out: &mut Vec<&'a XmlElement>) {
+        if element.is_rdf("RDF") {
+            out.extend(element.children.iter().filter(|child| !child.is_dropped()));
+        } else if element.is_rdf("Description") {
+            out.push(element);
+        } else {
+            for child in &element.children {
+                Self::collect_node_elements(child, out);
             }
         }
-        Ok(())
     }
 
-    /// Handle Empty element event during RDF parsing
-    fn handle_empty_element(
+    /// Parse a top-level `rdf:Description` into the root
+    fn parse_top_node_element(
         &mut self,
-        e: &quick_xml::events::BytesStart<'_>,
+        element: &XmlElement,
         root: &mut StructureNode,
-        stack: &mut [StackFrame],
-        current_qualifiers: &mut Vec<Qualifier>,
-        description_depth: usize,
     ) -> XmpResult<()> {
-        let name = String::from_utf8_lossy(e.name().as_ref()).to_string();
-        let attrs = Self::collect_attributes_empty(e);
-        self.process_attributes(&attrs, current_qualifiers);
-
-        // Handle RDF Description
-        if self.is_description_element(&name) {
-            if description_depth > 0 {
-                if let Some(frame) = stack.last_mut() {
-                    self.populate_node_from_attributes(
-                        &attrs,
-                        &mut frame.node,
-                        current_qualifiers,
-                    )?;
-                }
-            } else {
-                self.handle_description_attributes(&attrs, root, current_qualifiers)?;
+        if !element.is_rdf("Description") {
+            let error = rdf_error(element, "top-level elements must be rdf:Description");
+            return self.recover(ParseWarningKind::InvalidRdf, error);
+        }
+
+        // All descriptions must be about the same resource
+        let about = element
+            .attrs
+            .iter()
+            .find(|attr| attr.ns_uri == ns::RDF && attr.local == "about")
+            .map(|attr| attr.value.as_str());
+        match (self.about.as_deref(), about) {
+            (Some(previous), Some(about))
+                if !previous.is_empty() && !about.is_empty() && previous != about =>
+            {
+                let error = rdf_error(
+                    element,
+                    &format!("mismatched rdf:about values '{}' and '{}'", previous, about),
+                );
+                self.recover(ParseWarningKind::InvalidRdf, error)?;
             }
-        } else if description_depth > 0 && !self.is_rdf_element(&name) {
-            if self.is_array_container(&name) {
-                if let Some(frame) = stack.last_mut() {
-                    Self::make_array_node(&name, frame);
-                }
-            } else {
-                let colon_pos = name.find(':');
-                let key = if let Some(pos) = colon_pos {
-                    let prefix = &name[..pos];
-                    let prop_name = &name[pos + 1..];
-                    if let Some(ns_uri) = self.namespaces.get_uri(prefix) {
-                        format!("{}:{}", ns_uri, prop_name)
-                    } else {
-                        name.clone()
-                    }
-                } else {
-                    name.clone()
-                };
+            (None | Some(""), Some(about)) => self.about = Some(about.to_string()),
+            _ => {}
+        }
 
-                let mut node = Node::simple("");
-                if let Node::Simple(sn) = &mut node {
-                    sn.qualifiers.extend(current_qualifiers.clone());
-                }
-                self.populate_node_from_attributes(&attrs, &mut node, current_qualifiers)?;
+        self.parse_node_element(element, root)
+    }
 
-                if name == "li" || name.ends_with(":li") {
-                    Self::insert_node_into_parent(root, stack, "".to_string(), node);
-                } else {
-                    Self::insert_node_into_parent(root, stack, key, node);
+    /// Parse the property attributes and property elements of a node
+    /// element (`rdf:Description`) into `structure`
+    fn parse_node_element(
+        &mut self,
+        element: &XmlElement,
+        structure: &mut StructureNode,
+    ) -> XmpResult<()> {
+        let lang = element.lang();
+        let mut identifiers = 0;
+        for attr in &element.attrs {
+            if attr.is_lang() {
+                continue;
+            }
+            if attr.ns_uri == ns::RDF {
+                match attr.local.as_str() {
+                    "about" | "ID" | "nodeID" => {
+                        identifiers += 1;
+                        continue;
+                    }
+                    "type" => {}
+                    // Written by earlier versions of this crate
+                    "parseType" if attr.value == "Resource" => continue,
+                    _ => {
+                        let error = rdf_error(element, &format!("invalid attribute {}", attr.name));
+                        self.recover(ParseWarningKind::InvalidRdf, error)?;
+                        continue;
+                    }
                 }
             }
+            // Attributes without a namespace are skipped
+            if attr.ns_uri.is_empty() {
+                continue;
+            }
+            let mut node = SimpleNode::new(attr.value.clone());
+            if let Some(lang) = lang {
+                node.add_qualifier(Qualifier::new(ns::XML, "lang", lang));
+            }
+            structure.set_field(attr.key(), Node::Simple(node));
         }
-        Ok(())
+        if identifiers > 1 {
+            let error = rdf_error(
+                element,
+                "rdf:about, rdf:ID and rdf:nodeID are mutually exclusive",
+            );
+            self.recover(ParseWarningKind::InvalidRdf, error)?;
+        }
+        if element.has_text() {
+            let error = rdf_error(element, "unexpected text content");
+            self.recover(ParseWarningKind::InvalidRdf, error)?;
+        }
+        self.parse_fields(element, structure)
     }
 
-    /// Process collected attributes: extract namespaces and qualifiers
-    fn process_attributes(
+    /// Parse the property elements inside `element` into fields of `structure`
+    fn parse_fields(
         &mut self,
-        attrs: &[(String, String)],
-        current_qualifiers: &mut Vec<Qualifier>,
-    ) {
-        // Extract namespace declarations from attributes (on any element)
-        for (attr_name, attr_value) in attrs {
-            if attr_name == "xmlns" {
-                // Default namespace - For XMP, we typically don't use default namespace
+        element: &XmlElement,
+        structure: &mut StructureNode,
+    ) -> XmpResult<()> {
+        for child in &element.children {
+            if child.is_dropped() {
                 continue;
             }
-            if let Some(prefix) = attr_name.strip_prefix("xmlns:") {
-                // Namespace prefix declaration: xmlns:prefix="uri"
-                let _ = self.namespaces.register(attr_value, prefix);
+            match self.parse_property_element(child, false) {
+                Ok(node) => structure.set_field(child.key(), node),
+                Err(error) => self.recover(ParseWarningKind::InvalidRdf, error)?,
             }
         }
+        Ok(())
+    }
 
-        // Extract qualifiers from attributes (e.g., xml:lang)
-        current_qualifiers.clear();
-        for (attr_name, attr_value) in attrs {
-            if self.is_lang_attribute(attr_name) {
-                let qualifier = Qualifier::new(ns::XML, "lang", attr_value.clone());
-                current_qualifiers.push(qualifier);
+    /// Parse a property element (or an `rdf:li` array item) into a node
+    fn parse_property_element(&mut self, element: &XmlElement, is_item: bool) -> XmpResult<Node> {
+        if element.ns_uri.is_empty() {
+            return Err(rdf_error(
+                element,
+                "property elements must have a namespace",
+            ));
+        }
+        if element.ns_uri == ns::RDF {
+            let allowed = match element.local.as_str() {
+                "li" => is_item,
+                "value" | "type" => !is_item,
+                _ => false,
+            };
+            if !allowed {
+                return Err(rdf_error(element, "not allowed as a property element"));
             }
+        } else if is_item {
+            return Err(rdf_error(element, "array items must be rdf:li elements"));
         }
-    }
-
-    /// Collect attributes from XML element
-    fn collect_attributes(e: &quick_xml::events::BytesStart<'_>) -> Vec<(String, String)> {
-        e.attributes()
-            .flatten()
-            .map(|attr| {
-                let key = String::from_utf8_lossy(attr.key.as_ref()).to_string();
-                // Decode XML entities in attribute values (e.g., &quot; -> ")
-                let raw_value = String::from_utf8_lossy(attr.value.as_ref());
-                let value = match unescape(&raw_value) {
-                    Ok(unescaped) => unescaped.to_string(),
-                    Err(_) => raw_value.to_string(),
-                };
-                (key, value)
-            })
-            .collect()
-    }
 
-    /// Collect attributes from empty XML element (same as Start)
-    fn collect_attributes_empty(e: &quick_xml::events::BytesStart<'_>) -> Vec<(String, String)> {
-        Self::collect_attributes(e)
+        let attrs = PropertyAttributes::of(element)?;
+        let node = match attrs.parse_type {
+            Some("Resource") => self.parse_resource_property(element, &attrs)?,
+            Some(parse_type @ ("Literal" | "Collection")) => {
+                return Err(rdf_error(
+                    element,
+                    &format!("rdf:parseType=\"{}\" is not allowed in XMP", parse_type),
+                ))
+            }
+            Some(parse_type) => {
+                return Err(rdf_error(
+                    element,
+                    &format!("unknown rdf:parseType=\"{}\"", parse_type),
+                ))
+            }
+            None if !element.children.is_empty() => self.parse_resource_element(element, &attrs)?,
+            None if element.has_text() => Self::parse_literal(element, &attrs)?,
+            None => Self::parse_empty(&attrs),
+        };
+        Self::fold_rdf_value(element, node)
     }
 
-    /// Check if attribute name is a language qualifier
-    fn is_lang_attribute(&self, attr_name: &str) -> bool {
-        attr_name == "lang" || attr_name == "xml:lang" || attr_name.ends_with(":lang")
+    /// Parse a property element holding text
+    ///
+    /// Property attributes on such an element are kept as qualifiers.
+    fn parse_literal(element: &XmlElement, attrs: &PropertyAttributes<'_>) -> XmpResult<Node> {
+        if attrs.resource.is_some() || attrs.value.is_some() || attrs.node_id {
+            return Err(rdf_error(
+                element,
+                "rdf:resource, rdf:value and rdf:nodeID cannot be used with text content",
+            ));
+        }
+        let mut node = SimpleNode::new(element.text.trim());
+        node.qualifiers = attrs.qualifiers();
+        Ok(Node::Simple(node))
     }
 
-    /// Check if element name is a Description element
-    fn is_description_element(&self, name: &str) -> bool {
-        let colon_pos = name.find(':');
-        let (prefix, local_name) = if let Some(pos) = colon_pos {
-            (&name[..pos], &name[pos + 1..])
-        } else {
-            ("", name)
+    /// Parse a property element without content
+    fn parse_empty(attrs: &PropertyAttributes<'_>) -> Node {
+        let mut node = match (attrs.value, attrs.resource) {
+            (Some(value), _) => SimpleNode::new(value),
+            (None, Some(uri)) => SimpleNode::uri(uri),
+            // A blank node or property attributes make a structure
+            _ if attrs.node_id || !attrs.others.is_empty() => {
+                return Node::Structure(attrs.structure())
+            }
+            _ => SimpleNode::new(""),
         };
+        node.qualifiers = attrs.qualifiers();
+        Node::Simple(node)
+    }
 
-        if local_name != "Description" {
-            return false;
+    /// Parse a property element holding an array, a nested
+    /// `rdf:Description` or (leniently) property elements
+    fn parse_resource_element(
+        &mut self,
+        element: &XmlElement,
+        attrs: &PropertyAttributes<'_>,
+    ) -> XmpResult<Node> {
+        if attrs.resource.is_some() || attrs.value.is_some() {
+            return Err(rdf_error(
+                element,
+                "rdf:resource and rdf:value cannot be used with element content",
+            ));
         }
-
-        if let Some(ns_uri) = self.namespaces.get_uri(prefix) {
-            ns_uri == crate::core::namespace::ns::RDF
-        } else {
-            prefix == "rdf" || (prefix.is_empty() && name == "Description")
+        if element.has_text() {
+            return Err(rdf_error(element, "mixed text and element content"));
         }
-    }
-
-    /// Check if element name is an array container (Seq, Bag, Alt)
-    fn is_array_container(&self, name: &str) -> bool {
-        name == "Seq"
-            || name == "Bag"
-            || name == "Alt"
-            || name.ends_with(":Seq")
-            || name.ends_with(":Bag")
-            || name.ends_with(":Alt")
-    }
 
-    /// Check if element name is a li element
-    fn is_li_element(&self, name: &str) -> bool {
-        name == "li" || name.ends_with(":li")
-    }
+        let child = &element.children[0];
+        if !child.is_container() && !child.is_rdf("Description") {
+            // Fields without rdf:parseType="Resource"
+            return self.parse_resource_property(element, attrs);
+        }
+        if element.children.len() > 1 {
+            return Err(rdf_error(element, "more than one child node element"));
+        }
 
-    /// Check if element name is an RDF element
-    fn is_rdf_element(&self, name: &str) -> bool {
-        name == "RDF" || name.ends_with(":RDF")
-    }
+        if child.is_rdf("Description") {
+            let mut structure = attrs.structure();
+            self.parse_node_element(child, &mut structure)?;
+            return Ok(Node::Structure(structure));
+        }
 
-    /// Handle Description element attributes
-    fn handle_description_attributes(
-        &self,
-        attrs: &[(String, String)],
-        root: &mut StructureNode,
-        qualifiers: &[Qualifier],
-    ) -> XmpResult<()> {
-        for (attr_name, attr_value) in attrs {
-            // Skip xmlns declarations, rdf:about, and qualifiers
-            if self.should_skip_attribute(attr_name) {
+        let array_type = match child.local.as_str() {
+            "Seq" => ArrayType::Ordered,
+            "Bag" => ArrayType::Unordered,
+            _ => ArrayType::Alternative,
+        };
+        if child.has_text() {
+            return Err(rdf_error(child, "unexpected text content"));
+        }
+        let mut array = ArrayNode::new(array_type);
+        array.qualifiers = attrs.qualifiers();
+        for item in &child.children {
+            if item.is_dropped() {
                 continue;
             }
-
-            // Parse namespace:property format
-            let Some(colon_pos) = attr_name.find(':') else {
-                continue;
-            };
-
-            let ns_prefix = &attr_name[..colon_pos];
-            let prop_name = &attr_name[colon_pos + 1..];
-
-            // Try to get namespace URI for the prefix
-            // Handle case where prefix in attribute name doesn't match declared prefix
-            // (e.g., TC260:AIGC but xmlns:C260="...")
-            let ns_uri = self.namespaces.get_uri(ns_prefix).or_else(|| {
-                // If prefix not found, try common variations
-                // For TC260, try C260
-                if ns_prefix == "TC260" {
-                    self.namespaces.get_uri("C260")
-                } else if ns_prefix == "C260" {
-                    self.namespaces.get_uri("TC260")
-                } else {
-                    None
-                }
-            });
-
-            let Some(ns_uri) = ns_uri else {
-                continue;
-            };
-
-            let full_path = format!("{}:{}", ns_uri, prop_name);
-            let mut simple_node = Node::simple(attr_value.clone());
-            // Add qualifiers to the node
-            if let Node::Simple(ref mut sn) = simple_node {
-                for qual in qualifiers {
-                    sn.add_qualifier(qual.clone());
-                }
+            match self.parse_property_element(item, true) {
+                Ok(node) => array.append(node),
+                Err(error) => self.recover(ParseWarningKind::InvalidRdf, error)?,
             }
-            root.set_field(full_path.clone(), simple_node);
         }
-        Ok(())
+        Ok(Node::Array(array))
     }
 
-    /// Populate a node's fields from XML attributes if they represent properties.
-    /// If the node is Simple and attributes are present, it will be converted to Structure.
-    fn populate_node_from_attributes(
-        &self,
-        attrs: &[(String, String)],
-        node: &mut Node,
-        qualifiers: &[Qualifier],
-    ) -> XmpResult<()> {
-        let has_properties = attrs.iter().any(|(k, _)| {
-            if self.should_skip_attribute(k) {
-                return false;
-            }
-            if let Some(pos) = k.find(':') {
-                let prefix = &k[..pos];
-                self.namespaces.get_uri(prefix).is_some()
-            } else {
-                false
-            }
-        });
-
-        if !has_properties {
-            return Ok(());
+    /// Parse the fields of a structure (`rdf:parseType="Resource"`)
+    fn parse_resource_property(
+        &mut self,
+        element: &XmlElement,
+        attrs: &PropertyAttributes<'_>,
+    ) -> XmpResult<Node> {
+        if attrs.resource.is_some() || attrs.value.is_some() || attrs.node_id {
+            return Err(rdf_error(
+                element,
+                "rdf:resource, rdf:value and rdf:nodeID cannot be used with fields",
+            ));
+        }
+        if element.has_text() {
+            return Err(rdf_error(element, "unexpected text content"));
         }
+        let mut structure = attrs.structure();
+        self.parse_fields(element, &mut structure)?;
+        Ok(Node::Structure(structure))
+    }
+
+    /// Turn a structure holding an `rdf:value` field into a qualified node.
+    ///
+    /// RDF attaches qualifiers by moving the value into `rdf:value` and making
+    /// each qualifier a sibling property. The value node takes the qualifiers
+    /// of the outer node (e.g. `xml:lang`) followed by the sibling properties.
+    /// Qualifiers are simple values, so a sibling that is an array, a
+    /// structure or carries qualifiers of its own is invalid RDF.
+    fn fold_rdf_value(element: &XmlElement, node: Node) -> XmpResult<Node> {
+        let Node::Structure(mut structure) = node else {
+            return Ok(node);
+        };
+        let value_key = format!("{}:value", ns::RDF);
+        let Some(mut value) = structure.remove_field(&value_key) else {
+            return Ok(Node::Structure(structure));
+        };
 
-        // Convert Node to Structure if it is not already.
-        if !node.is_structure() {
-            let mut struct_node = StructureNode::new();
-            match node {
-                Node::Simple(sn) => {
-                    struct_node.qualifiers = std::mem::take(&mut sn.qualifiers);
+        let mut qualifiers = std::mem::take(&mut structure.qualifiers);
+        for (key, field) in structure.fields {
+            let colon_pos = key.rfind(':').unwrap_or_default();
+            match field {
+                Node::Simple(simple) if simple.qualifiers.is_empty() => {
+                    qualifiers.push(Qualifier::new(
+                        &key[..colon_pos],
+                        &key[colon_pos + 1..],
+                        simple.value,
+                    ));
                 }
-                Node::Array(sn) => {
-                    struct_node.qualifiers = std::mem::take(&mut sn.qualifiers);
+                _ => {
+                    return Err(rdf_error(
+                        element,
+                        &format!(
+                            "qualifier {} of rdf:value must be a simple value",
+                            &key[colon_pos + 1..]
+                        ),
+                    ))
                 }
-                _ => {}
             }
-            *node = Node::Structure(struct_node);
-        }
-
-        if let Node::Structure(ref mut struct_node) = node {
-            self.handle_description_attributes(attrs, struct_node, qualifiers)?;
         }
-
-        Ok(())
-    }
-
-    /// Check if attribute should be skipped during Description processing
-    fn should_skip_attribute(&self, attr_name: &str) -> bool {
-        attr_name == "xmlns"
-            || attr_name.starts_with("xmlns:")
-            || attr_name == "about"
-            || attr_name.ends_with(":about")
-            || self.is_lang_attribute(attr_name)
-            || attr_name == "rdf:parseType"
-            || attr_name.ends_with(":parseType")
+        qualifiers.append(value.qualifiers_mut());
+        *value.qualifiers_mut() = qualifiers;
+        Ok(value)
     }
 }
 
@@ -608,7 +1540,7 @@ mod tests {
 <rdf:RDF>...</rdf:RDF>
 <?xpacket end="w"?>"#;
 
-        let content = parser.extract_packet_content(xml).unwrap();
+        let (_, content) = parser.extract_packet_content(xml).unwrap();
         assert!(content.contains("<rdf:RDF>"));
     }
 
@@ -622,7 +1554,7 @@ mod tests {
                    xmp:CreatorTool="MyApp"/>
 </rdf:RDF>"#;
 
-        let result = parser.parse_rdf(xml);
+        let result = parser.parse_rdf(xml, "");
         assert!(result.is_ok());
         let root = result.unwrap();
 
@@ -651,7 +1583,7 @@ Line3</test:Multiline>
   </rdf:Description>
 </rdf:RDF>"#;
 
-        let root = parser.parse_rdf(xml).unwrap();
+        let root = parser.parse_rdf(xml, "").unwrap();
         let node = root
             .get_field("http://example.com/test/:Multiline")
             .unwrap();
@@ -678,7 +1610,7 @@ Line3</test:Multiline>
             large_val
         );
 
-        let root = parser.parse_rdf(&xml).unwrap();
+        let root = parser.parse_rdf(&xml, "").unwrap();
         let node = root.get_field("http://example.com/test/:Large").unwrap();
         let simple = node.as_simple().unwrap();
         assert_eq!(simple.value, large_val.trim());
@@ -696,7 +1628,7 @@ Line3</test:Multiline>
   </rdf:Description>
 </rdf:RDF>"#;
 
-        let root = parser.parse_rdf(xml).unwrap();
+        let root = parser.parse_rdf(xml, "").unwrap();
         let node = root
             .get_field("http://example.com/test/:Commented")
             .unwrap();
@@ -716,7 +1648,7 @@ Line3</test:Multiline>
   </rdf:Description>
 </rdf:RDF>"#;
 
-        let root = parser.parse_rdf(xml).unwrap();
+        let root = parser.parse_rdf(xml, "").unwrap();
         let node = root.get_field("http://example.com/test/:CharRef").unwrap();
         let simple = node.as_simple().unwrap();
         assert_eq!(simple.value, "Hello World & Universe");
@@ -738,7 +1670,7 @@ Line3</test:Multiline>
   </rdf:Description>
 </rdf:RDF>"#;
 
-        let root = parser.parse_rdf(xml).unwrap();
+        let root = parser.parse_rdf(xml, "").unwrap();
         let xmp_mm_uri = "http://ns.adobe.com/xap/1.0/mm/";
         let st_evt_uri = "http://ns.adobe.com/xap/1.0/sType/ResourceEvent#";
 
@@ -765,7 +1697,7 @@ Line3</test:Multiline>
   </rdf:Description>
 </rdf:RDF>"#;
 
-        let root = parser.parse_rdf(xml).unwrap();
+        let root = parser.parse_rdf(xml, "").unwrap();
         let xmp_mm_uri = "http://ns.adobe.com/xap/1.0/mm/";
 
         let history_key = format!("{}:History", xmp_mm_uri);
@@ -795,7 +1727,7 @@ Line3</test:Multiline>
   </rdf:Description>
 </rdf:RDF>"#;
 
-        let root = parser.parse_rdf(xml).unwrap();
+        let root = parser.parse_rdf(xml, "").unwrap();
         let container_uri = "http://ns.google.com/photos/1.0/container/";
         let item_uri = "http://ns.google.com/photos/1.0/container/item/";
 
@@ -812,6 +1744,40 @@ Line3</test:Multiline>
         assert_eq!(semantic.value, "Primary");
     }
 
+    #[test]
+    fn test_parse_rdf_value_qualifiers() {
+        let mut parser = XmpParser::new();
+        let xml = r#"
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+         xmlns:dc="http://purl.org/dc/elements/1.1/"
+         xmlns:xmp="http://ns.adobe.com/xap/1.0/">
+  <rdf:Description rdf:about="">
+    <dc:creator>
+      <rdf:Seq>
+        <rdf:li rdf:parseType="Resource">
+          <rdf:value>Alice</rdf:value>
+          <xmp:Role>author</xmp:Role>
+        </rdf:li>
+      </rdf:Seq>
+    </dc:creator>
+  </rdf:Description>
+</rdf:RDF>"#;
+
+        let root = parser.parse_rdf(xml, "").unwrap();
+        let creator = root
+            .get_field("http://purl.org/dc/elements/1.1/:creator")
+            .unwrap()
+            .as_array()
+            .unwrap();
+        let item = creator.get(0).unwrap().as_simple().unwrap();
+        assert_eq!(item.value, "Alice");
+        assert_eq!(
+            item.get_qualifier(ns::XMP, "Role")
+                .map(|q| q.value.as_str()),
+            Some("author")
+        );
+    }
+
     #[test]
     fn test_exif_ex_namespace() {
         let mut parser = XmpParser::new();
@@ -822,11 +1788,384 @@ Line3</test:Multiline>
                    exifEX:LensMake="Google"/>
 </rdf:RDF>"#;
 
-        let root = parser.parse_rdf(xml).unwrap();
+        let root = parser.parse_rdf(xml, "").unwrap();
         let lens_make_key = "http://cipa.jp/exif/1.0/:LensMake";
         assert!(root.has_field(lens_make_key));
 
         let lens_make = root.get_field(lens_make_key).unwrap().as_simple().unwrap();
         assert_eq!(lens_make.value, "Google");
     }
+
+    #[test]
+    fn test_parse_resource_and_nested_description() {
+        let mut parser = XmpParser::new();
+        let xml = r#"
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+         xmlns:xmpRights="http://ns.adobe.com/xap/1.0/rights/"
+         xmlns:xmpMM="http://ns.adobe.com/xap/1.0/mm/"
+         xmlns:stRef="http://ns.adobe.com/xap/1.0/sType/ResourceRef#">
+  <rdf:Description rdf:about="uuid:1234">
+    <xmpRights:WebStatement rdf:ID="s1" rdf:resource="http://example.com/rights"/>
+    <xmpMM:DerivedFrom>
+      <rdf:Description stRef:instanceID="xmp.iid:1">
+        <stRef:documentID rdf:datatype="http://www.w3.org/2001/XMLSchema#string">xmp.did:1</stRef:documentID>
+      </rdf:Description>
+    </xmpMM:DerivedFrom>
+    <xmpMM:Ingredients rdf:nodeID="n1"/>
+  </rdf:Description>
+</rdf:RDF>"#;
+
+        let root = parser.parse_rdf(xml, "").unwrap();
+        assert_eq!(parser.about_uri(), Some("uuid:1234"));
+
+        let statement = root
+            .get_field(&format!("{}:WebStatement", ns::XMP_RIGHTS))
+            .unwrap()
+            .as_simple()
+            .unwrap();
+        assert!(statement.is_uri);
+        assert_eq!(statement.value, "http://example.com/rights");
+
+        let derived = root
+            .get_field(&format!("{}:DerivedFrom", ns::XMP_MM))
+            .unwrap()
+            .as_structure()
+            .unwrap();
+        let field = |name: &str| {
+            derived
+                .get_field(&format!("{}:{}", ns::ST_REF, name))
+                .and_then(|node| node.as_simple())
+                .map(|node| node.value.as_str())
+        };
+        assert_eq!(field("instanceID"), Some("xmp.iid:1"));
+        assert_eq!(field("documentID"), Some("xmp.did:1"));
+
+        let ingredients = root.get_field(&format!("{}:Ingredients", ns::XMP_MM));
+        assert!(ingredients
+            .unwrap()
+            .as_structure()
+            .unwrap()
+            .fields
+            .is_empty());
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_rdf() {
+        let wrap = |body: &str| {
+            format!(
+                r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+         xmlns:dc="http://purl.org/dc/elements/1.1/">{}</rdf:RDF>"#,
+                body
+            )
+        };
+        let invalid = [
+            r#"<rdf:Description><dc:title rdf:parseType="Literal"><b>x</b></dc:title></rdf:Description>"#,
+            r#"<rdf:Description><dc:subject rdf:parseType="Collection"><rdf:Description/></dc:subject></rdf:Description>"#,
+            r#"<rdf:Description><dc:title rdf:parseType="Other"/></rdf:Description>"#,
+            r#"<rdf:Description rdf:about="a"/><rdf:Description rdf:about="b"/>"#,
+            r#"<rdf:Description rdf:about="" rdf:nodeID="n"/>"#,
+            r#"<rdf:Description><rdf:li>x</rdf:li></rdf:Description>"#,
+            r#"<rdf:Description><dc:subject><rdf:Bag><dc:x>y</dc:x></rdf:Bag></dc:subject></rdf:Description>"#,
+            r#"<rdf:Description><dc:source rdf:resource="a">text</dc:source></rdf:Description>"#,
+            r#"<dc:title>x</dc:title>"#,
+        ];
+        for body in invalid {
+            let result = XmpParser::new().parse_rdf(&wrap(body), "");
+            assert!(
+                matches!(result, Err(XmpError::ParseError(_))),
+                "accepted {}",
+                body
+            );
+        }
+    }
+
+    /// Wrap properties in an `rdf:RDF` declaring rdf and dc
+    fn packet(properties: &str) -> String {
+        format!(
+            r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+         xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <rdf:Description rdf:about="">{}</rdf:Description>
+</rdf:RDF>"#,
+            properties
+        )
+    }
+
+    #[test]
+    fn test_parse_lenient() {
+        // Markup, warning in lenient mode, lenient value, and the value in
+        // strict mode if it accepts the markup
+        let cases = [
+            (
+                "<dc:source>Fish & Chips &amp; &unknown; &#38;</dc:source>",
+                ParseWarningKind::BareAmpersand,
+                "Fish & Chips & &unknown; &",
+                None,
+            ),
+            (
+                "<dc:source>&unknown; &#38;</dc:source>",
+                ParseWarningKind::BareAmpersand,
+                "&unknown; &",
+                Some("&unknown; &"),
+            ),
+            (
+                "<dc:source>bell\u{7}</dc:source>",
+                ParseWarningKind::InvalidCharacter,
+                "bell",
+                Some("bell\u{7}"),
+            ),
+            (
+                "<dc:source xmp:Label=\"x\">undeclared</dc:source>",
+                ParseWarningKind::UndeclaredPrefix,
+                "undeclared",
+                Some("undeclared"),
+            ),
+            (
+                "<dc:source>dropped</dc:source><zz:x>1</zz:x>",
+                ParseWarningKind::UnknownPrefix,
+                "dropped",
+                Some("dropped"),
+            ),
+            (
+                "<dc:source>open</dc:format></dc:source>",
+                ParseWarningKind::UnmatchedEndTag,
+                "open",
+                None,
+            ),
+            (
+                "<dc:source>kept</dc:source><dc:title rdf:parseType=\"Literal\">x</dc:title>",
+                ParseWarningKind::InvalidRdf,
+                "kept",
+                None,
+            ),
+        ];
+        let source_key = format!("{}:source", ns::DC);
+        for (properties, kind, value, strict) in cases {
+            let xml = packet(properties);
+            let mut parser = XmpParser::new();
+            match strict {
+                Some(strict) => {
+                    let root = parser.parse_rdf(&xml, "").unwrap();
+                    let source = root.get_field(&source_key).unwrap().as_simple().unwrap();
+                    assert_eq!(source.value, strict);
+                    assert!(parser.warnings().is_empty());
+                }
+                None => assert!(parser.parse_rdf(&xml, "").is_err(), "{}", properties),
+            }
+
+            let mut parser = XmpParser::with_options(ParseOptions::default().lenient());
+            let root = parser.parse_rdf(&xml, "").unwrap();
+            let source = root.get_field(&source_key).unwrap().as_simple().unwrap();
+            assert_eq!(source.value, value);
+            assert!(
+                parser.warnings().iter().any(|warning| warning.kind == kind),
+                "{:?} not in {:?}",
+                kind,
+                parser.warnings()
+            );
+            assert_eq!(root.fields.len(), 1);
+        }
+
+        // Unclosed elements at the end of the content
+        let xml = r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+  <rdf:Description rdf:about="" xmlns:dc="http://purl.org/dc/elements/1.1/">
+    <dc:source>truncated</dc:source>"#;
+        let mut parser = XmpParser::new();
+        assert!(parser.parse_rdf(xml, "").unwrap().has_field(&source_key));
+        assert!(parser.warnings().is_empty());
+        let mut parser = XmpParser::with_options(ParseOptions::default().lenient());
+        let root = parser.parse_rdf(xml, "").unwrap();
+        assert!(root.has_field(&source_key));
+        assert_eq!(parser.take_warnings().len(), 2);
+        assert!(parser.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_parse_undeclared_prefix() {
+        // As written by some cameras, and accepted in strict mode too
+        let xml = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+<rdf:Description rdf:about="" xmp:Rating="5"/>
+</rdf:RDF>
+</x:xmpmeta>"#;
+        let mut parser = XmpParser::new();
+        let root = parser.parse_packet(xml).unwrap();
+        let rating = root.get_field(&format!("{}:Rating", ns::XMP)).unwrap();
+        assert_eq!(rating.as_simple().unwrap().value, "5");
+        assert!(parser.warnings().is_empty());
+
+        let mut parser = XmpParser::with_options(ParseOptions::default().lenient());
+        parser.parse_packet(xml).unwrap();
+        assert_eq!(
+            parser.warnings()[0].kind,
+            ParseWarningKind::UndeclaredPrefix
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_utf8() {
+        let source_key = format!("{}:source", ns::DC);
+        let bytes = |properties: &[u8]| {
+            let xml = packet("{}");
+            let (before, after) = xml.split_once("{}").unwrap();
+            [before.as_bytes(), properties, after.as_bytes()].concat()
+        };
+        let text = bytes(b"<dc:source>a\xFF\xFEb</dc:source>");
+        let attribute = bytes(b"<dc:source xml:lang=\"\xC3\">x</dc:source>");
+
+        for parse in [
+            XmpParser::parse_bytes,
+            |parser: &mut XmpParser, bytes: &[u8]| parser.parse_reader(bytes),
+        ] {
+            let error = parse(&mut XmpParser::new(), &text).unwrap_err();
+            assert!(error.to_string().contains("invalid UTF-8 byte 0xFF"));
+            let location = error.parse_location().unwrap();
+            assert_eq!((location.line, location.column), (3, 44));
+            assert_eq!(location.path_string(), "rdf:RDF/rdf:Description/dc:source");
+        }
+        let error = XmpParser::new().parse_bytes(&attribute).unwrap_err();
+        let location = error.parse_location().unwrap();
+        assert_eq!((location.line, location.column), (3, 33));
+
+        let mut parser = XmpParser::with_options(ParseOptions::default().lenient());
+        let root = parser.parse_bytes(&text).unwrap();
+        let source = root.get_field(&source_key).unwrap().as_simple().unwrap();
+        assert_eq!(source.value, "a\u{FFFD}\u{FFFD}b");
+        assert_eq!(parser.warnings().len(), 1);
+        assert_eq!(parser.warnings()[0].kind, ParseWarningKind::InvalidUtf8);
+        assert!(parser.parse_bytes(&attribute).is_ok());
+    }
+
+    #[test]
+    fn test_parse_require_xmp_meta() {
+        let bare = packet("<dc:source>x</dc:source>");
+        let wrapped = format!(
+            r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">{}</x:xmpmeta>"#,
+            bare
+        );
+        let options = ParseOptions::default().require_xmp_meta();
+
+        assert!(XmpParser::new().parse_packet(&bare).is_ok());
+        assert!(XmpParser::with_options(options.clone())
+            .parse_packet(&bare)
+            .is_err());
+        let root = XmpParser::with_options(options)
+            .parse_packet(&wrapped)
+            .unwrap();
+        assert!(root.has_field(&format!("{}:source", ns::DC)));
+    }
+
+    #[test]
+    fn test_parse_error_location() {
+        let packet = |body: &str| {
+            format!(
+                "<?xpacket begin=\"\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?>\n\
+                 <rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\"\n\
+                 \x20        xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n\
+                 <rdf:Description rdf:about=\"\">\n{}\n</rdf:Description>\n</rdf:RDF>\n\
+                 <?xpacket end=\"w\"?>",
+                body
+            )
+        };
+        let locate = |xml: &str| {
+            let error = XmpParser::new().parse_packet(xml).unwrap_err();
+            let location = error.parse_location().cloned();
+            location.unwrap_or_else(|| panic!("no location: {}", error))
+        };
+
+        // RDF error, located in the second pass
+        let xml =
+            packet("  <dc:creator>\n    <rdf:Seq rdf:ID=\"a\">text</rdf:Seq>\n  </dc:creator>");
+        let location = locate(&xml);
+        assert_eq!((location.line, location.column), (6, 5));
+        assert_eq!(
+            location.path_string(),
+            "rdf:RDF/rdf:Description/dc:creator/rdf:Seq"
+        );
+        assert_eq!(location.snippet, "<rdf:Seq rdf:ID=\"a\">");
+        assert_eq!(&xml[location.offset as usize..][..8], "<rdf:Seq");
+
+        // Mismatched end tag, found by the XML reader
+        let location = locate(&packet("  <dc:format>image/png</dc:title>"));
+        assert_eq!((location.line, location.column), (5, 23));
+        assert_eq!(location.path_string(), "rdf:RDF/rdf:Description/dc:format");
+        assert_eq!(location.snippet, "</dc:title>");
+
+        // Malformed attribute, on the element itself
+        let location = locate(&packet("  <dc:format x>image/png</dc:format>"));
+        assert_eq!((location.line, location.column), (5, 3));
+        assert_eq!(location.path_string(), "rdf:RDF/rdf:Description/dc:format");
+        assert_eq!(location.snippet, "<dc:format x>");
+
+        // Offsets from bytes include the byte order mark
+        let xml = packet("  <dc:format>image/png</dc:title>");
+        let mut bytes = vec![0xEF, 0xBB, 0xBF];
+        bytes.extend_from_slice(xml.as_bytes());
+        let error = XmpParser::new().parse_bytes(&bytes).unwrap_err();
+        let location = error.parse_location().unwrap();
+        assert_eq!((location.line, location.column), (5, 23));
+        assert_eq!(&bytes[location.offset as usize..][..4], b"</dc");
+        assert!(error.to_string().contains("at line 5, column 23"));
+    }
+
+    #[test]
+    fn test_parse_limits() {
+        let wrap = |body: &str| {
+            format!(
+                "<rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\" \
+                 xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\
+                 <rdf:Description rdf:about=\"\">{}</rdf:Description></rdf:RDF>",
+                body
+            )
+        };
+        let parse = |limits: ParseLimits, xml: &str| {
+            let options = ParseOptions::default().lenient().limits(limits);
+            XmpParser::with_options(options).parse_packet(xml)
+        };
+        let is_limit =
+            |result: XmpResult<StructureNode>| matches!(result, Err(XmpError::LimitExceeded(_)));
+
+        let items = "<rdf:li>a</rdf:li>".repeat(10);
+        let xml = wrap(&format!(
+            "<dc:subject><rdf:Bag>{}</rdf:Bag></dc:subject>",
+            items
+        ));
+        assert!(parse(ParseLimits::default(), &xml).is_ok());
+
+        // rdf:RDF, rdf:Description, dc:subject, rdf:Bag, rdf:li
+        assert!(parse(ParseLimits::default().max_depth(5), &xml).is_ok());
+        assert!(is_limit(parse(ParseLimits::default().max_depth(4), &xml)));
+
+        // 14 elements and rdf:about (namespace declarations are not counted)
+        assert!(parse(ParseLimits::default().max_nodes(15), &xml).is_ok());
+        assert!(is_limit(parse(ParseLimits::default().max_nodes(14), &xml)));
+
+        // Longer than the namespace URIs, which are values too
+        let value = "x".repeat(64);
+        let xml = wrap(&format!("<dc:format>{}&amp;</dc:format>", value));
+        assert!(parse(ParseLimits::default().max_text_len(65), &xml).is_ok());
+        assert!(is_limit(parse(
+            ParseLimits::default().max_text_len(64),
+            &xml
+        )));
+
+        let size = xml.len() as u64;
+        assert!(parse(ParseLimits::default().max_packet_size(size), &xml).is_ok());
+        assert!(is_limit(parse(
+            ParseLimits::default().max_packet_size(size - 1),
+            &xml
+        )));
+        let result = XmpParser::with_options(
+            ParseOptions::default().limits(ParseLimits::default().max_packet_size(size - 1)),
+        )
+        .parse_reader(xml.as_bytes());
+        assert!(is_limit(result));
+
+        // DOCTYPE and entity declarations
+        let xml = format!(
+            "<!DOCTYPE rdf:RDF [<!ENTITY lol \"lol\">]>\n{}",
+            wrap("<dc:format>&lol;</dc:format>")
+        );
+        assert!(is_limit(parse(ParseLimits::default(), &xml)));
+        assert!(!is_limit(parse(ParseLimits::unlimited(), &xml)));
+    }
 }
@@ -0,0 +1,533 @@
+//! Schema descriptions for XMP
+//!
+//! A [`SchemaRegistry`] records the expected value type of known properties
+//! (e.g., `dc:creator` is a `Seq` of proper names, `xmp:Rating` is a real
+//! number). [`XmpMeta::validate`](crate::XmpMeta::validate) checks metadata
+//! against it.
+
+use crate::core::error::{XmpError, XmpResult};
+use crate::core::namespace::ns;
+use std::collections::HashMap;
+use std::fmt;
+
+/// The value type of a property, structure field or array item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValueType {
+    /// Any text
+    Text,
+    /// The name of a person or organization
+    ProperName,
+    /// A URI or URL
+    Uri,
+    /// A signed decimal integer
+    Integer,
+    /// A decimal number
+    Real,
+    /// `True` or `False`
+    Boolean,
+    /// An XMP date (ISO 8601 subset)
+    Date,
+    /// A rational number written as `numerator/denominator`
+    Rational,
+    /// A language alternative (`rdf:Alt` of text items with `xml:lang`)
+    LangAlt,
+    /// An ordered array (`rdf:Seq`) of the given item type
+    Seq(Box<ValueType>),
+    /// An unordered array (`rdf:Bag`) of the given item type
+    Bag(Box<ValueType>),
+    /// An alternative array (`rdf:Alt`) of the given item type
+    Alt(Box<ValueType>),
+    /// A structure with the given known fields
+    Struct(Vec<FieldDesc>),
+}
+
+impl ValueType {
+    /// An ordered array of `item`
+    pub fn seq(item: ValueType) -> Self {
+        ValueType::Seq(Box::new(item))
+    }
+
+    /// An unordered array of `item`
+    pub fn bag(item: ValueType) -> Self {
+        ValueType::Bag(Box::new(item))
+    }
+
+    /// An alternative array of `item`
+    pub fn alt(item: ValueType) -> Self {
+        ValueType::Alt(Box::new(item))
+    }
+
+    /// Whether values of this type are simple (not arrays or structures)
+    pub fn is_simple(&self) -> bool {
+        !matches!(
+            self,
+            ValueType::LangAlt
+                | ValueType::Seq(_)
+                | ValueType::Bag(_)
+                | ValueType::Alt(_)
+                | ValueType::Struct(_)
+        )
+    }
+}
+
+impl fmt::Display for ValueType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueType::Text => write!(f, "Text"),
+            ValueType::ProperName => write!(f, "ProperName"),
+            ValueType::Uri => write!(f, "URI"),
+            ValueType::Integer => write!(f, "Integer"),
+            ValueType::Real => write!(f, "Real"),
+            ValueType::Boolean => write!(f, "Boolean"),
+            ValueType::Date => write!(f, "Date"),
+            ValueType::Rational => write!(f, "Rational"),
+            ValueType::LangAlt => write!(f, "Lang Alt"),
+            ValueType::Seq(item) => write!(f, "Seq {}", item),
+            ValueType::Bag(item) => write!(f, "Bag {}", item),
+            ValueType::Alt(item) => write!(f, "Alt {}", item),
+            ValueType::Struct(_) => write!(f, "Struct"),
+        }
+    }
+}
+
+/// A known field of a structure type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDesc {
+    /// Namespace URI of the field
+    pub namespace_uri: String,
+    /// Name of the field
+    pub name: String,
+    /// Value type of the field
+    pub value_type: ValueType,
+}
+
+impl FieldDesc {
+    /// Create a new field description
+    pub fn new(
+        namespace_uri: impl Into<String>,
+        name: impl Into<String>,
+        value_type: ValueType,
+    ) -> Self {
+        Self {
+            namespace_uri: namespace_uri.into(),
+            name: name.into(),
+            value_type,
+        }
+    }
+}
+
+/// Registry of property value types
+///
+/// [`SchemaRegistry::new`] comes with the standard schemas (Dublin Core,
+/// XMP Basic, XMP Rights, XMP Media Management, EXIF, TIFF, IPTC Core and
+/// Extension, and Photoshop). [`SchemaRegistry::default`] is empty.
+///
+/// # Example
+///
+/// ```rust
+/// use xmpkit::core::namespace::ns;
+/// use xmpkit::core::schema::{SchemaRegistry, ValueType};
+///
+/// let mut registry = SchemaRegistry::new();
+/// assert_eq!(
+///     registry.get(ns::DC, "creator"),
+///     Some(&ValueType::seq(ValueType::ProperName))
+/// );
+///
+/// registry
+///     .register("http://example.com/ns/", "Score", ValueType::Integer)
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SchemaRegistry {
+    /// Keyed by `namespace_uri:name`
+    properties: HashMap<String, ValueType>,
+}
+
+impl SchemaRegistry {
+    /// Create a new registry with the standard schemas
+    pub fn new() -> Self {
+        let mut registry = Self::default();
+        registry.register_standard_schemas();
+        registry
+    }
+
+    /// Register (or replace) the value type of a top-level property
+    pub fn register(
+        &mut self,
+        namespace_uri: &str,
+        name: &str,
+        value_type: ValueType,
+    ) -> XmpResult<()> {
+        if namespace_uri.is_empty() || name.is_empty() {
+            return Err(XmpError::BadParam(
+                "Namespace URI and property name cannot be empty".to_string(),
+            ));
+        }
+        self.properties
+            .insert(format!("{}:{}", namespace_uri, name), value_type);
+        Ok(())
+    }
+
+    /// Get the value type of a top-level property
+    pub fn get(&self, namespace_uri: &str, name: &str) -> Option<&ValueType> {
+        self.properties.get(&format!("{}:{}", namespace_uri, name))
+    }
+
+    /// Get the properties registered for a namespace as (name, value type) pairs
+    pub fn properties(&self, namespace_uri: &str) -> Vec<(&str, &ValueType)> {
+        self.properties
+            .iter()
+            .filter_map(|(key, value_type)| {
+                let pos = key.rfind(':')?;
+                (&key[..pos] == namespace_uri).then(|| (&key[pos + 1..], value_type))
+            })
+            .collect()
+    }
+
+    fn register_all(&mut self, namespace_uri: &str, properties: Vec<(&str, ValueType)>) {
+        for (name, value_type) in properties {
+            // These should never fail, so we use unwrap
+            self.register(namespace_uri, name, value_type).unwrap();
+        }
+    }
+
+    fn register_standard_schemas(&mut self) {
+        use ValueType::*;
+
+        self.register_all(
+            ns::DC,
+            vec![
+                ("contributor", ValueType::bag(ProperName)),
+                ("coverage", Text),
+                ("creator", ValueType::seq(ProperName)),
+                ("date", ValueType::seq(Date)),
+                ("description", LangAlt),
+                ("format", Text),
+                ("identifier", Text),
+                ("language", ValueType::bag(Text)),
+                ("publisher", ValueType::bag(ProperName)),
+                ("relation", ValueType::bag(Text)),
+                ("rights", LangAlt),
+                ("source", Text),
+                ("subject", ValueType::bag(Text)),
+                ("title", LangAlt),
+                ("type", ValueType::bag(Text)),
+            ],
+        );
+
+        let thumbnail = Struct(vec![
+            FieldDesc::new(ns::XMP_IMAGE, "format", Text),
+            FieldDesc::new(ns::XMP_IMAGE, "width", Integer),
+            FieldDesc::new(ns::XMP_IMAGE, "height", Integer),
+            FieldDesc::new(ns::XMP_IMAGE, "image", Text),
+        ]);
+        self.register_all(
+            ns::XMP,
+            vec![
+                ("Advisory", ValueType::bag(Text)),
+                ("BaseURL", Uri),
+                ("CreateDate", Date),
+                ("CreatorTool", Text),
+                ("Identifier", ValueType::bag(Text)),
+                ("Label", Text),
+                ("MetadataDate", Date),
+                ("ModifyDate", Date),
+                ("Nickname", Text),
+                ("Rating", Real),
+                ("Thumbnails", ValueType::alt(thumbnail)),
+            ],
+        );
+
+        self.register_all(
+            ns::XMP_RIGHTS,
+            vec![
+                ("Certificate", Uri),
+                ("Marked", Boolean),
+                ("Owner", ValueType::bag(ProperName)),
+                ("UsageTerms", LangAlt),
+                ("WebStatement", Uri),
+            ],
+        );
+
+        let resource_event = Struct(vec![
+            FieldDesc::new(ns::ST_EVT, "action", Text),
+            FieldDesc::new(ns::ST_EVT, "changed", Text),
+            FieldDesc::new(ns::ST_EVT, "instanceID", Uri),
+            FieldDesc::new(ns::ST_EVT, "parameters", Text),
+            FieldDesc::new(ns::ST_EVT, "softwareAgent", Text),
+            FieldDesc::new(ns::ST_EVT, "when", Date),
+        ]);
+        let resource_ref = Struct(vec![
+            FieldDesc::new(ns::ST_REF, "documentID", Uri),
+            FieldDesc::new(ns::ST_REF, "filePath", Uri),
+            FieldDesc::new(ns::ST_REF, "instanceID", Uri),
+            FieldDesc::new(ns::ST_REF, "lastModifyDate", Date),
+            FieldDesc::new(ns::ST_REF, "manager", Text),
+            FieldDesc::new(ns::ST_REF, "managerVariant", Text),
+            FieldDesc::new(ns::ST_REF, "manageTo", Uri),
+            FieldDesc::new(ns::ST_REF, "manageUI", Uri),
+            FieldDesc::new(ns::ST_REF, "originalDocumentID", Uri),
+            FieldDesc::new(ns::ST_REF, "renditionClass", Text),
+            FieldDesc::new(ns::ST_REF, "renditionParams", Text),
+            FieldDesc::new(ns::ST_REF, "versionID", Text),
+        ]);
+        let version = Struct(vec![
+            FieldDesc::new(ns::ST_VER, "comments", Text),
+            FieldDesc::new(ns::ST_VER, "event", resource_event.clone()),
+            FieldDesc::new(ns::ST_VER, "modifier", ProperName),
+            FieldDesc::new(ns::ST_VER, "modifyDate", Date),
+            FieldDesc::new(ns::ST_VER, "version", Text),
+        ]);
+        self.register_all(
+            ns::XMP_MM,
+            vec![
+                ("DerivedFrom", resource_ref.clone()),
+                ("DocumentID", Uri),
+                ("History", ValueType::seq(resource_event)),
+                ("Ingredients", ValueType::bag(resource_ref.clone())),
+                ("InstanceID", Uri),
+                ("ManagedFrom", resource_ref),
+                ("Manager", Text),
+                ("ManageTo", Uri),
+                ("ManageUI", Uri),
+                ("ManagerVariant", Text),
+                ("OriginalDocumentID", Uri),
+                ("RenditionClass", Text),
+                ("RenditionParams", Text),
+                ("VersionID", Text),
+                ("Versions", ValueType::seq(version)),
+            ],
+        );
+
+        let flash = Struct(vec![
+            FieldDesc::new(ns::EXIF, "Fired", Boolean),
+            FieldDesc::new(ns::EXIF, "Return", Integer),
+            FieldDesc::new(ns::EXIF, "Mode", Integer),
+            FieldDesc::new(ns::EXIF, "Function", Boolean),
+            FieldDesc::new(ns::EXIF, "RedEyeMode", Boolean),
+        ]);
+        self.register_all(
+            ns::EXIF,
+            vec![
+                ("ExifVersion", Text),
+                ("FlashpixVersion", Text),
+                ("ColorSpace", Integer),
+                ("PixelXDimension", Integer),
+                ("PixelYDimension", Integer),
+                ("UserComment", LangAlt),
+                ("RelatedSoundFile", Text),
+                ("DateTimeOriginal", Date),
+                ("ExposureTime", Rational),
+                ("FNumber", Rational),
+                ("ExposureProgram", Integer),
+                ("SpectralSensitivity", Text),
+                ("ISOSpeedRatings", ValueType::seq(Integer)),
+                ("ShutterSpeedValue", Rational),
+                ("ApertureValue", Rational),
+                ("BrightnessValue", Rational),
+                ("ExposureBiasValue", Rational),
+                ("MaxApertureValue", Rational),
+                ("SubjectDistance", Rational),
+                ("MeteringMode", Integer),
+                ("LightSource", Integer),
+                ("Flash", flash),
+                ("FocalLength", Rational),
+                ("SubjectArea", ValueType::seq(Integer)),
+                ("FlashEnergy", Rational),
+                ("FocalPlaneXResolution", Rational),
+                ("FocalPlaneYResolution", Rational),
+                ("FocalPlaneResolutionUnit", Integer),
+                ("SubjectLocation", ValueType::seq(Integer)),
+                ("ExposureIndex", Rational),
+                ("SensingMethod", Integer),
+                ("FileSource", Integer),
+                ("SceneType", Integer),
+                ("CustomRendered", Integer),
+                ("ExposureMode", Integer),
+                ("WhiteBalance", Integer),
+                ("DigitalZoomRatio", Rational),
+                ("FocalLengthIn35mmFilm", Integer),
+                ("SceneCaptureType", Integer),
+                ("GainControl", Integer),
+                ("Contrast", Integer),
+                ("Saturation", Integer),
+                ("Sharpness", Integer),
+                ("SubjectDistanceRange", Integer),
+                ("ImageUniqueID", Text),
+                ("GPSVersionID", Text),
+                ("GPSLatitude", Text),
+                ("GPSLongitude", Text),
+                ("GPSAltitudeRef", Integer),
+                ("GPSAltitude", Rational),
+                ("GPSTimeStamp", Date),
+                ("GPSSatellites", Text),
+                ("GPSStatus", Text),
+                ("GPSMeasureMode", Text),
+                ("GPSDOP", Rational),
+                ("GPSSpeedRef", Text),
+                ("GPSSpeed", Rational),
+                ("GPSTrackRef", Text),
+                ("GPSTrack", Rational),
+                ("GPSImgDirectionRef", Text),
+                ("GPSImgDirection", Rational),
+                ("GPSMapDatum", Text),
+                ("GPSDestLatitude", Text),
+                ("GPSDestLongitude", Text),
+                ("GPSDestBearingRef", Text),
+                ("GPSDestBearing", Rational),
+                ("GPSDestDistanceRef", Text),
+                ("GPSDestDistance", Rational),
+                ("GPSProcessingMethod", Text),
+                ("GPSAreaInformation", Text),
+                ("GPSDifferential", Integer),
+            ],
+        );
+
+        // tiff:ImageDescription, Artist, Copyright, DateTime and Software are
+        // aliases and never stored, so they are not listed
+        self.register_all(
+            ns::TIFF,
+            vec![
+                ("ImageWidth", Integer),
+                ("ImageLength", Integer),
+                ("BitsPerSample", ValueType::seq(Integer)),
+                ("Compression", Integer),
+                ("PhotometricInterpretation", Integer),
+                ("Orientation", Integer),
+                ("SamplesPerPixel", Integer),
+                ("PlanarConfiguration", Integer),
+                ("YCbCrSubSampling", ValueType::seq(Integer)),
+                ("YCbCrPositioning", Integer),
+                ("XResolution", Rational),
+                ("YResolution", Rational),
+                ("ResolutionUnit", Integer),
+                ("TransferFunction", ValueType::seq(Integer)),
+                ("WhitePoint", ValueType::seq(Rational)),
+                ("PrimaryChromaticities", ValueType::seq(Rational)),
+                ("YCbCrCoefficients", ValueType::seq(Rational)),
+                ("ReferenceBlackWhite", ValueType::seq(Rational)),
+                ("Make", ProperName),
+                ("Model", ProperName),
+            ],
+        );
+
+        let contact_info = Struct(
+            [
+                "CiAdrExtadr",
+                "CiAdrCity",
+                "CiAdrRegion",
+                "CiAdrPcode",
+                "CiAdrCtry",
+                "CiEmailWork",
+                "CiTelWork",
+                "CiUrlWork",
+            ]
+            .into_iter()
+            .map(|name| FieldDesc::new(ns::IPTC_CORE, name, Text))
+            .collect(),
+        );
+        self.register_all(
+            ns::IPTC_CORE,
+            vec![
+                ("AltTextAccessibility", LangAlt),
+                ("CountryCode", Text),
+                ("CreatorContactInfo", contact_info),
+                ("ExtDescrAccessibility", LangAlt),
+                ("IntellectualGenre", Text),
+                ("Location", Text),
+                ("Scene", ValueType::bag(Text)),
+                ("SubjectCode", ValueType::bag(Text)),
+            ],
+        );
+
+        let location = Struct(vec![
+            FieldDesc::new(ns::IPTC_EXT, "City", Text),
+            FieldDesc::new(ns::IPTC_EXT, "CountryCode", Text),
+            FieldDesc::new(ns::IPTC_EXT, "CountryName", Text),
+            FieldDesc::new(ns::IPTC_EXT, "LocationId", ValueType::bag(Uri)),
+            FieldDesc::new(ns::IPTC_EXT, "LocationName", LangAlt),
+            FieldDesc::new(ns::IPTC_EXT, "ProvinceState", Text),
+            FieldDesc::new(ns::IPTC_EXT, "Sublocation", Text),
+            FieldDesc::new(ns::IPTC_EXT, "WorldRegion", Text),
+        ]);
+        let artwork = Struct(vec![
+            FieldDesc::new(ns::IPTC_EXT, "AOCopyrightNotice", Text),
+            FieldDesc::new(ns::IPTC_EXT, "AOCreator", ValueType::seq(ProperName)),
+            FieldDesc::new(ns::IPTC_EXT, "AODateCreated", Date),
+            FieldDesc::new(ns::IPTC_EXT, "AOSource", Text),
+            FieldDesc::new(ns::IPTC_EXT, "AOSourceInvNo", Text),
+            FieldDesc::new(ns::IPTC_EXT, "AOTitle", LangAlt),
+        ]);
+        self.register_all(
+            ns::IPTC_EXT,
+            vec![
+                ("AddlModelInfo", Text),
+                ("ArtworkOrObject", ValueType::bag(artwork)),
+                ("DigImageGUID", Text),
+                ("DigitalSourceType", Uri),
+                ("Event", LangAlt),
+                ("LocationCreated", ValueType::bag(location.clone())),
+                ("LocationShown", ValueType::bag(location)),
+                ("MaxAvailHeight", Integer),
+                ("MaxAvailWidth", Integer),
+                ("ModelAge", ValueType::bag(Integer)),
+                ("OrganisationInImageCode", ValueType::bag(Text)),
+                ("OrganisationInImageName", ValueType::bag(Text)),
+                ("PersonInImage", ValueType::bag(Text)),
+            ],
+        );
+
+        let text_layer = Struct(vec![
+            FieldDesc::new(ns::PHOTOSHOP, "LayerName", Text),
+            FieldDesc::new(ns::PHOTOSHOP, "LayerText", Text),
+        ]);
+        self.register_all(
+            ns::PHOTOSHOP,
+            vec![
+                ("AuthorsPosition", Text),
+                ("CaptionWriter", ProperName),
+                ("Category", Text),
+                ("City", Text),
+                ("ColorMode", Integer),
+                ("Country", Text),
+                ("Credit", Text),
+                ("DateCreated", Date),
+                ("DocumentAncestors", ValueType::bag(Text)),
+                ("Headline", Text),
+                ("History", Text),
+                ("ICCProfile", Text),
+                ("Instructions", Text),
+                ("Source", Text),
+                ("State", Text),
+                ("SupplementalCategories", ValueType::bag(Text)),
+                ("TextLayers", ValueType::seq(text_layer)),
+                ("TransmissionReference", Text),
+                ("Urgency", Integer),
+            ],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standard_schemas() {
+        let registry = SchemaRegistry::new();
+        assert_eq!(registry.get(ns::DC, "title"), Some(&ValueType::LangAlt));
+        assert_eq!(registry.get(ns::XMP, "Rating"), Some(&ValueType::Real));
+        assert!(matches!(
+            registry.get(ns::XMP_MM, "History"),
+            Some(ValueType::Seq(item)) if matches!(**item, ValueType::Struct(_))
+        ));
+        assert!(registry.get(ns::TIFF, "Artist").is_none());
+        assert_eq!(registry.properties(ns::XMP_RIGHTS).len(), 5);
+        assert!(SchemaRegistry::default().get(ns::DC, "title").is_none());
+        assert_eq!(
+            ValueType::bag(ValueType::ProperName).to_string(),
+            "Bag ProperName"
+        );
+    }
+}
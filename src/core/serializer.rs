@@ -5,6 +5,7 @@
 use crate::core::error::{XmpError, XmpResult};
 use crate::core::namespace::{ns, NamespaceMap};
 use crate::core::node::{ArrayNode, ArrayType, Node, StructureNode};
+use crate::types::qualifier::Qualifier;
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::Writer;
 use std::io::Cursor;
@@ -12,6 +13,7 @@ use std::io::Cursor;
 /// Serializer for XMP Packets
 pub struct XmpSerializer {
     namespaces: NamespaceMap,
+    about_uri: String,
 }
 
 impl XmpSerializer {
@@ -19,12 +21,22 @@ impl XmpSerializer {
     pub fn new() -> Self {
         Self {
             namespaces: NamespaceMap::new(),
+            about_uri: String::new(),
         }
     }
 
     /// Create a serializer with a pre-populated namespace map.
     pub fn with_namespaces(namespaces: NamespaceMap) -> Self {
-        Self { namespaces }
+        Self {
+            namespaces,
+            about_uri: String::new(),
+        }
+    }
+
+    /// Set the `rdf:about` URI of the serialized description (default: empty)
+    pub fn with_about_uri(mut self, uri: impl Into<String>) -> Self {
+        self.about_uri = uri.into();
+        self
     }
 
     /// Serialize a StructureNode to RDF/XML
@@ -71,13 +83,13 @@ impl XmpSerializer {
 
         // Add dynamically discovered namespaces
         for (ns_uri, prefix) in &used_namespaces {
-            // Skip namespaces already declared above
-            match ns_uri.as_str() {
-                "http://www.w3.org/1999/02/22-rdf-syntax-ns#" => continue,
-                "http://ns.adobe.com/xap/1.0/" => continue,
-                "http://purl.org/dc/elements/1.1/" => continue,
-                "http://ns.adobe.com/exif/1.0/" => continue,
-                ns::XML => continue,
+            // Skip namespaces already declared above (with the same prefix)
+            match (ns_uri.as_str(), prefix.as_str()) {
+                ("http://www.w3.org/1999/02/22-rdf-syntax-ns#", "rdf") => continue,
+                ("http://ns.adobe.com/xap/1.0/", "xmp") => continue,
+                ("http://purl.org/dc/elements/1.1/", "dc") => continue,
+                ("http://ns.adobe.com/exif/1.0/", "exif") => continue,
+                (ns::XML, _) => continue,
                 _ => {
                     rdf_start
                         .push_attribute((format!("xmlns:{}", prefix).as_str(), ns_uri.as_str()));
@@ -89,7 +101,7 @@ impl XmpSerializer {
 
         // Write Description element with attributes and nested elements
         let mut desc_start = BytesStart::new("rdf:Description");
-        desc_start.push_attribute(("rdf:about", ""));
+        desc_start.push_attribute(("rdf:about", self.about_uri.as_str()));
 
         // Add simple attributes to Description
         for (attr_name, attr_value) in &simple_attrs {
@@ -165,6 +177,16 @@ impl XmpSerializer {
         path: &str,
         node: &Node,
     ) -> XmpResult<()> {
+        if self.has_general_qualifiers(node) {
+            let (prefix, prop_name) = self
+                .parse_path(path)
+                .ok_or_else(|| XmpError::BadXPath(format!("Invalid path format: {}", path)))?;
+            return self.serialize_qualified_node(
+                writer,
+                &format!("{}:{}", prefix, prop_name),
+                node,
+            );
+        }
         match node {
             Node::Simple(simple) => {
                 self.serialize_simple_node(writer, path, simple)?;
@@ -194,7 +216,13 @@ impl XmpSerializer {
         let mut elem_start = BytesStart::new(&elem_name);
 
         // Add qualifiers as attributes (e.g., xml:lang)
-        self.add_lang_qualifier_attributes(&Node::Simple(node.clone()), &mut elem_start);
+        self.add_lang_qualifier_attributes(&node.qualifiers, &mut elem_start);
+
+        if node.is_uri {
+            elem_start.push_attribute(("rdf:resource", node.value.as_str()));
+            writer.write_event(Event::Empty(elem_start))?;
+            return Ok(());
+        }
 
         writer.write_event(Event::Start(elem_start))?;
         writer.write_event(Event::Text(BytesText::new(&node.value)))?;
@@ -214,24 +242,47 @@ impl XmpSerializer {
             .parse_path(path)
             .ok_or_else(|| XmpError::BadXPath(format!("Invalid path format: {}", path)))?;
 
+        // Write property element containing the container
+        let prop_elem = format!("{}:{}", prefix, prop_name);
+        let mut prop_start = BytesStart::new(&prop_elem);
+        self.add_lang_qualifier_attributes(&node.qualifiers, &mut prop_start);
+        writer.write_event(Event::Start(prop_start))?;
+        self.serialize_array_container(writer, node)?;
+        writer.write_event(Event::End(BytesEnd::new(&prop_elem)))?;
+        Ok(())
+    }
+
+    /// Serialize the rdf:Seq / rdf:Bag / rdf:Alt container of an array node
+    fn serialize_array_container(
+        &self,
+        writer: &mut Writer<Cursor<Vec<u8>>>,
+        node: &ArrayNode,
+    ) -> XmpResult<()> {
         let container_name = match node.array_type {
             ArrayType::Ordered => "rdf:Seq",
             ArrayType::Unordered => "rdf:Bag",
             ArrayType::Alternative => "rdf:Alt",
         };
 
-        // Write property element containing the container
-        let prop_elem = format!("{}:{}", prefix, prop_name);
-        writer.write_event(Event::Start(BytesStart::new(&prop_elem)))?;
-
         if !node.items.is_empty() {
             // Write container element
             writer.write_event(Event::Start(BytesStart::new(container_name)))?;
 
             // Write list items
             for item in &node.items {
+                if self.has_general_qualifiers(item) {
+                    self.serialize_qualified_node(writer, "rdf:li", item)?;
+                    continue;
+                }
                 let mut li_start = BytesStart::new("rdf:li");
-                self.add_lang_qualifier_attributes(item, &mut li_start);
+                self.add_lang_qualifier_attributes(item.qualifiers(), &mut li_start);
+                if let Node::Simple(simple) = item {
+                    if simple.is_uri {
+                        li_start.push_attribute(("rdf:resource", simple.value.as_str()));
+                        writer.write_event(Event::Empty(li_start))?;
+                        continue;
+                    }
+                }
                 writer.write_event(Event::Start(li_start))?;
 
                 self.serialize_array_item(writer, item)?;
@@ -244,10 +295,91 @@ impl XmpSerializer {
             // Write container empty element
             writer.write_event(Event::Empty(BytesStart::new(container_name)))?;
         }
-        writer.write_event(Event::End(BytesEnd::new(&prop_elem)))?;
         Ok(())
     }
 
+    /// Serialize a node carrying qualifiers other than `xml:lang`
+    ///
+    /// The node value goes into an `rdf:value` element and each qualifier
+    /// becomes a sibling property element:
+    ///
+    /// ```xml
+    /// <dc:creator rdf:parseType="Resource">
+    ///   <rdf:value>Alice</rdf:value>
+    ///   <ns:role>author</ns:role>
+    /// </dc:creator>
+    /// ```
+    fn serialize_qualified_node(
+        &self,
+        writer: &mut Writer<Cursor<Vec<u8>>>,
+        elem_name: &str,
+        node: &Node,
+    ) -> XmpResult<()> {
+        let mut elem_start = BytesStart::new(elem_name);
+        elem_start.push_attribute(("rdf:parseType", "Resource"));
+        self.add_lang_qualifier_attributes(node.qualifiers(), &mut elem_start);
+        writer.write_event(Event::Start(elem_start))?;
+
+        match node {
+            Node::Simple(simple) if simple.is_uri => {
+                let mut value_start = BytesStart::new("rdf:value");
+                value_start.push_attribute(("rdf:resource", simple.value.as_str()));
+                writer.write_event(Event::Empty(value_start))?;
+            }
+            Node::Simple(simple) => {
+                writer.write_event(Event::Start(BytesStart::new("rdf:value")))?;
+                writer.write_event(Event::Text(BytesText::new(&simple.value)))?;
+                writer.write_event(Event::End(BytesEnd::new("rdf:value")))?;
+            }
+            Node::Array(array) => {
+                writer.write_event(Event::Start(BytesStart::new("rdf:value")))?;
+                self.serialize_array_container(writer, array)?;
+                writer.write_event(Event::End(BytesEnd::new("rdf:value")))?;
+            }
+            Node::Structure(structure) => {
+                let mut value_start = BytesStart::new("rdf:value");
+                value_start.push_attribute(("rdf:parseType", "Resource"));
+                writer.write_event(Event::Start(value_start))?;
+                for (key, value) in &structure.fields {
+                    self.serialize_node(writer, key, value)?;
+                }
+                writer.write_event(Event::End(BytesEnd::new("rdf:value")))?;
+            }
+        }
+
+        for qualifier in node.qualifiers() {
+            if Self::is_lang_qualifier(qualifier) {
+                continue;
+            }
+            let key = format!("{}:{}", qualifier.namespace, qualifier.name);
+            let (prefix, name) = self.parse_path(&key).ok_or_else(|| {
+                XmpError::BadSchema(format!(
+                    "Unregistered qualifier namespace URI '{}'",
+                    qualifier.namespace
+                ))
+            })?;
+            let qual_elem = format!("{}:{}", prefix, name);
+            writer.write_event(Event::Start(BytesStart::new(&qual_elem)))?;
+            writer.write_event(Event::Text(BytesText::new(&qualifier.value)))?;
+            writer.write_event(Event::End(BytesEnd::new(&qual_elem)))?;
+        }
+
+        writer.write_event(Event::End(BytesEnd::new(elem_name)))?;
+        Ok(())
+    }
+
+    /// Check if a qualifier is `xml:lang`
+    fn is_lang_qualifier(qualifier: &Qualifier) -> bool {
+        qualifier.namespace == ns::XML && qualifier.name == "lang"
+    }
+
+    /// Check if a node has qualifiers that cannot be written as `xml:lang` attributes
+    fn has_general_qualifiers(&self, node: &Node) -> bool {
+        node.qualifiers()
+            .iter()
+            .any(|q| !Self::is_lang_qualifier(q))
+    }
+
     /// Serialize a structure node
     fn serialize_structure_node(
         &self,
@@ -259,21 +391,17 @@ impl XmpSerializer {
             .parse_path(path)
             .ok_or_else(|| XmpError::BadXPath(format!("Invalid path format: {}", path)))?;
 
-        // Write property element containing the structure
+        // Write the fields inside a property element with rdf:parseType="Resource"
         let prop_elem = format!("{}:{}", prefix, prop_name);
-        writer.write_event(Event::Start(BytesStart::new(&prop_elem)))?;
+        let mut prop_start = BytesStart::new(&prop_elem);
+        prop_start.push_attribute(("rdf:parseType", "Resource"));
+        self.add_lang_qualifier_attributes(&node.qualifiers, &mut prop_start);
+        writer.write_event(Event::Start(prop_start))?;
 
-        // Write structure as nested Description with rdf:parseType="Resource"
-        let mut desc_start = BytesStart::new("rdf:Description");
-        desc_start.push_attribute(("rdf:parseType", "Resource"));
-        writer.write_event(Event::Start(desc_start))?;
-
-        // Write fields
         for (key, value) in &node.fields {
             self.serialize_node(writer, key, value)?;
         }
 
-        writer.write_event(Event::End(BytesEnd::new("rdf:Description")))?;
         writer.write_event(Event::End(BytesEnd::new(&prop_elem)))?;
         Ok(())
     }
@@ -285,21 +413,14 @@ impl XmpSerializer {
             return true;
         };
 
-        // Simple nodes with xml:lang qualifier must be elements
-        simple
-            .qualifiers
-            .iter()
-            .any(|q| q.namespace == ns::XML && q.name == "lang")
+        // Simple nodes with qualifiers (e.g., xml:lang) or URI values must be elements
+        !simple.qualifiers.is_empty() || simple.is_uri
     }
 
     /// Add language qualifier attributes to an element
-    fn add_lang_qualifier_attributes(&self, node: &Node, elem_start: &mut BytesStart) {
-        let Node::Simple(simple) = node else {
-            return;
-        };
-
-        for qualifier in &simple.qualifiers {
-            if qualifier.namespace == ns::XML && qualifier.name == "lang" {
+    fn add_lang_qualifier_attributes(&self, qualifiers: &[Qualifier], elem_start: &mut BytesStart) {
+        for qualifier in qualifiers {
+            if Self::is_lang_qualifier(qualifier) {
                 elem_start.push_attribute(("xml:lang", qualifier.value.as_str()));
             }
         }
@@ -322,10 +443,8 @@ impl XmpSerializer {
                 }
                 writer.write_event(Event::End(BytesEnd::new("rdf:Description")))?;
             }
-            Node::Array(_) => {
-                return Err(XmpError::NotSupported(
-                    "Nested arrays not yet supported".to_string(),
-                ));
+            Node::Array(array) => {
+                self.serialize_array_container(writer, array)?;
             }
         }
         Ok(())
@@ -399,6 +518,15 @@ impl XmpSerializer {
         node: &Node,
         used_namespaces: &mut indexmap::IndexMap<String, String>,
     ) {
+        for qualifier in node.qualifiers() {
+            if Self::is_lang_qualifier(qualifier) {
+                continue;
+            }
+            let key = format!("{}:{}", qualifier.namespace, qualifier.name);
+            if let Some((prefix, _, ns_uri)) = self.parse_path_with_namespace(&key) {
+                used_namespaces.insert(ns_uri, prefix);
+            }
+        }
         match node {
             Node::Simple(_) => {}
             Node::Array(arr) => {
@@ -453,6 +581,23 @@ mod tests {
         assert!(packet.contains("xmp:CreatorTool"));
     }
 
+    #[test]
+    fn test_serialize_declares_non_default_prefix() {
+        let mut namespaces = NamespaceMap::default();
+        namespaces
+            .register("http://ns.adobe.com/xap/1.0/", "xap")
+            .unwrap();
+        let serializer = XmpSerializer::with_namespaces(namespaces);
+        let mut root = StructureNode::new();
+        root.set_field(
+            "http://ns.adobe.com/xap/1.0/:CreatorTool".to_string(),
+            Node::simple("TestApp".to_string()),
+        );
+        let rdf = serializer.serialize_rdf(&root).unwrap();
+        assert!(rdf.contains(r#"xmlns:xap="http://ns.adobe.com/xap/1.0/""#));
+        assert!(rdf.contains(r#"xap:CreatorTool="TestApp""#));
+    }
+
     #[test]
     fn test_serialize_packet_with_padding() {
         let serializer = XmpSerializer::new();
@@ -541,7 +686,7 @@ mod tests {
             rdf
         );
         assert!(
-            rdf.find("<exif:Nested>").unwrap() < rdf.find("<dc:SecondNested>").unwrap(),
+            rdf.find("<exif:Nested ").unwrap() < rdf.find("<dc:SecondNested ").unwrap(),
             "complex nodes should preserve insertion order: {}",
             rdf
         );
@@ -552,6 +697,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_serialize_general_qualifiers() {
+        use crate::core::node::SimpleNode;
+        use crate::types::qualifier::Qualifier;
+
+        let serializer = XmpSerializer::new();
+        let mut item = SimpleNode::new("Alice");
+        item.add_qualifier(Qualifier::new(ns::XMP, "Role", "author"));
+        let mut creator = ArrayNode::new(ArrayType::Ordered);
+        creator.append(Node::Simple(item));
+
+        let mut root = StructureNode::new();
+        root.set_field(
+            "http://purl.org/dc/elements/1.1/:creator",
+            Node::Array(creator),
+        );
+
+        let rdf = serializer.serialize_rdf(&root).unwrap();
+        assert!(
+            rdf.contains("<rdf:li rdf:parseType=\"Resource\">"),
+            "{}",
+            rdf
+        );
+        assert!(rdf.contains("<rdf:value>Alice</rdf:value>"), "{}", rdf);
+        assert!(rdf.contains("<xmp:Role>author</xmp:Role>"), "{}", rdf);
+    }
+
     #[test]
     /// Test that serializing an empty array generates an empty
     /// element tag.
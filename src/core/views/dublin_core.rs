@@ -0,0 +1,146 @@
+//! Dublin Core view
+
+use super::{localized_text, set_localized_text, set_text_items, text, text_items};
+use crate::core::error::XmpResult;
+use crate::core::metadata::XmpMeta;
+use crate::core::namespace::ns;
+use crate::core::node::ArrayType;
+use std::ops::{Deref, DerefMut};
+
+/// Typed access to the Dublin Core schema (`dc:`)
+///
+/// Obtained with [`XmpMeta::dublin_core`] or [`XmpMeta::dublin_core_mut`].
+/// Language arguments are tags such as `en`, `en-US` or `x-default`; lookups
+/// fall back to the generic language and then to `x-default`.
+#[derive(Debug)]
+pub struct DublinCore<M> {
+    meta: M,
+}
+
+impl XmpMeta {
+    /// Read-only view of the Dublin Core properties
+    pub fn dublin_core(&self) -> DublinCore<&XmpMeta> {
+        DublinCore { meta: self }
+    }
+
+    /// Writable view of the Dublin Core properties
+    pub fn dublin_core_mut(&mut self) -> DublinCore<&mut XmpMeta> {
+        DublinCore { meta: self }
+    }
+}
+
+impl<M: Deref<Target = XmpMeta>> DublinCore<M> {
+    /// The title (`dc:title`) in the given language
+    pub fn title(&self, lang: &str) -> Option<String> {
+        localized_text(&self.meta, ns::DC, "title", lang)
+    }
+
+    /// The description (`dc:description`) in the given language
+    pub fn description(&self, lang: &str) -> Option<String> {
+        localized_text(&self.meta, ns::DC, "description", lang)
+    }
+
+    /// The copyright notice (`dc:rights`) in the given language
+    pub fn rights(&self, lang: &str) -> Option<String> {
+        localized_text(&self.meta, ns::DC, "rights", lang)
+    }
+
+    /// The creators (`dc:creator`), in order
+    pub fn creators(&self) -> Vec<String> {
+        text_items(&self.meta, ns::DC, "creator")
+    }
+
+    /// The keywords (`dc:subject`)
+    pub fn subjects(&self) -> Vec<String> {
+        text_items(&self.meta, ns::DC, "subject")
+    }
+
+    /// The MIME type of the resource (`dc:format`)
+    pub fn format(&self) -> Option<String> {
+        text(&self.meta, ns::DC, "format")
+    }
+}
+
+impl<M: DerefMut<Target = XmpMeta>> DublinCore<M> {
+    /// Set the title (`dc:title`) in the given language
+    pub fn set_title(&mut self, lang: &str, text: &str) -> XmpResult<()> {
+        set_localized_text(&mut self.meta, ns::DC, "title", lang, text)
+    }
+
+    /// Set the description (`dc:description`) in the given language
+    pub fn set_description(&mut self, lang: &str, text: &str) -> XmpResult<()> {
+        set_localized_text(&mut self.meta, ns::DC, "description", lang, text)
+    }
+
+    /// Set the copyright notice (`dc:rights`) in the given language
+    pub fn set_rights(&mut self, lang: &str, text: &str) -> XmpResult<()> {
+        set_localized_text(&mut self.meta, ns::DC, "rights", lang, text)
+    }
+
+    /// Replace the creators (`dc:creator`); an empty list removes them
+    pub fn set_creators<S: AsRef<str>>(&mut self, creators: &[S]) -> XmpResult<()> {
+        set_text_items(
+            &mut self.meta,
+            ns::DC,
+            "creator",
+            ArrayType::Ordered,
+            creators,
+        )
+    }
+
+    /// Replace the keywords (`dc:subject`); an empty list removes them
+    pub fn set_subjects<S: AsRef<str>>(&mut self, subjects: &[S]) -> XmpResult<()> {
+        set_text_items(
+            &mut self.meta,
+            ns::DC,
+            "subject",
+            ArrayType::Unordered,
+            subjects,
+        )
+    }
+
+    /// Add a keyword to `dc:subject`, unless it is already there
+    pub fn add_subject(&mut self, subject: &str) -> XmpResult<()> {
+        let mut subjects = self.subjects();
+        if subjects.iter().any(|s| s == subject) {
+            return Ok(());
+        }
+        subjects.push(subject.to_string());
+        self.set_subjects(&subjects)
+    }
+
+    /// Set the MIME type of the resource (`dc:format`)
+    pub fn set_format(&mut self, format: &str) -> XmpResult<()> {
+        self.meta.set_property(ns::DC, "format", format.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dublin_core() {
+        let mut meta = XmpMeta::new();
+        let mut dc = meta.dublin_core_mut();
+        dc.set_title("x-default", "Sunset").unwrap();
+        dc.set_title("fr-FR", "Coucher de soleil").unwrap();
+        dc.set_rights("x-default", "© 2024 Jane Doe").unwrap();
+        dc.set_creators(&["Jane Doe", "John Roe"]).unwrap();
+        dc.set_subjects(&["beach"]).unwrap();
+        dc.add_subject("sunset").unwrap();
+        dc.add_subject("beach").unwrap();
+
+        let dc = meta.dublin_core();
+        assert_eq!(dc.title("fr").as_deref(), Some("Coucher de soleil"));
+        assert_eq!(dc.title("de").as_deref(), Some("Sunset"));
+        assert_eq!(dc.rights("en").as_deref(), Some("© 2024 Jane Doe"));
+        assert_eq!(dc.creators(), vec!["Jane Doe", "John Roe"]);
+        assert_eq!(dc.subjects(), vec!["beach", "sunset"]);
+        assert!(dc.description("en").is_none());
+        assert!(meta.serialize().unwrap().contains("<rdf:Bag>"));
+
+        meta.dublin_core_mut().set_creators::<&str>(&[]).unwrap();
+        assert!(!meta.has_property(ns::DC, "creator"));
+    }
+}
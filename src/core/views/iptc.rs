@@ -0,0 +1,760 @@
+//! IPTC Core and Extension view
+//!
+//! Structures of the IPTC Photo Metadata Standard as Rust types. Fields that
+//! are missing from the metadata are `None` (or empty) when reading, and are
+//! left out when writing, so a read followed by a write does not add
+//! anything. Writing updates existing structures in place, so fields the
+//! types do not model (e.g., `exif:GPSLatitude` in a location) are kept.
+
+use super::{set_text_items, text, text_items};
+use crate::core::error::XmpResult;
+use crate::core::metadata::XmpMeta;
+use crate::core::namespace::ns;
+use crate::core::node::ArrayType;
+use crate::types::value::XmpValue;
+use crate::utils::datetime::XmpDateTime;
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+
+/// Contact details of the creator (`Iptc4xmpCore:CreatorContactInfo`)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CreatorContactInfo {
+    /// Street address (`CiAdrExtadr`)
+    pub address: Option<String>,
+    /// City (`CiAdrCity`)
+    pub city: Option<String>,
+    /// State or province (`CiAdrRegion`)
+    pub region: Option<String>,
+    /// Postal code (`CiAdrPcode`)
+    pub postal_code: Option<String>,
+    /// Country (`CiAdrCtry`)
+    pub country: Option<String>,
+    /// Email address(es) (`CiEmailWork`)
+    pub email: Option<String>,
+    /// Phone number(s) (`CiTelWork`)
+    pub phone: Option<String>,
+    /// Web address(es) (`CiUrlWork`)
+    pub url: Option<String>,
+}
+
+impl CreatorContactInfo {
+    fn fields(&mut self) -> [(&'static str, &mut Option<String>); 8] {
+        [
+            ("Iptc4xmpCore:CiAdrExtadr", &mut self.address),
+            ("Iptc4xmpCore:CiAdrCity", &mut self.city),
+            ("Iptc4xmpCore:CiAdrRegion", &mut self.region),
+            ("Iptc4xmpCore:CiAdrPcode", &mut self.postal_code),
+            ("Iptc4xmpCore:CiAdrCtry", &mut self.country),
+            ("Iptc4xmpCore:CiEmailWork", &mut self.email),
+            ("Iptc4xmpCore:CiTelWork", &mut self.phone),
+            ("Iptc4xmpCore:CiUrlWork", &mut self.url),
+        ]
+    }
+}
+
+/// A location (`Iptc4xmpExt:LocationShown` and `LocationCreated` items)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Location {
+    /// Full name of the location, as (language, text) pairs (`LocationName`)
+    pub name: Vec<(String, String)>,
+    /// Identifiers of the location, e.g., gazetteer URIs (`LocationId`)
+    pub ids: Vec<String>,
+    /// Sublocation, e.g., a district or landmark (`Sublocation`)
+    pub sublocation: Option<String>,
+    /// City (`City`)
+    pub city: Option<String>,
+    /// Province or state (`ProvinceState`)
+    pub province_state: Option<String>,
+    /// Country name (`CountryName`)
+    pub country_name: Option<String>,
+    /// ISO 3166 country code (`CountryCode`)
+    pub country_code: Option<String>,
+    /// World region (`WorldRegion`)
+    pub world_region: Option<String>,
+}
+
+impl Location {
+    fn fields(&mut self) -> [(&'static str, &mut Option<String>); 6] {
+        [
+            ("Iptc4xmpExt:Sublocation", &mut self.sublocation),
+            ("Iptc4xmpExt:City", &mut self.city),
+            ("Iptc4xmpExt:ProvinceState", &mut self.province_state),
+            ("Iptc4xmpExt:CountryName", &mut self.country_name),
+            ("Iptc4xmpExt:CountryCode", &mut self.country_code),
+            ("Iptc4xmpExt:WorldRegion", &mut self.world_region),
+        ]
+    }
+}
+
+/// An artwork or object shown in the image (`Iptc4xmpExt:ArtworkOrObject` items)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArtworkOrObject {
+    /// Title, as (language, text) pairs (`AOTitle`)
+    pub title: Vec<(String, String)>,
+    /// Creators, in order (`AOCreator`)
+    pub creators: Vec<String>,
+    /// When it was created (`AODateCreated`)
+    pub date_created: Option<XmpDateTime>,
+    /// Organization or person holding the artwork (`AOSource`)
+    pub source: Option<String>,
+    /// Inventory number at the source (`AOSourceInvNo`)
+    pub source_inventory_number: Option<String>,
+    /// Copyright notice of the artwork (`AOCopyrightNotice`)
+    pub copyright_notice: Option<String>,
+}
+
+impl ArtworkOrObject {
+    fn fields(&mut self) -> [(&'static str, &mut Option<String>); 3] {
+        [
+            ("Iptc4xmpExt:AOSource", &mut self.source),
+            (
+                "Iptc4xmpExt:AOSourceInvNo",
+                &mut self.source_inventory_number,
+            ),
+            ("Iptc4xmpExt:AOCopyrightNotice", &mut self.copyright_notice),
+        ]
+    }
+}
+
+/// How the image was made (`Iptc4xmpExt:DigitalSourceType`)
+///
+/// The values are the terms of the IPTC Digital Source Type NewsCodes
+/// vocabulary; anything else is kept as [`DigitalSourceType::Other`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DigitalSourceType {
+    /// Original digital capture of a real-life scene (`digitalCapture`)
+    DigitalCapture,
+    /// Capture processed computationally, e.g., by a phone (`computationalCapture`)
+    ComputationalCapture,
+    /// Digitized from a negative (`negativeFilm`)
+    NegativeFilm,
+    /// Digitized from a transparency (`positiveFilm`)
+    PositiveFilm,
+    /// Digitized from a print (`print`)
+    Print,
+    /// Edited by a human using non-generative tools (`minorHumanEdits`)
+    MinorHumanEdits,
+    /// Composite of captured elements (`compositeCapture`)
+    CompositeCapture,
+    /// Enhanced with algorithms, e.g., noise reduction (`algorithmicallyEnhanced`)
+    AlgorithmicallyEnhanced,
+    /// Created by a human with digital tools (`digitalCreation`)
+    DigitalCreation,
+    /// Generated from data, e.g., a chart (`dataDrivenMedia`)
+    DataDrivenMedia,
+    /// Generated by a trained model, e.g., generative AI (`trainedAlgorithmicMedia`)
+    TrainedAlgorithmicMedia,
+    /// Composite including generated elements (`compositeWithTrainedAlgorithmicMedia`)
+    CompositeWithTrainedAlgorithmicMedia,
+    /// Generated by an algorithm without training data (`algorithmicMedia`)
+    AlgorithmicMedia,
+    /// Capture of a screen (`screenCapture`)
+    ScreenCapture,
+    /// Recording of a virtual event (`virtualRecording`)
+    VirtualRecording,
+    /// Composite of elements of any kind (`composite`)
+    Composite,
+    /// Composite including synthetic elements (`compositeSynthetic`)
+    CompositeSynthetic,
+    /// Any other value (the full URI)
+    Other(String),
+}
+
+impl DigitalSourceType {
+    /// Base URI of the IPTC Digital Source Type vocabulary
+    pub const VOCABULARY: &'static str = "http://cv.iptc.org/newscodes/digitalsourcetype/";
+
+    const TERMS: [(&'static str, DigitalSourceType); 17] = [
+        ("digitalCapture", DigitalSourceType::DigitalCapture),
+        (
+            "computationalCapture",
+            DigitalSourceType::ComputationalCapture,
+        ),
+        ("negativeFilm", DigitalSourceType::NegativeFilm),
+        ("positiveFilm", DigitalSourceType::PositiveFilm),
+        ("print", DigitalSourceType::Print),
+        ("minorHumanEdits", DigitalSourceType::MinorHumanEdits),
+        ("compositeCapture", DigitalSourceType::CompositeCapture),
+        (
+            "algorithmicallyEnhanced",
+            DigitalSourceType::AlgorithmicallyEnhanced,
+        ),
+        ("digitalCreation", DigitalSourceType::DigitalCreation),
+        ("dataDrivenMedia", DigitalSourceType::DataDrivenMedia),
+        (
+            "trainedAlgorithmicMedia",
+            DigitalSourceType::TrainedAlgorithmicMedia,
+        ),
+        (
+            "compositeWithTrainedAlgorithmicMedia",
+            DigitalSourceType::CompositeWithTrainedAlgorithmicMedia,
+        ),
+        ("algorithmicMedia", DigitalSourceType::AlgorithmicMedia),
+        ("screenCapture", DigitalSourceType::ScreenCapture),
+        ("virtualRecording", DigitalSourceType::VirtualRecording),
+        ("composite", DigitalSourceType::Composite),
+        ("compositeSynthetic", DigitalSourceType::CompositeSynthetic),
+    ];
+
+    /// Get the type for a URI
+    pub fn from_uri(uri: &str) -> Self {
+        uri.strip_prefix(Self::VOCABULARY)
+            .and_then(|term| Self::TERMS.iter().find(|(t, _)| *t == term))
+            .map(|(_, value)| value.clone())
+            .unwrap_or_else(|| DigitalSourceType::Other(uri.to_string()))
+    }
+
+    /// The URI of the type
+    pub fn uri(&self) -> String {
+        match self {
+            DigitalSourceType::Other(uri) => uri.clone(),
+            _ => {
+                let (term, _) = Self::TERMS
+                    .iter()
+                    .find(|(_, value)| value == self)
+                    .expect("every named type has a term");
+                format!("{}{}", Self::VOCABULARY, term)
+            }
+        }
+    }
+}
+
+/// Typed access to the IPTC Core (`Iptc4xmpCore:`) and IPTC Extension
+/// (`Iptc4xmpExt:`) schemas
+///
+/// Obtained with [`XmpMeta::iptc`] or [`XmpMeta::iptc_mut`].
+///
+/// # Example
+///
+/// ```rust
+/// use xmpkit::core::views::{DigitalSourceType, Location};
+/// use xmpkit::XmpMeta;
+///
+/// let mut meta = XmpMeta::new();
+/// let mut iptc = meta.iptc_mut();
+/// iptc.set_locations_shown(&[Location {
+///     city: Some("Paris".to_string()),
+///     country_code: Some("FR".to_string()),
+///     ..Default::default()
+/// }])
+/// .unwrap();
+/// iptc.set_digital_source_type(&DigitalSourceType::DigitalCapture)
+///     .unwrap();
+///
+/// assert_eq!(meta.iptc().locations_shown()[0].city.as_deref(), Some("Paris"));
+/// ```
+#[derive(Debug)]
+pub struct Iptc<M> {
+    meta: M,
+}
+
+impl XmpMeta {
+    /// Read-only view of the IPTC Core and Extension properties
+    pub fn iptc(&self) -> Iptc<&XmpMeta> {
+        Iptc { meta: self }
+    }
+
+    /// Writable view of the IPTC Core and Extension properties
+    pub fn iptc_mut(&mut self) -> Iptc<&mut XmpMeta> {
+        Iptc { meta: self }
+    }
+}
+
+impl<M: Deref<Target = XmpMeta>> Iptc<M> {
+    /// The creator's contact details (`Iptc4xmpCore:CreatorContactInfo`)
+    pub fn creator_contact_info(&self) -> Option<CreatorContactInfo> {
+        let path = "CreatorContactInfo";
+        if !self.meta.has_property(ns::IPTC_CORE, path) {
+            return None;
+        }
+        let mut info = CreatorContactInfo::default();
+        for (name, value) in info.fields() {
+            *value = self.field(ns::IPTC_CORE, path, name);
+        }
+        Some(info)
+    }
+
+    /// The sublocation shown (`Iptc4xmpCore:Location`)
+    ///
+    /// Superseded by [`locations_shown`](Self::locations_shown) in IPTC
+    /// Extension, but still widely used.
+    pub fn location(&self) -> Option<String> {
+        text(&self.meta, ns::IPTC_CORE, "Location")
+    }
+
+    /// The locations shown in the image (`Iptc4xmpExt:LocationShown`)
+    pub fn locations_shown(&self) -> Vec<Location> {
+        self.items("LocationShown", |iptc, path| iptc.read_location(path))
+    }
+
+    /// Where the image was created (`Iptc4xmpExt:LocationCreated`)
+    ///
+    /// The standard allows a single location, but it is stored as a `Bag`.
+    pub fn locations_created(&self) -> Vec<Location> {
+        self.items("LocationCreated", |iptc, path| iptc.read_location(path))
+    }
+
+    /// The artworks or objects in the image (`Iptc4xmpExt:ArtworkOrObject`)
+    pub fn artwork_or_object(&self) -> Vec<ArtworkOrObject> {
+        self.items("ArtworkOrObject", |iptc, path| {
+            let mut artwork = ArtworkOrObject {
+                title: iptc.lang_alt(&format!("{}/Iptc4xmpExt:AOTitle", path)),
+                creators: text_items(
+                    &iptc.meta,
+                    ns::IPTC_EXT,
+                    &format!("{}/Iptc4xmpExt:AOCreator", path),
+                ),
+                date_created: iptc
+                    .meta
+                    .get_struct_field(ns::IPTC_EXT, path, "Iptc4xmpExt:AODateCreated")
+                    .and_then(|value| value.as_date_time()),
+                ..Default::default()
+            };
+            for (name, value) in artwork.fields() {
+                *value = iptc.field(ns::IPTC_EXT, path, name);
+            }
+            artwork
+        })
+    }
+
+    /// The names of the persons shown (`Iptc4xmpExt:PersonInImage`)
+    pub fn persons_in_image(&self) -> Vec<String> {
+        text_items(&self.meta, ns::IPTC_EXT, "PersonInImage")
+    }
+
+    /// How the image was made (`Iptc4xmpExt:DigitalSourceType`)
+    pub fn digital_source_type(&self) -> Option<DigitalSourceType> {
+        text(&self.meta, ns::IPTC_EXT, "DigitalSourceType")
+            .map(|uri| DigitalSourceType::from_uri(&uri))
+    }
+
+    /// Read the structure items of an `Iptc4xmpExt` array
+    fn items<T>(&self, property: &str, read: impl Fn(&Self, &str) -> T) -> Vec<T> {
+        let size = self
+            .meta
+            .get_array_size(ns::IPTC_EXT, property)
+            .unwrap_or(0);
+        (1..=size)
+            .map(|index| format!("{}[{}]", property, index))
+            .filter(|path| self.is_struct(ns::IPTC_EXT, path))
+            .map(|path| read(self, &path))
+            .collect()
+    }
+
+    fn is_struct(&self, namespace: &str, path: &str) -> bool {
+        matches!(
+            self.meta.get_property(namespace, path),
+            Some(XmpValue::Structure(_))
+        )
+    }
+
+    fn read_location(&self, path: &str) -> Location {
+        let mut location = Location {
+            name: self.lang_alt(&format!("{}/Iptc4xmpExt:LocationName", path)),
+            ids: text_items(
+                &self.meta,
+                ns::IPTC_EXT,
+                &format!("{}/Iptc4xmpExt:LocationId", path),
+            ),
+            ..Default::default()
+        };
+        for (name, value) in location.fields() {
+            *value = self.field(ns::IPTC_EXT, path, name);
+        }
+        location
+    }
+
+    fn field(&self, namespace: &str, struct_path: &str, field_name: &str) -> Option<String> {
+        match self
+            .meta
+            .get_struct_field(namespace, struct_path, field_name)?
+        {
+            XmpValue::String(value) | XmpValue::Uri(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn lang_alt(&self, path: &str) -> Vec<(String, String)> {
+        match self.meta.get_property(ns::IPTC_EXT, path) {
+            Some(XmpValue::AltText(items)) => items,
+            Some(XmpValue::String(value)) => vec![("x-default".to_string(), value)],
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl<M: DerefMut<Target = XmpMeta>> Iptc<M> {
+    /// Replace the creator's contact details (`Iptc4xmpCore:CreatorContactInfo`)
+    pub fn set_creator_contact_info(&mut self, info: &CreatorContactInfo) -> XmpResult<()> {
+        let path = "CreatorContactInfo";
+        self.put_struct(ns::IPTC_CORE, path)?;
+        let mut info = info.clone();
+        for (name, value) in info.fields() {
+            self.put_field(ns::IPTC_CORE, path, name, value.take())?;
+        }
+        Ok(())
+    }
+
+    /// Set the sublocation shown (`Iptc4xmpCore:Location`)
+    pub fn set_location(&mut self, location: &str) -> XmpResult<()> {
+        self.meta
+            .set_property(ns::IPTC_CORE, "Location", location.into())
+    }
+
+    /// Replace the locations shown (`Iptc4xmpExt:LocationShown`); an empty
+    /// list removes them
+    pub fn set_locations_shown(&mut self, locations: &[Location]) -> XmpResult<()> {
+        self.put_items("LocationShown", locations, Self::write_location)
+    }
+
+    /// Replace where the image was created (`Iptc4xmpExt:LocationCreated`);
+    /// an empty list removes it
+    pub fn set_locations_created(&mut self, locations: &[Location]) -> XmpResult<()> {
+        self.put_items("LocationCreated", locations, Self::write_location)
+    }
+
+    /// Replace the artworks or objects (`Iptc4xmpExt:ArtworkOrObject`); an
+    /// empty list removes them
+    pub fn set_artwork_or_object(&mut self, artworks: &[ArtworkOrObject]) -> XmpResult<()> {
+        self.put_items("ArtworkOrObject", artworks, |iptc, path, artwork| {
+            let mut artwork = artwork.clone();
+            iptc.put_lang_alt(
+                &format!("{}/Iptc4xmpExt:AOTitle", path),
+                artwork.title.clone(),
+            )?;
+            set_text_items(
+                &mut iptc.meta,
+                ns::IPTC_EXT,
+                &format!("{}/Iptc4xmpExt:AOCreator", path),
+                ArrayType::Ordered,
+                &artwork.creators,
+            )?;
+            let date_field = "Iptc4xmpExt:AODateCreated";
+            match artwork.date_created.take() {
+                Some(date) => iptc.meta.set_struct_field(
+                    ns::IPTC_EXT,
+                    path,
+                    date_field,
+                    XmpValue::DateTime(date),
+                )?,
+                None => iptc
+                    .meta
+                    .delete_struct_field(ns::IPTC_EXT, path, date_field)?,
+            }
+            for (name, value) in artwork.fields() {
+                iptc.put_field(ns::IPTC_EXT, path, name, value.take())?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Replace the names of the persons shown (`Iptc4xmpExt:PersonInImage`);
+    /// an empty list removes them
+    pub fn set_persons_in_image<S: AsRef<str>>(&mut self, persons: &[S]) -> XmpResult<()> {
+        set_text_items(
+            &mut self.meta,
+            ns::IPTC_EXT,
+            "PersonInImage",
+            ArrayType::Unordered,
+            persons,
+        )
+    }
+
+    /// Set how the image was made (`Iptc4xmpExt:DigitalSourceType`)
+    pub fn set_digital_source_type(&mut self, source_type: &DigitalSourceType) -> XmpResult<()> {
+        self.meta
+            .set_property(ns::IPTC_EXT, "DigitalSourceType", source_type.uri().into())
+    }
+
+    /// Replace the items of an `Iptc4xmpExt` array, one structure per item
+    ///
+    /// The structures already in the array are updated in place, item by
+    /// item, and the ones left over are removed.
+    fn put_items<T>(
+        &mut self,
+        property: &str,
+        items: &[T],
+        write: impl Fn(&mut Self, &str, &T) -> XmpResult<()>,
+    ) -> XmpResult<()> {
+        if items.is_empty() {
+            return self.meta.delete_property(ns::IPTC_EXT, property);
+        }
+        match self.meta.get_array_size(ns::IPTC_EXT, property) {
+            Some(size) => {
+                // Items that are not structures are skipped when reading;
+                // drop them, so the structures line up with `items`
+                for index in (0..size).rev() {
+                    let path = format!("{}[{}]", property, index + 1);
+                    if !self.is_struct(ns::IPTC_EXT, &path) {
+                        self.meta.delete_array_item(ns::IPTC_EXT, property, index)?;
+                    }
+                }
+                let size = self
+                    .meta
+                    .get_array_size(ns::IPTC_EXT, property)
+                    .unwrap_or(0);
+                for index in (items.len()..size).rev() {
+                    self.meta.delete_array_item(ns::IPTC_EXT, property, index)?;
+                }
+            }
+            None => self.meta.set_property(
+                ns::IPTC_EXT,
+                property,
+                XmpValue::Array(ArrayType::Unordered, Vec::new()),
+            )?,
+        }
+        for (index, item) in items.iter().enumerate() {
+            let path = format!("{}[{}]", property, index + 1);
+            self.put_struct(ns::IPTC_EXT, &path)?;
+            write(self, &path, item)?;
+        }
+        Ok(())
+    }
+
+    fn write_location(&mut self, path: &str, location: &Location) -> XmpResult<()> {
+        let mut location = location.clone();
+        self.put_lang_alt(
+            &format!("{}/Iptc4xmpExt:LocationName", path),
+            location.name.clone(),
+        )?;
+        set_text_items(
+            &mut self.meta,
+            ns::IPTC_EXT,
+            &format!("{}/Iptc4xmpExt:LocationId", path),
+            ArrayType::Unordered,
+            &location.ids,
+        )?;
+        for (name, value) in location.fields() {
+            self.put_field(ns::IPTC_EXT, path, name, value.take())?;
+        }
+        Ok(())
+    }
+
+    /// Store an empty structure at `path`, unless there is a structure
+    /// already
+    fn put_struct(&mut self, namespace: &str, path: &str) -> XmpResult<()> {
+        if self.is_struct(namespace, path) {
+            return Ok(());
+        }
+        self.meta
+            .set_property(namespace, path, XmpValue::Structure(HashMap::new()))
+    }
+
+    fn put_field(
+        &mut self,
+        namespace: &str,
+        struct_path: &str,
+        field_name: &str,
+        value: Option<String>,
+    ) -> XmpResult<()> {
+        match value {
+            Some(value) => {
+                self.meta
+                    .set_struct_field(namespace, struct_path, field_name, value.into())
+            }
+            None => self
+                .meta
+                .delete_struct_field(namespace, struct_path, field_name),
+        }
+    }
+
+    fn put_lang_alt(&mut self, path: &str, items: Vec<(String, String)>) -> XmpResult<()> {
+        if items.is_empty() {
+            return self.meta.delete_property(ns::IPTC_EXT, path);
+        }
+        self.meta
+            .set_property(ns::IPTC_EXT, path, XmpValue::AltText(items))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IPTC: &str = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+<rdf:Description rdf:about=""
+    xmlns:Iptc4xmpCore="http://iptc.org/std/Iptc4xmpCore/1.0/xmlns/"
+    xmlns:Iptc4xmpExt="http://iptc.org/std/Iptc4xmpExt/2008-02-29/"
+    Iptc4xmpCore:Location="Old Town"
+    Iptc4xmpExt:DigitalSourceType="http://cv.iptc.org/newscodes/digitalsourcetype/trainedAlgorithmicMedia">
+  <Iptc4xmpCore:CreatorContactInfo rdf:parseType="Resource">
+    <Iptc4xmpCore:CiAdrCity>Berlin</Iptc4xmpCore:CiAdrCity>
+    <Iptc4xmpCore:CiEmailWork>jane@example.com</Iptc4xmpCore:CiEmailWork>
+  </Iptc4xmpCore:CreatorContactInfo>
+  <Iptc4xmpExt:LocationShown>
+    <rdf:Bag>
+      <rdf:li rdf:parseType="Resource">
+        <Iptc4xmpExt:City>Berlin</Iptc4xmpExt:City>
+        <Iptc4xmpExt:CountryCode>DE</Iptc4xmpExt:CountryCode>
+        <Iptc4xmpExt:LocationName>
+          <rdf:Alt>
+            <rdf:li xml:lang="x-default">Brandenburg Gate</rdf:li>
+            <rdf:li xml:lang="de">Brandenburger Tor</rdf:li>
+          </rdf:Alt>
+        </Iptc4xmpExt:LocationName>
+      </rdf:li>
+      <rdf:li rdf:parseType="Resource">
+        <Iptc4xmpExt:City>Potsdam</Iptc4xmpExt:City>
+      </rdf:li>
+    </rdf:Bag>
+  </Iptc4xmpExt:LocationShown>
+  <Iptc4xmpExt:ArtworkOrObject>
+    <rdf:Bag>
+      <rdf:li rdf:parseType="Resource">
+        <Iptc4xmpExt:AOTitle>
+          <rdf:Alt><rdf:li xml:lang="x-default">Quadriga</rdf:li></rdf:Alt>
+        </Iptc4xmpExt:AOTitle>
+        <Iptc4xmpExt:AOCreator>
+          <rdf:Seq><rdf:li>Johann Gottfried Schadow</rdf:li></rdf:Seq>
+        </Iptc4xmpExt:AOCreator>
+        <Iptc4xmpExt:AODateCreated>1793</Iptc4xmpExt:AODateCreated>
+      </rdf:li>
+    </rdf:Bag>
+  </Iptc4xmpExt:ArtworkOrObject>
+  <Iptc4xmpExt:PersonInImage>
+    <rdf:Bag><rdf:li>Jane Doe</rdf:li></rdf:Bag>
+  </Iptc4xmpExt:PersonInImage>
+</rdf:Description>
+</rdf:RDF>
+</x:xmpmeta>"#;
+
+    #[test]
+    fn test_read_iptc() {
+        let meta = XmpMeta::parse(IPTC).unwrap();
+        let iptc = meta.iptc();
+
+        let contact = iptc.creator_contact_info().unwrap();
+        assert_eq!(contact.city.as_deref(), Some("Berlin"));
+        assert_eq!(contact.email.as_deref(), Some("jane@example.com"));
+        assert!(contact.phone.is_none());
+        assert_eq!(iptc.location().as_deref(), Some("Old Town"));
+
+        let shown = iptc.locations_shown();
+        assert_eq!(shown.len(), 2);
+        assert_eq!(shown[0].country_code.as_deref(), Some("DE"));
+        assert_eq!(
+            shown[0].name[1],
+            ("de".to_string(), "Brandenburger Tor".to_string())
+        );
+        assert_eq!(
+            shown[1],
+            Location {
+                city: Some("Potsdam".to_string()),
+                ..Default::default()
+            }
+        );
+        assert!(iptc.locations_created().is_empty());
+
+        let artwork = &iptc.artwork_or_object()[0];
+        assert_eq!(artwork.creators, vec!["Johann Gottfried Schadow"]);
+        assert_eq!(artwork.date_created.as_ref().unwrap().year, 1793);
+        assert!(artwork.source.is_none());
+
+        assert_eq!(iptc.persons_in_image(), vec!["Jane Doe"]);
+        assert_eq!(
+            iptc.digital_source_type(),
+            Some(DigitalSourceType::TrainedAlgorithmicMedia)
+        );
+    }
+
+    #[test]
+    fn test_iptc_round_trip() {
+        let source = XmpMeta::parse(IPTC).unwrap();
+        let iptc = source.iptc();
+
+        let mut meta = XmpMeta::new();
+        let mut copy = meta.iptc_mut();
+        copy.set_creator_contact_info(&iptc.creator_contact_info().unwrap())
+            .unwrap();
+        copy.set_location(&iptc.location().unwrap()).unwrap();
+        copy.set_locations_shown(&iptc.locations_shown()).unwrap();
+        copy.set_artwork_or_object(&iptc.artwork_or_object())
+            .unwrap();
+        copy.set_persons_in_image(&iptc.persons_in_image()).unwrap();
+        copy.set_digital_source_type(&iptc.digital_source_type().unwrap())
+            .unwrap();
+
+        assert!(meta.semantically_eq(&source));
+        let reparsed = XmpMeta::parse(&meta.serialize().unwrap()).unwrap();
+        assert!(reparsed.semantically_eq(&source));
+
+        let other = DigitalSourceType::from_uri("urn:example:scan");
+        assert_eq!(
+            other,
+            DigitalSourceType::Other("urn:example:scan".to_string())
+        );
+        assert_eq!(other.uri(), "urn:example:scan");
+    }
+    #[test]
+    fn test_iptc_update_keeps_unmodelled_fields() {
+        let xml = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+<rdf:Description rdf:about=""
+    xmlns:Iptc4xmpExt="http://iptc.org/std/Iptc4xmpExt/2008-02-29/"
+    xmlns:exif="http://ns.adobe.com/exif/1.0/">
+  <Iptc4xmpExt:LocationShown>
+    <rdf:Bag>
+      <rdf:li rdf:parseType="Resource">
+        <Iptc4xmpExt:City>Berlin</Iptc4xmpExt:City>
+        <Iptc4xmpExt:CountryCode>DE</Iptc4xmpExt:CountryCode>
+        <exif:GPSLatitude>52,30.9N</exif:GPSLatitude>
+      </rdf:li>
+      <rdf:li rdf:parseType="Resource">
+        <Iptc4xmpExt:City>Potsdam</Iptc4xmpExt:City>
+      </rdf:li>
+    </rdf:Bag>
+  </Iptc4xmpExt:LocationShown>
+  <Iptc4xmpExt:ArtworkOrObject>
+    <rdf:Bag>
+      <rdf:li rdf:parseType="Resource">
+        <Iptc4xmpExt:AOSource>Museum</Iptc4xmpExt:AOSource>
+        <Iptc4xmpExt:AOContentDescription>
+          <rdf:Alt><rdf:li xml:lang="x-default">A horse</rdf:li></rdf:Alt>
+        </Iptc4xmpExt:AOContentDescription>
+        <Iptc4xmpExt:AOSourceInvURL>https://example.com/1</Iptc4xmpExt:AOSourceInvURL>
+      </rdf:li>
+    </rdf:Bag>
+  </Iptc4xmpExt:ArtworkOrObject>
+</rdf:Description>
+</rdf:RDF>
+</x:xmpmeta>"#;
+        let mut meta = XmpMeta::parse(xml).unwrap();
+        let source = meta.clone();
+
+        // Writing back what was read changes nothing
+        let mut iptc = meta.iptc_mut();
+        let mut locations = iptc.locations_shown();
+        let artworks = iptc.artwork_or_object();
+        iptc.set_locations_shown(&locations).unwrap();
+        iptc.set_artwork_or_object(&artworks).unwrap();
+        assert!(meta.semantically_eq(&source));
+
+        let mut iptc = meta.iptc_mut();
+        locations[0].city = Some("Berlin-Mitte".to_string());
+        locations[0].country_code = None;
+        iptc.set_locations_shown(&locations[..1]).unwrap();
+
+        let location = "LocationShown[1]";
+        assert_eq!(meta.get_array_size(ns::IPTC_EXT, "LocationShown"), Some(1));
+        assert_eq!(
+            meta.iptc().locations_shown(),
+            vec![Location {
+                city: Some("Berlin-Mitte".to_string()),
+                ..Default::default()
+            }]
+        );
+        assert_eq!(
+            meta.get_struct_field(ns::IPTC_EXT, location, "exif:GPSLatitude"),
+            Some(XmpValue::String("52,30.9N".to_string()))
+        );
+        let artwork = "ArtworkOrObject[1]";
+        assert!(meta.has_property(
+            ns::IPTC_EXT,
+            &format!("{}/Iptc4xmpExt:AOContentDescription", artwork)
+        ));
+        assert!(meta
+            .get_struct_field(ns::IPTC_EXT, artwork, "Iptc4xmpExt:AOSourceInvURL")
+            .is_some());
+    }
+}
@@ -0,0 +1,460 @@
+//! XMP Media Management view
+//!
+//! Helpers to keep the document lineage (`xmpMM:DocumentID`, `InstanceID`,
+//! `OriginalDocumentID`, `DerivedFrom` and `History`) correct when a
+//! document is saved or exported.
+
+use super::text;
+use crate::core::error::XmpResult;
+use crate::core::metadata::XmpMeta;
+use crate::core::namespace::ns;
+use crate::types::value::XmpValue;
+use crate::utils::datetime::XmpDateTime;
+use crate::utils::uuid::new_uuid;
+use std::ops::{Deref, DerefMut};
+
+/// Generate a new document ID (`xmp.did:<uuid>`)
+pub fn new_document_id() -> String {
+    format!("xmp.did:{}", new_uuid())
+}
+
+/// Generate a new instance ID (`xmp.iid:<uuid>`)
+pub fn new_instance_id() -> String {
+    format!("xmp.iid:{}", new_uuid())
+}
+
+/// An event of the `xmpMM:History` sequence (`stEvt:` fields)
+///
+/// # Example
+///
+/// ```rust
+/// use xmpkit::core::views::HistoryEvent;
+///
+/// let event = HistoryEvent::new("converted").software_agent("MyApp 1.0");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HistoryEvent {
+    /// What happened (e.g., `created`, `saved`, `converted`, `derived`)
+    pub action: String,
+    /// When it happened
+    pub when: Option<XmpDateTime>,
+    /// The application that performed the action
+    pub software_agent: Option<String>,
+    /// The parts of the document that changed (e.g., `/metadata`)
+    pub changed: Option<String>,
+    /// The instance ID of the document after the action
+    pub instance_id: Option<String>,
+}
+
+impl HistoryEvent {
+    /// Create an event for the given action
+    pub fn new(action: impl Into<String>) -> Self {
+        Self {
+            action: action.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set when the action happened.
+    pub fn when(mut self, when: XmpDateTime) -> Self {
+        self.when = Some(when);
+        self
+    }
+
+    /// Set the application that performed the action.
+    pub fn software_agent(mut self, agent: impl Into<String>) -> Self {
+        self.software_agent = Some(agent.into());
+        self
+    }
+
+    /// Set the parts of the document that changed.
+    pub fn changed(mut self, changed: impl Into<String>) -> Self {
+        self.changed = Some(changed.into());
+        self
+    }
+
+    /// Set the instance ID of the document after the action.
+    pub fn instance_id(mut self, instance_id: impl Into<String>) -> Self {
+        self.instance_id = Some(instance_id.into());
+        self
+    }
+}
+
+/// A reference to another document (`stRef:` fields), as used by
+/// `xmpMM:DerivedFrom` and `xmpMM:Ingredients`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResourceRef {
+    /// `stRef:documentID`
+    pub document_id: Option<String>,
+    /// `stRef:instanceID`
+    pub instance_id: Option<String>,
+    /// `stRef:originalDocumentID`
+    pub original_document_id: Option<String>,
+    /// `stRef:filePath`
+    pub file_path: Option<String>,
+    /// `stRef:renditionClass`
+    pub rendition_class: Option<String>,
+    /// `stRef:versionID`
+    pub version_id: Option<String>,
+}
+
+impl ResourceRef {
+    fn fields(&self) -> [(&'static str, &Option<String>); 6] {
+        [
+            ("stRef:documentID", &self.document_id),
+            ("stRef:instanceID", &self.instance_id),
+            ("stRef:originalDocumentID", &self.original_document_id),
+            ("stRef:filePath", &self.file_path),
+            ("stRef:renditionClass", &self.rendition_class),
+            ("stRef:versionID", &self.version_id),
+        ]
+    }
+}
+
+/// Typed access to the XMP Media Management schema (`xmpMM:`)
+///
+/// Obtained with [`XmpMeta::media_management`] or
+/// [`XmpMeta::media_management_mut`].
+///
+/// # Example
+///
+/// ```rust
+/// use xmpkit::XmpMeta;
+///
+/// let mut original = XmpMeta::new();
+/// original.media_management_mut().init_ids().unwrap();
+///
+/// // Export a derived copy
+/// let mut export = original.clone();
+/// export.media_management_mut().derive_from(&original).unwrap();
+///
+/// let mm = export.media_management();
+/// assert_ne!(mm.document_id(), original.media_management().document_id());
+/// assert_eq!(mm.derived_from().unwrap().document_id, original.media_management().document_id());
+/// ```
+#[derive(Debug)]
+pub struct MediaManagement<M> {
+    meta: M,
+}
+
+impl XmpMeta {
+    /// Read-only view of the XMP Media Management properties
+    pub fn media_management(&self) -> MediaManagement<&XmpMeta> {
+        MediaManagement { meta: self }
+    }
+
+    /// Writable view of the XMP Media Management properties
+    pub fn media_management_mut(&mut self) -> MediaManagement<&mut XmpMeta> {
+        MediaManagement { meta: self }
+    }
+}
+
+impl<M: Deref<Target = XmpMeta>> MediaManagement<M> {
+    /// The document ID (`xmpMM:DocumentID`), shared by all versions of a document
+    pub fn document_id(&self) -> Option<String> {
+        text(&self.meta, ns::XMP_MM, "DocumentID")
+    }
+
+    /// The instance ID (`xmpMM:InstanceID`), which changes on every save
+    pub fn instance_id(&self) -> Option<String> {
+        text(&self.meta, ns::XMP_MM, "InstanceID")
+    }
+
+    /// The ID of the document this one was originally created from
+    /// (`xmpMM:OriginalDocumentID`)
+    pub fn original_document_id(&self) -> Option<String> {
+        text(&self.meta, ns::XMP_MM, "OriginalDocumentID")
+    }
+
+    /// The events of `xmpMM:History`, oldest first
+    ///
+    /// Items without an `stEvt:action` are skipped.
+    pub fn history(&self) -> Vec<HistoryEvent> {
+        (1..=self.array_size("History"))
+            .filter_map(|index| {
+                let path = format!("History[{}]", index);
+                Some(HistoryEvent {
+                    action: self.field(&path, "stEvt:action")?,
+                    when: self
+                        .meta
+                        .get_struct_field(ns::XMP_MM, &path, "stEvt:when")
+                        .and_then(|value| value.as_date_time()),
+                    software_agent: self.field(&path, "stEvt:softwareAgent"),
+                    changed: self.field(&path, "stEvt:changed"),
+                    instance_id: self.field(&path, "stEvt:instanceID"),
+                })
+            })
+            .collect()
+    }
+
+    /// The document this one was derived from (`xmpMM:DerivedFrom`)
+    pub fn derived_from(&self) -> Option<ResourceRef> {
+        self.resource_ref("DerivedFrom")
+    }
+
+    /// The documents included in this one (`xmpMM:Ingredients`)
+    pub fn ingredients(&self) -> Vec<ResourceRef> {
+        (1..=self.array_size("Ingredients"))
+            .filter_map(|index| self.resource_ref(&format!("Ingredients[{}]", index)))
+            .collect()
+    }
+
+    /// The metadata of the ingredients (`xmpMM:Pantry`)
+    ///
+    /// Each entry holds the properties of one ingredient, keyed by its
+    /// `xmpMM:InstanceID`, as a separate metadata object.
+    pub fn pantry(&self) -> Vec<XmpMeta> {
+        (1..=self.array_size("Pantry"))
+            .filter_map(|index| {
+                self.meta
+                    .structure_as_meta(ns::XMP_MM, &format!("Pantry[{}]", index))
+            })
+            .collect()
+    }
+
+    fn array_size(&self, property: &str) -> usize {
+        self.meta.get_array_size(ns::XMP_MM, property).unwrap_or(0)
+    }
+
+    fn field(&self, struct_path: &str, field_name: &str) -> Option<String> {
+        match self
+            .meta
+            .get_struct_field(ns::XMP_MM, struct_path, field_name)?
+        {
+            XmpValue::String(value) | XmpValue::Uri(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn resource_ref(&self, path: &str) -> Option<ResourceRef> {
+        if !self.meta.has_property(ns::XMP_MM, path) {
+            return None;
+        }
+        Some(ResourceRef {
+            document_id: self.field(path, "stRef:documentID"),
+            instance_id: self.field(path, "stRef:instanceID"),
+            original_document_id: self.field(path, "stRef:originalDocumentID"),
+            file_path: self.field(path, "stRef:filePath"),
+            rendition_class: self.field(path, "stRef:renditionClass"),
+            version_id: self.field(path, "stRef:versionID"),
+        })
+    }
+}
+
+impl<M: DerefMut<Target = XmpMeta>> MediaManagement<M> {
+    /// Set the document ID (`xmpMM:DocumentID`)
+    pub fn set_document_id(&mut self, id: &str) -> XmpResult<()> {
+        self.meta.set_property(ns::XMP_MM, "DocumentID", id.into())
+    }
+
+    /// Set the instance ID (`xmpMM:InstanceID`)
+    pub fn set_instance_id(&mut self, id: &str) -> XmpResult<()> {
+        self.meta.set_property(ns::XMP_MM, "InstanceID", id.into())
+    }
+
+    /// Set the original document ID (`xmpMM:OriginalDocumentID`)
+    pub fn set_original_document_id(&mut self, id: &str) -> XmpResult<()> {
+        self.meta
+            .set_property(ns::XMP_MM, "OriginalDocumentID", id.into())
+    }
+
+    /// Fill in the missing IDs of a new document
+    ///
+    /// Generates `xmpMM:DocumentID` and `xmpMM:InstanceID` if they are
+    /// missing, and sets `xmpMM:OriginalDocumentID` to the document ID if it
+    /// is missing.
+    pub fn init_ids(&mut self) -> XmpResult<()> {
+        let document_id = match self.document_id() {
+            Some(id) => id,
+            None => {
+                let id = new_document_id();
+                self.set_document_id(&id)?;
+                id
+            }
+        };
+        if self.instance_id().is_none() {
+            self.set_instance_id(&new_instance_id())?;
+        }
+        if self.original_document_id().is_none() {
+            self.set_original_document_id(&document_id)?;
+        }
+        Ok(())
+    }
+
+    /// Give the document a new instance ID and return it
+    pub fn renew_instance_id(&mut self) -> XmpResult<String> {
+        let id = new_instance_id();
+        self.set_instance_id(&id)?;
+        Ok(id)
+    }
+
+    /// Append an event to `xmpMM:History`
+    pub fn append_history(&mut self, event: &HistoryEvent) -> XmpResult<()> {
+        let path = format!("History[{}]", self.array_size("History") + 1);
+        let fields = [
+            ("stEvt:action", Some(XmpValue::String(event.action.clone()))),
+            (
+                "stEvt:instanceID",
+                event.instance_id.clone().map(XmpValue::String),
+            ),
+            ("stEvt:when", event.when.clone().map(XmpValue::DateTime)),
+            (
+                "stEvt:softwareAgent",
+                event.software_agent.clone().map(XmpValue::String),
+            ),
+            ("stEvt:changed", event.changed.clone().map(XmpValue::String)),
+        ];
+        for (name, value) in fields {
+            if let Some(value) = value {
+                self.meta.set_struct_field(ns::XMP_MM, &path, name, value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Record a save: give the document a new instance ID and append a
+    /// `saved` event to `xmpMM:History`
+    ///
+    /// The event's software agent is `xmp:CreatorTool`, if set. On Wasm the
+    /// event has no time, as there is no system clock.
+    pub fn record_save(&mut self) -> XmpResult<()> {
+        let instance_id = self.renew_instance_id()?;
+        let mut event = HistoryEvent::new("saved").instance_id(instance_id);
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            event.when = Some(XmpDateTime::now());
+        }
+        event.software_agent = text(&self.meta, ns::XMP, "CreatorTool");
+        self.append_history(&event)
+    }
+
+    /// Replace `xmpMM:DerivedFrom`
+    pub fn set_derived_from(&mut self, reference: &ResourceRef) -> XmpResult<()> {
+        self.meta.delete_property(ns::XMP_MM, "DerivedFrom")?;
+        for (name, value) in reference.fields() {
+            if let Some(value) = value {
+                self.meta.set_struct_field(
+                    ns::XMP_MM,
+                    "DerivedFrom",
+                    name,
+                    value.as_str().into(),
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Record that this document was derived from `parent`
+    ///
+    /// Points `xmpMM:DerivedFrom` at the parent's IDs, gives this document
+    /// new document and instance IDs, and keeps the parent's original
+    /// document ID (or its document ID) as `xmpMM:OriginalDocumentID`.
+    pub fn derive_from(&mut self, parent: &XmpMeta) -> XmpResult<()> {
+        let parent = parent.media_management();
+        let original_document_id = parent.original_document_id().or(parent.document_id());
+        self.set_derived_from(&ResourceRef {
+            document_id: parent.document_id(),
+            instance_id: parent.instance_id(),
+            original_document_id: original_document_id.clone(),
+            ..Default::default()
+        })?;
+        self.set_document_id(&new_document_id())?;
+        self.set_instance_id(&new_instance_id())?;
+        match original_document_id {
+            Some(id) => self.set_original_document_id(&id),
+            None => self.meta.delete_property(ns::XMP_MM, "OriginalDocumentID"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_and_history() {
+        let mut meta = XmpMeta::new();
+        let mut mm = meta.media_management_mut();
+        mm.init_ids().unwrap();
+        let document_id = mm.document_id().unwrap();
+        assert!(document_id.starts_with("xmp.did:"));
+        assert!(mm.instance_id().unwrap().starts_with("xmp.iid:"));
+        assert_eq!(mm.original_document_id(), Some(document_id.clone()));
+
+        let created = HistoryEvent::new("created")
+            .when(XmpDateTime::parse("2024-03-01T10:00:00Z").unwrap())
+            .software_agent("MyApp 1.0")
+            .instance_id(mm.instance_id().unwrap());
+        mm.append_history(&created).unwrap();
+        let before = mm.instance_id();
+        mm.record_save().unwrap();
+        assert_ne!(mm.instance_id(), before);
+
+        let history = meta.media_management().history();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0], created);
+        assert_eq!(history[1].action, "saved");
+        assert_eq!(
+            history[1].instance_id,
+            meta.media_management().instance_id()
+        );
+        // IDs are kept by init_ids
+        meta.media_management_mut().init_ids().unwrap();
+        assert_eq!(meta.media_management().document_id(), Some(document_id));
+    }
+
+    #[test]
+    fn test_derive_and_ingredients() {
+        let mut parent = XmpMeta::new();
+        parent.media_management_mut().init_ids().unwrap();
+        let mut child = parent.clone();
+        child.media_management_mut().derive_from(&parent).unwrap();
+
+        let (parent_mm, child_mm) = (parent.media_management(), child.media_management());
+        let derived_from = child_mm.derived_from().unwrap();
+        assert_eq!(derived_from.document_id, parent_mm.document_id());
+        assert_eq!(derived_from.instance_id, parent_mm.instance_id());
+        assert_ne!(child_mm.document_id(), parent_mm.document_id());
+        assert_eq!(child_mm.original_document_id(), parent_mm.document_id());
+
+        let xmp = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+<rdf:Description rdf:about=""
+    xmlns:xmpMM="http://ns.adobe.com/xap/1.0/mm/"
+    xmlns:stRef="http://ns.adobe.com/xap/1.0/sType/ResourceRef#"
+    xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <xmpMM:Ingredients>
+    <rdf:Bag>
+      <rdf:li rdf:parseType="Resource">
+        <stRef:instanceID>xmp.iid:1</stRef:instanceID>
+        <stRef:filePath>logo.ai</stRef:filePath>
+      </rdf:li>
+    </rdf:Bag>
+  </xmpMM:Ingredients>
+  <xmpMM:Pantry>
+    <rdf:Bag>
+      <rdf:li rdf:parseType="Resource">
+        <xmpMM:InstanceID>xmp.iid:1</xmpMM:InstanceID>
+        <dc:format>application/postscript</dc:format>
+      </rdf:li>
+    </rdf:Bag>
+  </xmpMM:Pantry>
+</rdf:Description>
+</rdf:RDF>
+</x:xmpmeta>"#;
+        let meta = XmpMeta::parse(xmp).unwrap();
+        let ingredients = meta.media_management().ingredients();
+        assert_eq!(ingredients.len(), 1);
+        assert_eq!(ingredients[0].file_path.as_deref(), Some("logo.ai"));
+        let pantry = meta.media_management().pantry();
+        assert_eq!(pantry.len(), 1);
+        assert_eq!(
+            pantry[0].media_management().instance_id().as_deref(),
+            Some("xmp.iid:1")
+        );
+        assert_eq!(
+            pantry[0].dublin_core().format().as_deref(),
+            Some("application/postscript")
+        );
+    }
+}
@@ -0,0 +1,110 @@
+//! Typed views of common schemas
+//!
+//! The views wrap an [`XmpMeta`] and expose the properties of one schema
+//! with Rust types, so callers don't have to deal with namespaces, paths and
+//! [`XmpValue`] matching:
+//!
+//! ```rust
+//! use xmpkit::XmpMeta;
+//!
+//! let mut meta = XmpMeta::new();
+//! meta.dublin_core_mut().set_title("x-default", "Sunset").unwrap();
+//! meta.xmp_basic_mut().set_rating(4).unwrap();
+//!
+//! assert_eq!(meta.dublin_core().title("en").as_deref(), Some("Sunset"));
+//! assert_eq!(meta.xmp_basic().rating(), Some(4));
+//! ```
+//!
+//! Read-only views are obtained with `&XmpMeta` (e.g., [`XmpMeta::dublin_core`])
+//! and writable ones with `&mut XmpMeta` (e.g., [`XmpMeta::dublin_core_mut`]).
+
+mod dublin_core;
+mod iptc;
+mod media_management;
+mod rights;
+mod xmp_basic;
+
+pub use dublin_core::DublinCore;
+pub use iptc::{ArtworkOrObject, CreatorContactInfo, DigitalSourceType, Iptc, Location};
+pub use media_management::{
+    new_document_id, new_instance_id, HistoryEvent, MediaManagement, ResourceRef,
+};
+pub use rights::XmpRights;
+pub use xmp_basic::XmpBasic;
+
+use crate::core::error::XmpResult;
+use crate::core::metadata::XmpMeta;
+use crate::core::node::ArrayType;
+use crate::types::value::XmpValue;
+
+/// Split a language tag into the (generic, specific) pair used by the
+/// localized text APIs
+fn split_lang(lang: &str) -> (&str, &str) {
+    if lang.eq_ignore_ascii_case("x-default") {
+        return ("", "x-default");
+    }
+    (lang.split('-').next().unwrap_or(lang), lang)
+}
+
+/// Get the text of a language alternative in the given language
+///
+/// Falls back to the generic language, then to `x-default`.
+fn localized_text(meta: &XmpMeta, namespace: &str, property: &str, lang: &str) -> Option<String> {
+    let (generic, specific) = split_lang(lang);
+    meta.get_localized_text(namespace, property, generic, specific)
+        .map(|(value, _)| value)
+}
+
+/// Set the text of a language alternative in the given language
+fn set_localized_text(
+    meta: &mut XmpMeta,
+    namespace: &str,
+    property: &str,
+    lang: &str,
+    text: &str,
+) -> XmpResult<()> {
+    let (generic, specific) = split_lang(lang);
+    meta.set_localized_text(namespace, property, generic, specific, text)
+}
+
+/// Get the text items of an array property
+///
+/// A simple value is returned as a single item.
+fn text_items(meta: &XmpMeta, namespace: &str, property: &str) -> Vec<String> {
+    match meta.get_property(namespace, property) {
+        Some(XmpValue::Array(_, items)) => items
+            .iter()
+            .filter_map(|item| item.as_str().map(String::from))
+            .collect(),
+        Some(XmpValue::String(value)) => vec![value],
+        _ => Vec::new(),
+    }
+}
+
+/// Replace an array property with the given text items
+///
+/// An empty list deletes the property.
+fn set_text_items<S: AsRef<str>>(
+    meta: &mut XmpMeta,
+    namespace: &str,
+    property: &str,
+    array_type: ArrayType,
+    items: &[S],
+) -> XmpResult<()> {
+    if items.is_empty() {
+        return meta.delete_property(namespace, property);
+    }
+    let items = items
+        .iter()
+        .map(|item| XmpValue::String(item.as_ref().to_string()))
+        .collect();
+    meta.set_property(namespace, property, XmpValue::Array(array_type, items))
+}
+
+/// Get a simple text (or URI) property
+fn text(meta: &XmpMeta, namespace: &str, property: &str) -> Option<String> {
+    match meta.get_property(namespace, property)? {
+        XmpValue::String(value) | XmpValue::Uri(value) => Some(value),
+        _ => None,
+    }
+}
@@ -0,0 +1,116 @@
+//! XMP Rights Management view
+
+use super::{localized_text, set_localized_text, set_text_items, text, text_items};
+use crate::core::error::XmpResult;
+use crate::core::metadata::XmpMeta;
+use crate::core::namespace::ns;
+use crate::core::node::ArrayType;
+use crate::types::value::XmpValue;
+use std::ops::{Deref, DerefMut};
+
+/// Typed access to the XMP Rights Management schema (`xmpRights:`)
+///
+/// Obtained with [`XmpMeta::rights`] or [`XmpMeta::rights_mut`]. The
+/// copyright notice itself is `dc:rights`, see
+/// [`DublinCore::rights`](super::DublinCore::rights).
+#[derive(Debug)]
+pub struct XmpRights<M> {
+    meta: M,
+}
+
+impl XmpMeta {
+    /// Read-only view of the XMP Rights Management properties
+    pub fn rights(&self) -> XmpRights<&XmpMeta> {
+        XmpRights { meta: self }
+    }
+
+    /// Writable view of the XMP Rights Management properties
+    pub fn rights_mut(&mut self) -> XmpRights<&mut XmpMeta> {
+        XmpRights { meta: self }
+    }
+}
+
+impl<M: Deref<Target = XmpMeta>> XmpRights<M> {
+    /// URL of a web page describing the rights (`xmpRights:WebStatement`)
+    pub fn web_statement(&self) -> Option<String> {
+        text(&self.meta, ns::XMP_RIGHTS, "WebStatement")
+    }
+
+    /// Whether the resource is rights-managed (`xmpRights:Marked`)
+    ///
+    /// `Some(false)` means public domain; `None` means unknown.
+    pub fn marked(&self) -> Option<bool> {
+        self.meta.get_property(ns::XMP_RIGHTS, "Marked")?.as_bool()
+    }
+
+    /// The legal owners (`xmpRights:Owner`)
+    pub fn owners(&self) -> Vec<String> {
+        text_items(&self.meta, ns::XMP_RIGHTS, "Owner")
+    }
+
+    /// The usage terms (`xmpRights:UsageTerms`) in the given language
+    pub fn usage_terms(&self, lang: &str) -> Option<String> {
+        localized_text(&self.meta, ns::XMP_RIGHTS, "UsageTerms", lang)
+    }
+}
+
+impl<M: DerefMut<Target = XmpMeta>> XmpRights<M> {
+    /// Set the URL of a web page describing the rights (`xmpRights:WebStatement`)
+    pub fn set_web_statement(&mut self, url: &str) -> XmpResult<()> {
+        self.meta
+            .set_property(ns::XMP_RIGHTS, "WebStatement", url.into())
+    }
+
+    /// Set whether the resource is rights-managed (`xmpRights:Marked`)
+    pub fn set_marked(&mut self, marked: bool) -> XmpResult<()> {
+        self.meta
+            .set_property(ns::XMP_RIGHTS, "Marked", XmpValue::Boolean(marked))
+    }
+
+    /// Replace the legal owners (`xmpRights:Owner`); an empty list removes them
+    pub fn set_owners<S: AsRef<str>>(&mut self, owners: &[S]) -> XmpResult<()> {
+        set_text_items(
+            &mut self.meta,
+            ns::XMP_RIGHTS,
+            "Owner",
+            ArrayType::Unordered,
+            owners,
+        )
+    }
+
+    /// Set the usage terms (`xmpRights:UsageTerms`) in the given language
+    pub fn set_usage_terms(&mut self, lang: &str, text: &str) -> XmpResult<()> {
+        set_localized_text(&mut self.meta, ns::XMP_RIGHTS, "UsageTerms", lang, text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rights() {
+        let mut meta = XmpMeta::new();
+        let mut rights = meta.rights_mut();
+        rights
+            .set_web_statement("https://example.com/license")
+            .unwrap();
+        rights.set_marked(true).unwrap();
+        rights.set_owners(&["Jane Doe"]).unwrap();
+        rights
+            .set_usage_terms("x-default", "Editorial use only")
+            .unwrap();
+
+        let rights = meta.rights();
+        assert_eq!(
+            rights.web_statement().as_deref(),
+            Some("https://example.com/license")
+        );
+        assert_eq!(rights.marked(), Some(true));
+        assert_eq!(rights.owners(), vec!["Jane Doe"]);
+        assert_eq!(
+            rights.usage_terms("en-GB").as_deref(),
+            Some("Editorial use only")
+        );
+    }
+}
@@ -0,0 +1,134 @@
+//! XMP Basic view
+
+use super::text;
+use crate::core::error::{XmpError, XmpResult};
+use crate::core::metadata::XmpMeta;
+use crate::core::namespace::ns;
+use crate::utils::datetime::XmpDateTime;
+use std::ops::{Deref, DerefMut};
+
+/// Typed access to the XMP Basic schema (`xmp:`)
+///
+/// Obtained with [`XmpMeta::xmp_basic`] or [`XmpMeta::xmp_basic_mut`].
+#[derive(Debug)]
+pub struct XmpBasic<M> {
+    meta: M,
+}
+
+impl XmpMeta {
+    /// Read-only view of the XMP Basic properties
+    pub fn xmp_basic(&self) -> XmpBasic<&XmpMeta> {
+        XmpBasic { meta: self }
+    }
+
+    /// Writable view of the XMP Basic properties
+    pub fn xmp_basic_mut(&mut self) -> XmpBasic<&mut XmpMeta> {
+        XmpBasic { meta: self }
+    }
+}
+
+impl<M: Deref<Target = XmpMeta>> XmpBasic<M> {
+    /// The user rating (`xmp:Rating`)
+    ///
+    /// Ratings go from 1 to 5 stars, with 0 meaning unrated and -1 rejected.
+    /// Values outside this range, or with a fractional part, give `None`.
+    pub fn rating(&self) -> Option<i8> {
+        let rating = self.meta.get_property(ns::XMP, "Rating")?.as_f64()?;
+        if rating.fract() == 0.0 && (-1.0..=5.0).contains(&rating) {
+            Some(rating as i8)
+        } else {
+            None
+        }
+    }
+
+    /// When the resource was created (`xmp:CreateDate`)
+    pub fn create_date(&self) -> Option<XmpDateTime> {
+        self.meta.get_date_time(ns::XMP, "CreateDate")
+    }
+
+    /// When the resource was last modified (`xmp:ModifyDate`)
+    pub fn modify_date(&self) -> Option<XmpDateTime> {
+        self.meta.get_date_time(ns::XMP, "ModifyDate")
+    }
+
+    /// When the metadata was last changed (`xmp:MetadataDate`)
+    pub fn metadata_date(&self) -> Option<XmpDateTime> {
+        self.meta.get_date_time(ns::XMP, "MetadataDate")
+    }
+
+    /// The application that created the resource (`xmp:CreatorTool`)
+    pub fn creator_tool(&self) -> Option<String> {
+        text(&self.meta, ns::XMP, "CreatorTool")
+    }
+
+    /// The user label (`xmp:Label`)
+    pub fn label(&self) -> Option<String> {
+        text(&self.meta, ns::XMP, "Label")
+    }
+}
+
+impl<M: DerefMut<Target = XmpMeta>> XmpBasic<M> {
+    /// Set the user rating (`xmp:Rating`), from -1 (rejected) to 5
+    pub fn set_rating(&mut self, rating: i8) -> XmpResult<()> {
+        if !(-1..=5).contains(&rating) {
+            return Err(XmpError::BadValue(format!(
+                "Rating must be between -1 and 5, got {}",
+                rating
+            )));
+        }
+        self.meta
+            .set_property(ns::XMP, "Rating", rating.to_string().into())
+    }
+
+    /// Set when the resource was created (`xmp:CreateDate`)
+    pub fn set_create_date(&mut self, date: &XmpDateTime) -> XmpResult<()> {
+        self.meta.set_date_time(ns::XMP, "CreateDate", date)
+    }
+
+    /// Set when the resource was last modified (`xmp:ModifyDate`)
+    pub fn set_modify_date(&mut self, date: &XmpDateTime) -> XmpResult<()> {
+        self.meta.set_date_time(ns::XMP, "ModifyDate", date)
+    }
+
+    /// Set when the metadata was last changed (`xmp:MetadataDate`)
+    pub fn set_metadata_date(&mut self, date: &XmpDateTime) -> XmpResult<()> {
+        self.meta.set_date_time(ns::XMP, "MetadataDate", date)
+    }
+
+    /// Set the application that created the resource (`xmp:CreatorTool`)
+    pub fn set_creator_tool(&mut self, tool: &str) -> XmpResult<()> {
+        self.meta.set_property(ns::XMP, "CreatorTool", tool.into())
+    }
+
+    /// Set the user label (`xmp:Label`)
+    pub fn set_label(&mut self, label: &str) -> XmpResult<()> {
+        self.meta.set_property(ns::XMP, "Label", label.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xmp_basic() {
+        let mut meta = XmpMeta::new();
+        meta.set_property(ns::XMP, "CreateDate", "2024-03-01T10:00:00Z".into())
+            .unwrap();
+        let mut basic = meta.xmp_basic_mut();
+        basic.set_rating(-1).unwrap();
+        assert!(basic.set_rating(6).is_err());
+        basic.set_creator_tool("Editor 1.0").unwrap();
+
+        let basic = meta.xmp_basic();
+        assert_eq!(basic.rating(), Some(-1));
+        assert_eq!(basic.create_date().unwrap().year, 2024);
+        assert!(basic.modify_date().is_none());
+        assert_eq!(basic.creator_tool().as_deref(), Some("Editor 1.0"));
+
+        meta.set_property(ns::XMP, "Rating", "five".into()).unwrap();
+        assert_eq!(meta.xmp_basic().rating(), None);
+        meta.set_property(ns::XMP, "Rating", "3.0".into()).unwrap();
+        assert_eq!(meta.xmp_basic().rating(), Some(3));
+    }
+}
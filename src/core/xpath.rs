@@ -10,85 +10,184 @@ use crate::core::error::{XmpError, XmpResult};
 /// Supports formats like:
 /// - `xmp:CreatorTool` - simple property
 /// - `dc:creator[1]` - array item with index
+/// - `dc:creator[last()]` - last array item
+/// - `dc:title[?xml:lang="fr-FR"]` - array item selected by qualifier value
+/// - `xmpMM:History[stEvt:action="saved"]` - array item selected by field value
 /// - `exif:Flash/Fired` - structure field
 /// - `dc:creator[1]/title` - nested path
+/// - `dc:creator[1]/?role` - qualifier of a property or array item
+/// - `dc:title[1]/@xml:lang` - attribute form of a qualifier step
 pub fn parse_path(path: &str) -> XmpResult<PathComponents> {
     let mut components = Vec::new();
     let mut current = String::new();
-    let mut in_brackets = false;
+    let mut is_qualifier = false;
+    let mut chars = path.chars().peekable();
 
-    for ch in path.chars() {
+    while let Some(ch) = chars.next() {
         match ch {
             '[' => {
                 if !current.is_empty() {
-                    components.push(PathComponent::Name(current.clone()));
-                    current.clear();
+                    components.push(name_component(&mut current, &mut is_qualifier));
+                } else if is_qualifier {
+                    return Err(XmpError::BadXPath("Empty qualifier name".to_string()));
                 }
-                in_brackets = true;
+                components.push(parse_selector(&mut chars)?);
             }
             ']' => {
-                if in_brackets {
-                    let index = current.parse::<usize>().map_err(|_| {
-                        XmpError::BadXPath(format!("Invalid array index: {}", current))
-                    })?;
-                    components.push(PathComponent::Index(index));
-                    current.clear();
-                    in_brackets = false;
-                } else {
-                    return Err(XmpError::BadXPath("Unexpected ']'".to_string()));
-                }
+                return Err(XmpError::BadXPath("Unexpected ']'".to_string()));
             }
             '/' => {
-                if !current.is_empty() && !in_brackets {
-                    components.push(PathComponent::Name(current.clone()));
-                    current.clear();
+                if !current.is_empty() {
+                    components.push(name_component(&mut current, &mut is_qualifier));
                 }
             }
-            _ => {
-                if !in_brackets || ch.is_ascii_digit() {
-                    current.push(ch);
-                } else {
-                    return Err(XmpError::BadXPath(format!(
-                        "Invalid character in index: {}",
-                        ch
-                    )));
-                }
+            '?' | '@' if current.is_empty() && !is_qualifier => {
+                is_qualifier = true;
             }
+            _ => current.push(ch),
         }
     }
 
-    if !current.is_empty() && !in_brackets {
-        components.push(PathComponent::Name(current));
+    if !current.is_empty() {
+        components.push(name_component(&mut current, &mut is_qualifier));
     }
 
-    if in_brackets {
-        return Err(XmpError::BadXPath("Unclosed bracket".to_string()));
+    if is_qualifier {
+        return Err(XmpError::BadXPath("Empty qualifier name".to_string()));
     }
 
     if components.is_empty() {
         return Err(XmpError::BadXPath("Empty path".to_string()));
     }
 
+    // Qualifiers are simple values, so nothing can follow a qualifier step
+    if let Some(pos) = components
+        .iter()
+        .position(|c| matches!(c, PathComponent::Qualifier(_)))
+    {
+        if pos + 1 != components.len() {
+            return Err(XmpError::BadXPath(
+                "Qualifier must be the last path step".to_string(),
+            ));
+        }
+    }
+
     Ok(PathComponents { components })
 }
 
+/// Take the accumulated step name as a name or qualifier component
+fn name_component(current: &mut String, is_qualifier: &mut bool) -> PathComponent {
+    let name = std::mem::take(current);
+    if std::mem::take(is_qualifier) {
+        PathComponent::Qualifier(name)
+    } else {
+        PathComponent::Name(name)
+    }
+}
+
+/// Parse the inside of an array step, after the opening `[`
+///
+/// Handles `[n]`, `[last()]`, `[?qual="value"]`, `[@qual="value"]` and
+/// `[field="value"]`. Values may be quoted with `"` or `'`; a doubled quote
+/// inside the value stands for the quote character itself.
+fn parse_selector(chars: &mut std::iter::Peekable<std::str::Chars>) -> XmpResult<PathComponent> {
+    let mut name = String::new();
+    loop {
+        match chars.next() {
+            Some(']') => break,
+            Some('=') => {
+                let value = parse_quoted_value(chars)?;
+                if chars.next() != Some(']') {
+                    return Err(XmpError::BadXPath(
+                        "Expected ']' after selector value".to_string(),
+                    ));
+                }
+                let name = name.trim();
+                return match name.strip_prefix(['?', '@']) {
+                    Some("") => Err(XmpError::BadXPath("Empty qualifier name".to_string())),
+                    Some(qual_name) => Ok(PathComponent::QualifierSelector {
+                        name: qual_name.to_string(),
+                        value,
+                    }),
+                    None if name.is_empty() => {
+                        Err(XmpError::BadXPath("Empty field name".to_string()))
+                    }
+                    None => Ok(PathComponent::FieldSelector {
+                        name: name.to_string(),
+                        value,
+                    }),
+                };
+            }
+            Some(ch) => name.push(ch),
+            None => return Err(XmpError::BadXPath("Unclosed bracket".to_string())),
+        }
+    }
+
+    let name = name.trim();
+    if name == "last()" {
+        return Ok(PathComponent::Last);
+    }
+    name.parse::<usize>()
+        .map(PathComponent::Index)
+        .map_err(|_| XmpError::BadXPath(format!("Invalid array index: {}", name)))
+}
+
+/// Parse a quoted selector value
+fn parse_quoted_value(chars: &mut std::iter::Peekable<std::str::Chars>) -> XmpResult<String> {
+    let quote = match chars.next() {
+        Some(q @ ('"' | '\'')) => q,
+        _ => {
+            return Err(XmpError::BadXPath(
+                "Selector value must be quoted".to_string(),
+            ))
+        }
+    };
+    let mut value = String::new();
+    loop {
+        match chars.next() {
+            Some(ch) if ch == quote => {
+                if chars.peek() == Some(&quote) {
+                    chars.next();
+                    value.push(quote);
+                } else {
+                    return Ok(value);
+                }
+            }
+            Some(ch) => value.push(ch),
+            None => {
+                return Err(XmpError::BadXPath(
+                    "Unterminated selector value".to_string(),
+                ))
+            }
+        }
+    }
+}
+
 /// Build a path from components
 pub fn build_path(components: &PathComponents) -> String {
     let mut result = String::new();
     for (i, comp) in components.components.iter().enumerate() {
-        if i > 0 {
-            match comp {
-                PathComponent::Name(_) => result.push('/'),
-                PathComponent::Index(_) => {}
-            }
+        if i > 0 && !comp.is_array_step() {
+            result.push('/');
         }
         match comp {
             PathComponent::Name(name) => result.push_str(name),
+            PathComponent::Qualifier(name) => {
+                result.push('?');
+                result.push_str(name);
+            }
             PathComponent::Index(idx) => {
                 result.push('[');
                 result.push_str(&idx.to_string());
                 result.push(']');
             }
+            PathComponent::Last => result.push_str("[last()]"),
+            PathComponent::QualifierSelector { name, value } => {
+                result.push_str(&format!("[?{}=\"{}\"]", name, value.replace('"', "\"\"")));
+            }
+            PathComponent::FieldSelector { name, value } => {
+                result.push_str(&format!("[{}=\"{}\"]", name, value.replace('"', "\"\"")));
+            }
         }
     }
     result
@@ -101,6 +200,37 @@ pub enum PathComponent {
     Name(String),
     /// An array index (1-based in XMP, but we use 0-based internally)
     Index(usize),
+    /// A qualifier name (`?name` or `?prefix:name`, also written `@name`)
+    Qualifier(String),
+    /// The last array item (`[last()]`)
+    Last,
+    /// The first array item with a qualifier of the given value (`[?xml:lang="fr-FR"]`)
+    QualifierSelector {
+        /// Qualifier name (`name` or `prefix:name`)
+        name: String,
+        /// Qualifier value to match
+        value: String,
+    },
+    /// The first array item whose structure field has the given value (`[stEvt:action="saved"]`)
+    FieldSelector {
+        /// Field name (`name` or `prefix:name`)
+        name: String,
+        /// Field value to match
+        value: String,
+    },
+}
+
+impl PathComponent {
+    /// Whether this component selects an array item
+    pub fn is_array_step(&self) -> bool {
+        matches!(
+            self,
+            PathComponent::Index(_)
+                | PathComponent::Last
+                | PathComponent::QualifierSelector { .. }
+                | PathComponent::FieldSelector { .. }
+        )
+    }
 }
 
 /// Parsed path components
@@ -167,4 +297,77 @@ mod tests {
         };
         assert_eq!(build_path(&components), "creator[1]");
     }
+
+    #[test]
+    fn test_parse_qualifier_path() {
+        let path = parse_path("dc:creator[1]/?ns:role").unwrap();
+        assert_eq!(path.components.len(), 3);
+        assert_eq!(path.components[1], PathComponent::Index(1));
+        assert_eq!(
+            path.components[2],
+            PathComponent::Qualifier("ns:role".to_string())
+        );
+        assert_eq!(build_path(&path), "dc:creator[1]/?ns:role");
+
+        assert!(parse_path("creator/?").is_err());
+        assert!(parse_path("creator/?role/field").is_err());
+    }
+
+    #[test]
+    fn test_parse_selectors() {
+        let path = parse_path("dc:title[?xml:lang=\"fr-FR\"]").unwrap();
+        assert_eq!(
+            path.components[1],
+            PathComponent::QualifierSelector {
+                name: "xml:lang".to_string(),
+                value: "fr-FR".to_string()
+            }
+        );
+        assert_eq!(build_path(&path), "dc:title[?xml:lang=\"fr-FR\"]");
+
+        let path = parse_path("dc:title[@xml:lang='x-default']").unwrap();
+        assert_eq!(
+            path.components[1],
+            PathComponent::QualifierSelector {
+                name: "xml:lang".to_string(),
+                value: "x-default".to_string()
+            }
+        );
+
+        let path = parse_path("History[stEvt:action=\"saved\"]/stEvt:when").unwrap();
+        assert_eq!(
+            path.components[1],
+            PathComponent::FieldSelector {
+                name: "stEvt:action".to_string(),
+                value: "saved".to_string()
+            }
+        );
+        assert_eq!(path.components.len(), 3);
+
+        let path = parse_path("creator[last()]").unwrap();
+        assert_eq!(path.components[1], PathComponent::Last);
+        assert_eq!(build_path(&path), "creator[last()]");
+
+        let path = parse_path("title[1]/@xml:lang").unwrap();
+        assert_eq!(
+            path.components[2],
+            PathComponent::Qualifier("xml:lang".to_string())
+        );
+
+        let path = parse_path("note[ns:text=\"say \"\"hi\"\"/bye\"]").unwrap();
+        assert_eq!(
+            path.components[1],
+            PathComponent::FieldSelector {
+                name: "ns:text".to_string(),
+                value: "say \"hi\"/bye".to_string()
+            }
+        );
+        assert_eq!(parse_path(&build_path(&path)).unwrap(), path);
+
+        assert!(parse_path("creator[first()]").is_err());
+        assert!(parse_path("creator[ns:a=saved]").is_err());
+        assert!(parse_path("creator[ns:a=\"saved]").is_err());
+        assert!(parse_path("creator[?=\"x\"]").is_err());
+        assert!(parse_path("creator[1").is_err());
+    }
 }
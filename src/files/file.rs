@@ -7,6 +7,7 @@ use crate::core::error::{XmpError, XmpResult};
 use crate::core::metadata::XmpMeta;
 use crate::files::handler::{FileHandler, XmpOptions};
 use crate::files::registry::default_registry;
+use crate::files::scanner::{self, PacketInfo};
 use std::io::{Cursor, Read, Seek, Write};
 
 /// High-level API for working with XMP metadata in files
@@ -137,83 +138,53 @@ impl XmpFile {
     /// Scan file content for XMP packet (packet scanning mode)
     ///
     /// This method searches for XMP packets in file content by looking for
-    /// the `<?xpacket` marker. Used when packet scanning is requested.
+    /// the `<?xpacket` marker, and returns the first one that parses. Used
+    /// when packet scanning is requested.
     pub fn scan_for_xmp_packet(file_data: &[u8]) -> XmpResult<Option<XmpMeta>> {
-        // Use byte search to find XMP packet (files may contain binary data)
-        // Look for "<?xpacket" pattern
-        let xpacket_start = b"<?xpacket";
-        let mut search_pos = 0;
-
-        while search_pos + xpacket_start.len() <= file_data.len() {
-            // Find next occurrence of "<?xpacket"
-            let Some(pos) = file_data[search_pos..]
-                .windows(xpacket_start.len())
-                .position(|window| window == xpacket_start)
-            else {
-                break;
-            };
-
-            let start_pos = search_pos + pos;
-
-            // Find the end of the packet ("<?xpacket end")
-            let xpacket_end_marker = b"<?xpacket end";
-            let Some(packet_end_offset) = file_data[start_pos..]
-                .windows(xpacket_end_marker.len())
-                .position(|window| window.starts_with(xpacket_end_marker))
-            else {
-                search_pos = start_pos + 1;
-                continue;
-            };
-
-            // Find the actual end: "<?xpacket end=\"w\"?>" or "<?xpacket end=\"r\"?>"
-            // Search for "?>" after the end marker (should be close after "end=")
-            let end_marker_start = start_pos + packet_end_offset;
-            // Look for "?>" after "<?xpacket end" - it should be within a reasonable distance
-            // (typically "<?xpacket end=\"w\"?>" or "<?xpacket end=\"r\"?>")
-            let Some(close_pos) = file_data[end_marker_start..]
-                .iter()
-                .enumerate()
-                .find(|(_, &b)| b == b'?')
-                .and_then(|(q_pos, _)| {
-                    if end_marker_start + q_pos + 1 < file_data.len()
-                        && file_data[end_marker_start + q_pos + 1] == b'>'
-                    {
-                        // Verify this is actually the end of <?xpacket end (not just any ?>)
-                        // Check that we have "end=" before the ?>
-                        let before_close = &file_data[end_marker_start..end_marker_start + q_pos];
-                        if before_close.ends_with(b"\"w\"") || before_close.ends_with(b"\"r\"") {
-                            Some(q_pos + 2)
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                })
-            else {
-                search_pos = start_pos + 1;
-                continue;
-            };
-
-            let packet_end_pos = end_marker_start + close_pos;
-
-            // Extract packet as string (XMP content should be valid UTF-8)
-            if let Ok(packet_str) = std::str::from_utf8(&file_data[start_pos..packet_end_pos]) {
-                // Try to parse the packet
-                match XmpMeta::parse(packet_str) {
-                    Ok(meta) => return Ok(Some(meta)),
-                    Err(_) => {
-                        // If parsing fails, continue searching for another packet
-                        search_pos = start_pos + 1;
-                        continue;
-                    }
-                }
-            }
+        Ok(scanner::scan_packets(file_data)
+            .iter()
+            .find_map(|packet| packet.parse().ok()))
+    }
 
-            search_pos = start_pos + 1;
-        }
+    /// Find every XMP packet in file content
+    ///
+    /// Packets are found by their `<?xpacket` header and trailer, in any
+    /// XMP encoding, and returned in file order without being parsed. Files
+    /// may hold several: the main packet, stale copies and the packets of
+    /// embedded documents (e.g., images placed in a PDF or PSD). Use
+    /// [`XmpFile::main_packet`] to pick the main one.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use xmpkit::XmpFile;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let data = std::fs::read("document.pdf")?;
+    /// for packet in XmpFile::scan_packets(&data) {
+    ///     println!(
+    ///         "{} bytes at {} ({}, {} bytes of padding)",
+    ///         packet.length,
+    ///         packet.offset,
+    ///         packet.encoding.name(),
+    ///         packet.padding
+    ///     );
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn scan_packets(file_data: &[u8]) -> Vec<PacketInfo> {
+        scanner::scan_packets(file_data)
+    }
 
-        Ok(None)
+    /// Pick the main packet among scanned packets
+    ///
+    /// Only packets that parse are considered. The main packet is the one
+    /// with the latest `xmp:MetadataDate` (or `xmp:ModifyDate`), as stale
+    /// copies and the packets of embedded documents are older; among equals,
+    /// the last one in the file wins, since updates are usually appended.
+    pub fn main_packet(packets: &[PacketInfo]) -> Option<&PacketInfo> {
+        scanner::main_packet(packets)
     }
 
     /// Open a file from a path (native platforms only)
@@ -578,6 +549,11 @@ impl XmpFile {
         {
             if self.options.for_update {
                 if let Some(ref path) = self.file_path {
+                    if self.options.record_save_event {
+                        if let Some(meta) = self.meta.as_mut() {
+                            meta.media_management_mut().record_save()?;
+                        }
+                    }
                     if let Some(ref meta) = self.meta {
                         use std::fs::File;
                         use std::io::BufWriter;
@@ -175,14 +175,12 @@ pub fn skip_box<R: Read + Seek>(reader: &mut R, box_info: &BmffBox) -> std::io::
 }
 
 /// Read box data
-pub fn read_box_data<R: Read + Seek>(
-    reader: &mut R,
-    box_info: &BmffBox,
-) -> std::io::Result<Vec<u8>> {
+///
+/// Fails with [`XmpError::LimitExceeded`](crate::XmpError::LimitExceeded)
+/// for boxes larger than [`MAX_DATA_SIZE`](super::MAX_DATA_SIZE).
+pub fn read_box_data<R: Read + Seek>(reader: &mut R, box_info: &BmffBox) -> XmpResult<Vec<u8>> {
     reader.seek(SeekFrom::Start(box_info.data_offset))?;
-    let mut data = vec![0u8; box_info.data_size() as usize];
-    reader.read_exact(&mut data)?;
-    Ok(data)
+    super::read_data(reader, box_info.data_size())
 }
 
 /// Copy bytes from reader to writer
@@ -13,6 +13,7 @@ use crate::core::metadata::XmpMeta;
 use crate::files::formats::bmff::{
     copy_bytes, read_box, read_box_data, skip_box, BmffBox, FTYP_BOX, UUID_BOX, XMP_UUID,
 };
+use crate::files::formats::read_data;
 use crate::files::handler::{FileHandler, XmpOptions};
 use std::io::{Read, Seek, SeekFrom, Write};
 
@@ -371,9 +372,7 @@ impl Mpeg4Handler {
             return Ok(None);
         }
 
-        let xmp_str = String::from_utf8(payload[16..].to_vec())
-            .map_err(|e| XmpError::ParseError(format!("Invalid UTF-8: {}", e)))?;
-        Ok(Some(XmpMeta::parse(&xmp_str)?))
+        Ok(Some(XmpMeta::parse_bytes(&payload[16..])?))
     }
 
     /// Scan all top-level boxes and build layout map (matches Adobe C++ OptimizeFileLayout)
@@ -520,11 +519,7 @@ impl Mpeg4Handler {
         // First, process moov box to get its new size
         let (moov_buffer, new_moov_size) = if let Some(moov_idx) = moov_index {
             reader.seek(SeekFrom::Start(boxes[moov_idx].old_offset))?;
-            let old_moov_data = {
-                let mut data = vec![0u8; boxes[moov_idx].box_size as usize];
-                reader.read_exact(&mut data)?;
-                data
-            };
+            let old_moov_data = read_data(&mut reader, boxes[moov_idx].box_size)?;
             let mut moov_buffer = Vec::new();
             {
                 use std::io::Cursor;
@@ -1820,9 +1815,7 @@ mod native_reconcile {
         reader.read_exact(&mut header)?;
         let data_type = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
         let _locale = u32::from_be_bytes([header[4], header[5], header[6], header[7]]);
-        let value_len = payload_size as usize - 8;
-        let mut buf = vec![0u8; value_len];
-        reader.read_exact(&mut buf)?;
+        let buf = read_data(reader, payload_size - 8)?;
 
         let val = match data_type {
             1 | 0 => String::from_utf8_lossy(&buf).to_string(),
@@ -1848,74 +1841,67 @@ mod native_reconcile {
             let lang = item.language.as_deref().unwrap_or("x-default");
 
             match &item.box_type {
-                b if b == BOX_NAM => {
+                b if b == BOX_NAM
                     // ©nam -> dc:title
-                    if meta
+                    && meta
                         .get_localized_text(ns::DC, "title", lang, lang)
-                        .is_none()
-                    {
-                        let _ = meta.set_localized_text(ns::DC, "title", lang, lang, &item.value);
-                    }
+                        .is_none() =>
+                {
+                    let _ = meta.set_localized_text(ns::DC, "title", lang, lang, &item.value);
                 }
-                b if b == BOX_ART => {
+                b if b == BOX_ART
                     // ©ART -> dc:creator (array)
-                    if meta.get_array_size(ns::DC, "creator").unwrap_or(0) == 0 {
-                        let _ =
-                            meta.append_array_item(ns::DC, "creator", item.value.clone().into());
-                    }
+                    && meta.get_array_size(ns::DC, "creator").unwrap_or(0) == 0 =>
+                {
+                    let _ = meta.append_array_item(ns::DC, "creator", item.value.clone().into());
                 }
-                b if b == BOX_WRT => {
+                b if b == BOX_WRT
                     // ©wrt -> dc:creator (if not already set)
                     // Note: could also map to xmpDM:composer
-                    if meta.get_array_size(ns::DC, "creator").unwrap_or(0) == 0 {
-                        let _ =
-                            meta.append_array_item(ns::DC, "creator", item.value.clone().into());
-                    }
+                    && meta.get_array_size(ns::DC, "creator").unwrap_or(0) == 0 =>
+                {
+                    let _ = meta.append_array_item(ns::DC, "creator", item.value.clone().into());
                 }
-                b if b == BOX_ALB => {
+                b if b == BOX_ALB
                     // ©alb -> xmpDM:album
-                    if meta.get_property(ns::XMP_DM, "album").is_none() {
-                        let _ = meta.set_property(ns::XMP_DM, "album", item.value.clone().into());
-                    }
+                    && meta.get_property(ns::XMP_DM, "album").is_none() =>
+                {
+                    let _ = meta.set_property(ns::XMP_DM, "album", item.value.clone().into());
                 }
-                b if b == BOX_DAY => {
+                b if b == BOX_DAY
                     // ©day -> dc:date or xmp:CreateDate
                     // QuickTime date format is often just a year like "2024"
-                    if meta.get_property(ns::XMP, "CreateDate").is_none() {
-                        let _ = meta.set_property(ns::XMP, "CreateDate", item.value.clone().into());
-                    }
+                    && meta.get_property(ns::XMP, "CreateDate").is_none() =>
+                {
+                    let _ = meta.set_property(ns::XMP, "CreateDate", item.value.clone().into());
                 }
-                b if b == BOX_CMT => {
+                b if b == BOX_CMT
                     // ©cmt -> dc:description
-                    if meta
+                    && meta
                         .get_localized_text(ns::DC, "description", lang, lang)
-                        .is_none()
-                    {
-                        let _ =
-                            meta.set_localized_text(ns::DC, "description", lang, lang, &item.value);
-                    }
+                        .is_none() =>
+                {
+                    let _ = meta.set_localized_text(ns::DC, "description", lang, lang, &item.value);
                 }
-                b if b == BOX_GEN => {
+                b if b == BOX_GEN
                     // ©gen -> xmpDM:genre
-                    if meta.get_property(ns::XMP_DM, "genre").is_none() {
-                        let _ = meta.set_property(ns::XMP_DM, "genre", item.value.clone().into());
-                    }
+                    && meta.get_property(ns::XMP_DM, "genre").is_none() =>
+                {
+                    let _ = meta.set_property(ns::XMP_DM, "genre", item.value.clone().into());
                 }
-                b if b == BOX_CPRT || b == BOX_CPY => {
+                b if (b == BOX_CPRT || b == BOX_CPY)
                     // cprt/©cpy -> dc:rights
-                    if meta
+                    && meta
                         .get_localized_text(ns::DC, "rights", lang, lang)
-                        .is_none()
-                    {
-                        let _ = meta.set_localized_text(ns::DC, "rights", lang, lang, &item.value);
-                    }
+                        .is_none() =>
+                {
+                    let _ = meta.set_localized_text(ns::DC, "rights", lang, lang, &item.value);
                 }
-                b if b == BOX_TOO => {
+                b if b == BOX_TOO
                     // ©too -> xmp:CreatorTool
-                    if meta.get_property(ns::XMP, "CreatorTool").is_none() {
-                        let _ =
-                            meta.set_property(ns::XMP, "CreatorTool", item.value.clone().into());
-                    }
+                    && meta.get_property(ns::XMP, "CreatorTool").is_none() =>
+                {
+                    let _ = meta.set_property(ns::XMP, "CreatorTool", item.value.clone().into());
                 }
                 b if (b == BOX_DES || b == BOX_LDES)
                     // desc/ldes -> dc:description
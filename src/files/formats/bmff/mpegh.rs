@@ -9,6 +9,7 @@ use crate::core::metadata::XmpMeta;
 use crate::files::formats::bmff::{
     copy_bytes, is_bmff, read_box, read_box_data, skip_box, FTYP_BOX, UUID_BOX, XMP_UUID,
 };
+use crate::files::formats::read_data;
 use crate::files::handler::{FileHandler, XmpOptions};
 use std::io::{Read, Seek, SeekFrom, Write};
 
@@ -216,18 +217,12 @@ impl MpeghHandler {
                 let uuid = &meta_body[content_start..content_start + 16];
                 if uuid == XMP_UUID {
                     let payload = &meta_body[content_start + 16..end];
-                    let payload_str = std::str::from_utf8(payload).map_err(|e| {
-                        XmpError::BadValue(format!("Invalid UTF-8 in HEIF XMP payload: {}", e))
-                    })?;
-                    let xmp = XmpMeta::parse(payload_str)?;
+                    let xmp = XmpMeta::parse_bytes(payload)?;
                     return Ok(Some(xmp));
                 }
             } else if box_type == BOX_TYPE_XML {
                 let payload = &meta_body[content_start..end];
-                let payload_str = std::str::from_utf8(payload).map_err(|e| {
-                    XmpError::BadValue(format!("Invalid UTF-8 in HEIF XMP payload: {}", e))
-                })?;
-                let xmp = XmpMeta::parse(payload_str)?;
+                let xmp = XmpMeta::parse_bytes(payload)?;
                 return Ok(Some(xmp));
             }
 
@@ -599,8 +594,7 @@ mod native_reconcile {
                 let exif_offset = mdat_data_start + exif_location.offset;
                 reader.seek(SeekFrom::Start(exif_offset))?;
 
-                let mut exif_data = vec![0u8; exif_location.length as usize];
-                reader.read_exact(&mut exif_data)?;
+                let exif_data = read_data(reader, exif_location.length as u64)?;
 
                 reader.seek(SeekFrom::Start(saved_pos))?;
                 return Ok(Some(exif_data));
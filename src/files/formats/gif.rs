@@ -261,8 +261,7 @@ impl GifHandler {
 
         // Seek back to XMPPacketOffset and read packet_length bytes
         reader.seek(SeekFrom::Start(xmp_packet_offset))?;
-        let mut raw_data = vec![0u8; packet_length as usize];
-        reader.read_exact(&mut raw_data)?;
+        let raw_data = super::read_data(reader, packet_length)?;
 
         // Check format: if first byte is '<' (0x3c), it's direct format (C++ written)
         // Otherwise, it's sub-block format (original files)
@@ -292,7 +291,7 @@ impl GifHandler {
 
         // Convert to UTF-8 string
         String::from_utf8(packet_data)
-            .map_err(|e| XmpError::ParseError(format!("Invalid UTF-8 in XMP packet: {}", e)))
+            .map_err(|e| XmpError::ParseError(format!("Invalid UTF-8 in XMP packet: {}", e).into()))
     }
 
     /// Write XMP metadata to a GIF file
@@ -184,10 +184,7 @@ impl JpegHandler {
         }
 
         // Parse XMP Packet
-        let xmp_str = String::from_utf8(xmp_data)
-            .map_err(|e| XmpError::ParseError(format!("Invalid UTF-8 in XMP: {}", e)))?;
-
-        XmpMeta::parse(&xmp_str).map(Some)
+        XmpMeta::parse_bytes(&xmp_data).map(Some)
     }
 
     /// Write XMP metadata to a JPEG file
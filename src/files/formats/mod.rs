@@ -10,6 +10,15 @@
 //! - `riff/` - RIFF-based formats (WebP, WAV, AVI)
 //! - `bmff/` - BMFF-based formats (MP4, MOV)
 //! - Individual modules for standalone formats
+//!
+//! ## Declared sizes
+//!
+//! Chunk, box and segment sizes come from the file and cannot be trusted.
+//! Handlers read such data with [`read_data`], which rejects sizes above
+//! [`MAX_DATA_SIZE`] and only allocates as the data is actually read.
+
+use crate::core::error::{XmpError, XmpResult};
+use std::io::{self, Read};
 
 #[cfg(feature = "gif")]
 pub mod gif;
@@ -35,3 +44,56 @@ pub mod riff;
 // BMFF-based formats
 #[cfg(any(feature = "mpeg4", feature = "heif"))]
 pub mod bmff;
+
+/// Largest chunk, box or segment handlers read into memory (256 MB)
+pub(crate) const MAX_DATA_SIZE: u64 = 256 * 1024 * 1024;
+
+/// Check a size declared by the file against [`MAX_DATA_SIZE`]
+pub(crate) fn check_data_size(size: u64) -> XmpResult<()> {
+    if size > MAX_DATA_SIZE {
+        return Err(XmpError::LimitExceeded(format!(
+            "declared data size {} is larger than {} bytes",
+            size, MAX_DATA_SIZE
+        )));
+    }
+    Ok(())
+}
+
+/// Read `size` bytes, a size declared by the file
+///
+/// Fails with [`XmpError::LimitExceeded`] if `size` is over
+/// [`MAX_DATA_SIZE`], and with an [`io::ErrorKind::UnexpectedEof`] error if
+/// the file ends first. The buffer grows with the data read, so a truncated
+/// file declaring a large size does not allocate it.
+pub(crate) fn read_data<R: Read + ?Sized>(reader: &mut R, size: u64) -> XmpResult<Vec<u8>> {
+    check_data_size(size)?;
+    let mut data = Vec::new();
+    reader.take(size).read_to_end(&mut data)?;
+    if (data.len() as u64) < size {
+        return Err(
+            io::Error::new(io::ErrorKind::UnexpectedEof, "failed to fill whole buffer").into(),
+        );
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_data() {
+        let bytes = [1u8, 2, 3, 4];
+        assert_eq!(read_data(&mut &bytes[..], 3).unwrap(), vec![1, 2, 3]);
+
+        // Truncated data
+        let error = read_data(&mut &bytes[..], 1000).unwrap_err();
+        assert!(
+            matches!(error, XmpError::IoError(ref e) if e.kind() == io::ErrorKind::UnexpectedEof)
+        );
+
+        // Declared size over the limit, rejected before reading
+        let error = read_data(&mut &bytes[..], MAX_DATA_SIZE + 1).unwrap_err();
+        assert!(matches!(error, XmpError::LimitExceeded(_)));
+    }
+}
@@ -292,8 +292,7 @@ impl Mp3Handler {
             } else {
                 ext_header_size
             };
-            let mut ext_header = vec![0u8; skip_size as usize - 4];
-            reader.read_exact(&mut ext_header)?;
+            let ext_header = super::read_data(&mut reader, (skip_size as u64).saturating_sub(4))?;
             writer.write_all(&ext_header)?;
         }
 
@@ -326,8 +325,7 @@ impl Mp3Handler {
                 reader.seek(SeekFrom::Current(frame_size as i64))?;
             } else {
                 // Copy other frames
-                let mut frame_content = vec![0u8; frame_size as usize];
-                reader.read_exact(&mut frame_content)?;
+                let frame_content = super::read_data(&mut reader, frame_size as u64)?;
                 other_frames.push((frame_header, frame_content));
             }
         }
@@ -499,8 +497,7 @@ impl Mp3Handler {
         frame_size: u32,
     ) -> XmpResult<Option<XmpMeta>> {
         // Read frame content
-        let mut frame_content = vec![0u8; frame_size as usize];
-        reader.read_exact(&mut frame_content)?;
+        let frame_content = super::read_data(reader, frame_size as u64)?;
 
         // Check for XMP prefix
         if frame_content.len() < 4 || &frame_content[0..4] != b"XMP\0" {
@@ -509,11 +506,7 @@ impl Mp3Handler {
 
         // Extract XMP Packet
         let xmp_packet = &frame_content[4..];
-        let xmp_str = String::from_utf8(xmp_packet.to_vec())
-            .map_err(|e| XmpError::ParseError(format!("Invalid UTF-8 in XMP: {}", e)))?;
-
-        // Parse XMP Packet
-        Ok(Some(XmpMeta::parse(&xmp_str)?))
+        Ok(Some(XmpMeta::parse_bytes(xmp_packet)?))
     }
 
     /// Write a synchsafe 32-bit integer to bytes (big-endian)
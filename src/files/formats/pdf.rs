@@ -132,7 +132,7 @@ impl PdfHandler {
 
         // Convert to string and parse XMP
         let xmp_str = String::from_utf8(xmp_bytes)
-            .map_err(|e| XmpError::ParseError(format!("Invalid UTF-8 in XMP: {}", e)))?;
+            .map_err(|e| XmpError::ParseError(format!("Invalid UTF-8 in XMP: {}", e).into()))?;
 
         // Handle empty XMP
         if xmp_str.trim().is_empty() {
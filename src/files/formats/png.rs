@@ -129,10 +129,7 @@ impl PngHandler {
 
             if chunk.chunk_type == *CHUNK_TYPE_ITXT {
                 if let Some(xmp_data) = Self::extract_xmp_from_itxt(&chunk.data)? {
-                    let xmp_str = String::from_utf8(xmp_data).map_err(|e| {
-                        XmpError::ParseError(format!("Invalid UTF-8 in XMP: {}", e))
-                    })?;
-                    return XmpMeta::parse(&xmp_str).map(Some);
+                    return XmpMeta::parse_bytes(&xmp_data).map(Some);
                 }
             }
         }
@@ -229,8 +226,7 @@ impl PngHandler {
         reader.read_exact(&mut chunk_type)?;
 
         // Read chunk data
-        let mut data = vec![0u8; length as usize];
-        reader.read_exact(&mut data)?;
+        let data = super::read_data(reader, length as u64)?;
 
         // Read CRC (4 bytes, big-endian)
         let mut crc_bytes = [0u8; 4];
@@ -410,6 +406,17 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_read_xmp_huge_chunk() {
+        // A chunk declaring 4 GB of data, in a tiny file
+        let mut png_data = PNG_SIGNATURE.to_vec();
+        png_data.extend_from_slice(&u32::MAX.to_be_bytes());
+        png_data.extend_from_slice(CHUNK_TYPE_ITXT);
+        png_data.extend_from_slice(b"XML:com.adobe.xmp");
+        let result = PngHandler::read_xmp(Cursor::new(png_data));
+        assert!(matches!(result, Err(XmpError::LimitExceeded(_))));
+    }
+
     #[test]
     fn test_invalid_png() {
         let invalid_data = vec![0x00, 0x01, 0x02, 0x03];
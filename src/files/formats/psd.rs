@@ -168,8 +168,7 @@ impl FileHandler for PsdHandler {
             // Check if this is the XMP resource
             if rsrc_id == PSIR_XMP && data_len > 0 {
                 // Read XMP data
-                let mut xmp_data = vec![0u8; data_len as usize];
-                reader.read_exact(&mut xmp_data)?;
+                let xmp_data = super::read_data(reader, data_len as u64)?;
 
                 // Parse XMP
                 let xmp_str = String::from_utf8_lossy(&xmp_data);
@@ -290,8 +289,7 @@ impl FileHandler for PsdHandler {
                     new_resources.extend_from_slice(&data_len.to_be_bytes());
 
                     // Copy data
-                    let mut data = vec![0u8; data_padded_len as usize];
-                    reader.read_exact(&mut data)?;
+                    let data = super::read_data(reader, data_padded_len as u64)?;
                     new_resources.extend_from_slice(&data);
                 }
 
@@ -158,9 +158,7 @@ pub fn find_chunk<'a>(chunks: &'a [RiffChunk], id: &[u8; 4]) -> Option<&'a RiffC
 /// Read chunk data
 pub fn read_chunk_data<R: Read + Seek>(reader: &mut R, chunk: &RiffChunk) -> XmpResult<Vec<u8>> {
     reader.seek(SeekFrom::Start(chunk.data_offset()))?;
-    let mut data = vec![0u8; chunk.size as usize];
-    reader.read_exact(&mut data)?;
-    Ok(data)
+    super::read_data(reader, chunk.size as u64)
 }
 
 /// Skip chunk data (including padding byte if odd size)
@@ -297,8 +295,7 @@ pub mod info {
             match read_chunk_header(reader) {
                 Ok(sub_chunk) => {
                     // Read null-terminated string
-                    let mut data = vec![0u8; sub_chunk.size as usize];
-                    reader.read_exact(&mut data)?;
+                    let mut data = crate::files::formats::read_data(reader, sub_chunk.size as u64)?;
 
                     // Remove null terminator if present
                     if let Some(pos) = data.iter().position(|&b| b == 0) {
@@ -332,52 +329,48 @@ pub mod info {
     pub fn reconcile_to_xmp(meta: &mut XmpMeta, items: &[InfoItem]) {
         for item in items {
             match &item.id {
-                id if id == INAM => {
+                id if id == INAM
                     // Title -> dc:title (as lang alt)
-                    if meta
+                    && meta
                         .get_localized_text(ns::DC, "title", "", "x-default")
-                        .is_none()
-                    {
-                        let _ =
-                            meta.set_localized_text(ns::DC, "title", "", "x-default", &item.value);
-                    }
+                        .is_none() =>
+                {
+                    let _ = meta.set_localized_text(ns::DC, "title", "", "x-default", &item.value);
                 }
-                id if id == IART => {
-                    // Artist -> dc:creator (as array)
-                    if meta.get_property(ns::DC, "creator").is_none() {
-                        let _ = meta.set_property(
-                            ns::DC,
-                            "creator",
-                            crate::types::value::XmpValue::Array(vec![
-                                crate::types::value::XmpValue::String(item.value.clone()),
-                            ]),
-                        );
-                    }
+                id if id == IART
+                    // Artist -> dc:creator (an rdf:Seq, as the Dublin Core schema defines it)
+                    && meta.get_property(ns::DC, "creator").is_none() =>
+                {
+                    let _ = meta.set_property(
+                        ns::DC,
+                        "creator",
+                        crate::types::value::XmpValue::Array(
+                            crate::core::node::ArrayType::Ordered,
+                            vec![crate::types::value::XmpValue::String(item.value.clone())],
+                        ),
+                    );
                 }
-                id if id == ICOP => {
+                id if id == ICOP
                     // Copyright -> dc:rights (as lang alt)
-                    if meta
+                    && meta
                         .get_localized_text(ns::DC, "rights", "", "x-default")
-                        .is_none()
-                    {
-                        let _ =
-                            meta.set_localized_text(ns::DC, "rights", "", "x-default", &item.value);
-                    }
+                        .is_none() =>
+                {
+                    let _ = meta.set_localized_text(ns::DC, "rights", "", "x-default", &item.value);
                 }
-                id if id == ICMT => {
+                id if id == ICMT
                     // Comment -> dc:description (as lang alt)
-                    if meta
+                    && meta
                         .get_localized_text(ns::DC, "description", "", "x-default")
-                        .is_none()
-                    {
-                        let _ = meta.set_localized_text(
-                            ns::DC,
-                            "description",
-                            "",
-                            "x-default",
-                            &item.value,
-                        );
-                    }
+                        .is_none() =>
+                {
+                    let _ = meta.set_localized_text(
+                        ns::DC,
+                        "description",
+                        "",
+                        "x-default",
+                        &item.value,
+                    );
                 }
                 id if id == ISFT
                     // Software -> xmp:CreatorTool
@@ -398,6 +391,8 @@ pub mod info {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::metadata::XmpMeta;
+    use crate::core::namespace::ns;
     use std::io::Cursor;
 
     fn create_minimal_riff(form_type: &[u8; 4]) -> Vec<u8> {
@@ -449,4 +444,25 @@ mod tests {
         assert_eq!(padded_size(10), 10);
         assert_eq!(padded_size(11), 12);
     }
+
+    #[test]
+    fn test_reconcile_artist_as_seq() {
+        let mut meta = XmpMeta::new();
+        let items = [info::InfoItem {
+            id: *info::IART,
+            value: "Jane Doe".to_string(),
+        }];
+        info::reconcile_to_xmp(&mut meta, &items);
+
+        let creator = meta.get_property(ns::DC, "creator").unwrap();
+        assert_eq!(
+            creator.array_type(),
+            Some(crate::core::node::ArrayType::Ordered)
+        );
+        assert_eq!(
+            meta.get_property(ns::DC, "creator[1]"),
+            Some("Jane Doe".into())
+        );
+        assert!(meta.serialize().unwrap().contains("<rdf:Seq>"));
+    }
 }
@@ -9,8 +9,8 @@
 //! Reference: <http://www-mmsp.ece.mcgill.ca/Documents/AudioFormats/WAVE/WAVE.html>
 
 use super::{
-    chunk_total_size, copy_chunk, info, read_all_chunks, validate_riff_header, write_chunk,
-    write_riff_header, CHUNK_HEADER_SIZE, LIST_CHUNK_ID,
+    chunk_total_size, copy_chunk, info, read_all_chunks, read_chunk_data, validate_riff_header,
+    write_chunk, write_riff_header, LIST_CHUNK_ID,
 };
 use crate::core::error::{XmpError, XmpResult};
 use crate::core::metadata::XmpMeta;
@@ -76,14 +76,9 @@ impl FileHandler for WavHandler {
         // Find and read XMP chunk
         let mut meta = None;
         if let Some(xmp_chunk) = chunks.iter().find(|c| c.id == *XMP_CHUNK_ID) {
-            reader.seek(SeekFrom::Start(xmp_chunk.offset + CHUNK_HEADER_SIZE))?;
-            let mut xmp_data = vec![0u8; xmp_chunk.size as usize];
-            reader.read_exact(&mut xmp_data)?;
+            let xmp_data = read_chunk_data(reader, xmp_chunk)?;
 
-            let xmp_str = String::from_utf8(xmp_data)
-                .map_err(|e| XmpError::ParseError(format!("Invalid UTF-8 in XMP: {}", e)))?;
-
-            meta = Some(XmpMeta::parse(&xmp_str)?);
+            meta = Some(XmpMeta::parse_bytes(&xmp_data)?);
         }
 
         // If only_xmp is set, skip reconciliation
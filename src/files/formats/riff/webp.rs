@@ -6,9 +6,9 @@
 //! Reference: RFC 9649 - WebP Image Format
 
 use super::{
-    chunk_total_size, copy_chunk, read_all_chunks, read_chunk_header, skip_chunk_data,
-    validate_riff_header, write_chunk, write_riff_header, RiffChunk, CHUNK_HEADER_SIZE,
-    RIFF_HEADER_SIZE,
+    chunk_total_size, copy_chunk, read_all_chunks, read_chunk_data, read_chunk_header,
+    skip_chunk_data, validate_riff_header, write_chunk, write_riff_header, RiffChunk,
+    CHUNK_HEADER_SIZE, RIFF_HEADER_SIZE,
 };
 use crate::core::error::{XmpError, XmpResult};
 use crate::core::metadata::XmpMeta;
@@ -110,13 +110,9 @@ impl WebpHandler {
         while let Ok(chunk) = read_chunk_header(&mut reader) {
             if chunk.id == *XMP_CHUNK_ID {
                 // Found XMP chunk, read its data
-                let mut xmp_data = vec![0u8; chunk.size as usize];
-                reader.read_exact(&mut xmp_data)?;
+                let xmp_data = read_chunk_data(&mut reader, &chunk)?;
 
-                let xmp_str = String::from_utf8(xmp_data)
-                    .map_err(|e| XmpError::ParseError(format!("Invalid UTF-8 in XMP: {}", e)))?;
-
-                return XmpMeta::parse(&xmp_str).map(Some);
+                return XmpMeta::parse_bytes(&xmp_data).map(Some);
             }
 
             // Skip this chunk
@@ -186,9 +182,7 @@ impl WebpHandler {
 
             if chunk.id == *VP8X_CHUNK_ID {
                 // Update VP8X chunk with XMP flag
-                reader.seek(SeekFrom::Start(chunk.offset + CHUNK_HEADER_SIZE))?;
-                let mut vp8x_data = vec![0u8; chunk.size as usize];
-                reader.read_exact(&mut vp8x_data)?;
+                let mut vp8x_data = read_chunk_data(&mut reader, chunk)?;
 
                 if !vp8x_data.is_empty() {
                     vp8x_data[0] |= VP8X_XMP_FLAG;
@@ -25,7 +25,6 @@
 
 use std::io::{Read, Seek, SeekFrom, Write};
 
-use quick_xml::escape::unescape;
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::{Reader, Writer, XmlVersion};
 
@@ -117,7 +116,8 @@ impl FileHandler for SvgHandler {
 
         // Parse with quick-xml to find metadata
         let mut xml_reader = Reader::from_str(&content);
-        xml_reader.config_mut().trim_text(true);
+        // Keep whitespace, text around entity references comes in separate events
+        xml_reader.config_mut().trim_text(false);
 
         let mut in_metadata = false;
         let mut metadata_depth = 0;
@@ -138,7 +138,7 @@ impl FileHandler for SvgHandler {
                         metadata_depth += 1;
 
                         // Check for xmpmeta or RDF
-                        if name_str == "xmpmeta" || name_str == "RDF" {
+                        if !capture_xmp && (name_str == "xmpmeta" || name_str == "RDF") {
                             capture_xmp = true;
                             xmp_depth = 1;
                             // Include the opening tag
@@ -177,36 +177,29 @@ impl FileHandler for SvgHandler {
                         }
                     }
                 }
-                Ok(Event::Empty(e)) => {
-                    if capture_xmp {
-                        xmp_content.push('<');
-                        xmp_content.push_str(&reconstruct_element(&e));
-                        xmp_content.push_str("/>");
-                    }
+                Ok(Event::Empty(e)) if capture_xmp => {
+                    xmp_content.push('<');
+                    xmp_content.push_str(&reconstruct_element(&e));
+                    xmp_content.push_str("/>");
                 }
-                Ok(Event::Text(e)) => {
-                    if capture_xmp {
-                        let raw_text = String::from_utf8_lossy(e.as_ref());
-                        if let Ok(text) = unescape(&raw_text) {
-                            xmp_content.push_str(&text);
-                        } else {
-                            xmp_content.push_str(&raw_text);
-                        }
-                    }
+                Ok(Event::Text(e)) if capture_xmp => {
+                    // Keep the text escaped, it is parsed again below
+                    xmp_content.push_str(&String::from_utf8_lossy(e.as_ref()));
                 }
-                Ok(Event::CData(e)) => {
-                    if capture_xmp {
-                        xmp_content.push_str("<![CDATA[");
-                        xmp_content.push_str(std::str::from_utf8(e.as_ref()).unwrap_or(""));
-                        xmp_content.push_str("]]>");
-                    }
+                Ok(Event::GeneralRef(e)) if capture_xmp => {
+                    xmp_content.push('&');
+                    xmp_content.push_str(&String::from_utf8_lossy(e.as_ref()));
+                    xmp_content.push(';');
                 }
-                Ok(Event::PI(e)) => {
-                    if capture_xmp {
-                        xmp_content.push_str("<?");
-                        xmp_content.push_str(std::str::from_utf8(e.as_ref()).unwrap_or(""));
-                        xmp_content.push_str("?>");
-                    }
+                Ok(Event::CData(e)) if capture_xmp => {
+                    xmp_content.push_str("<![CDATA[");
+                    xmp_content.push_str(std::str::from_utf8(e.as_ref()).unwrap_or(""));
+                    xmp_content.push_str("]]>");
+                }
+                Ok(Event::PI(e)) if capture_xmp => {
+                    xmp_content.push_str("<?");
+                    xmp_content.push_str(std::str::from_utf8(e.as_ref()).unwrap_or(""));
+                    xmp_content.push_str("?>");
                 }
                 Ok(Event::Eof) => break,
                 Err(_) => break,
@@ -364,10 +357,9 @@ impl FileHandler for SvgHandler {
                 // Handle any other events (e.g., GeneralRef) - skip them
                 Ok(_) => {}
                 Err(e) => {
-                    return Err(crate::XmpError::ParseError(format!(
-                        "XML parse error: {}",
-                        e
-                    )));
+                    return Err(crate::XmpError::ParseError(
+                        format!("XML parse error: {}", e).into(),
+                    ));
                 }
             }
         }
@@ -609,4 +601,33 @@ mod tests {
         assert!(result.is_ok());
         assert!(result.unwrap().is_none());
     }
+
+    #[test]
+    fn test_read_xmp_keeps_escaped_text() {
+        let handler = SvgHandler;
+        let svg = r#"<?xml version="1.0"?>
+<svg xmlns="http://www.w3.org/2000/svg">
+<metadata>
+<x:xmpmeta xmlns:x="adobe:ns:meta/">
+<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+         xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <rdf:Description rdf:about="">
+    <dc:source>Tom &amp; Jerry &lt;1940&gt;</dc:source>
+  </rdf:Description>
+</rdf:RDF>
+</x:xmpmeta>
+</metadata>
+</svg>"#;
+        let mut cursor = Cursor::new(svg.as_bytes());
+
+        let meta = handler
+            .read_xmp(&mut cursor, &XmpOptions::default())
+            .unwrap()
+            .unwrap();
+        let source = meta.get_property(crate::core::namespace::ns::DC, "source");
+        assert_eq!(
+            source.and_then(|v| v.as_str().map(String::from)),
+            Some("Tom & Jerry <1940>".to_string())
+        );
+    }
 }
@@ -149,7 +149,7 @@ impl TiffHandler {
 
         // Parse XMP Packet
         let xmp_str = String::from_utf8(xmp_data)
-            .map_err(|e| XmpError::ParseError(format!("Invalid UTF-8 in XMP: {}", e)))?;
+            .map_err(|e| XmpError::ParseError(format!("Invalid UTF-8 in XMP: {}", e).into()))?;
 
         XmpMeta::parse(&xmp_str).map(Some)
     }
@@ -195,9 +195,8 @@ impl TiffHandler {
 
         // Copy file up to first IFD
         reader.seek(SeekFrom::Start(8))?;
-        let mut buffer = vec![0u8; (first_ifd_offset as usize).saturating_sub(8)];
+        let buffer = super::read_data(&mut reader, (first_ifd_offset as u64).saturating_sub(8))?;
         if !buffer.is_empty() {
-            reader.read_exact(&mut buffer)?;
             writer.write_all(&buffer)?;
         }
 
@@ -292,8 +291,7 @@ impl TiffHandler {
             let saved_pos = reader.stream_position()?;
             reader.seek(SeekFrom::Start(entry.value_or_offset as u64))?;
 
-            let mut data = vec![0u8; data_size];
-            reader.read_exact(&mut data)?;
+            let data = super::read_data(reader, data_size as u64)?;
 
             reader.seek(SeekFrom::Start(saved_pos))?;
             Ok(data)
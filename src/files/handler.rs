@@ -40,6 +40,8 @@ pub struct XmpOptions {
     pub use_packet_scanning: bool,
     /// Only packet scan files "known" to need scanning
     pub limited_scanning: bool,
+    /// Append a "saved" event to `xmpMM:History` when writing on close
+    pub record_save_event: bool,
 }
 
 impl XmpOptions {
@@ -103,6 +105,17 @@ impl XmpOptions {
         self.limited_scanning = true;
         self
     }
+
+    /// Record a save in the metadata when the file is written on close.
+    ///
+    /// The metadata gets a new `xmpMM:InstanceID` and a "saved" event in
+    /// `xmpMM:History` (see [`MediaManagement::record_save`]).
+    ///
+    /// [`MediaManagement::record_save`]: crate::core::views::MediaManagement::record_save
+    pub fn record_save_event(mut self) -> Self {
+        self.record_save_event = true;
+        self
+    }
 }
 
 /// Trait for file format handlers
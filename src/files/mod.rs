@@ -8,6 +8,7 @@ pub mod file;
 pub mod formats;
 pub mod handler;
 pub mod registry;
+pub mod scanner;
 
 pub use file::XmpFile;
 #[cfg(feature = "mpeg4")]
@@ -39,3 +40,4 @@ pub use formats::tiff::TiffHandler;
 pub use handler::FileHandler;
 pub use handler::XmpOptions;
 pub use registry::{default_registry, Handler, HandlerRegistry};
+pub use scanner::PacketInfo;
@@ -0,0 +1,315 @@
+//! XMP Packet scanning
+//!
+//! Finds XMP Packets in arbitrary file content by their `<?xpacket begin`
+//! header and `<?xpacket end` trailer (XMP Specification Part 1, 7.3), in
+//! any of the XMP encodings. Files may hold several packets: the main one,
+//! stale copies left by incremental saves (e.g., in PDF) and the packets of
+//! placed or embedded documents.
+
+use crate::core::encoding::PacketEncoding;
+use crate::core::metadata::XmpMeta;
+use crate::core::namespace::ns;
+use crate::types::value::XmpValue;
+use crate::utils::datetime::XmpDateTime;
+
+/// An XMP Packet found by [`XmpFile::scan_packets`](crate::XmpFile::scan_packets)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PacketInfo {
+    /// Byte offset of the `<?xpacket begin` header in the data
+    pub offset: usize,
+    /// Length of the packet in bytes, from the header to the end of the
+    /// trailer
+    pub length: usize,
+    /// Bytes of whitespace padding before the trailer
+    pub padding: usize,
+    /// Character encoding of the packet
+    pub encoding: PacketEncoding,
+    /// Whether the trailer allows modifying the packet in place
+    /// (`end="w"`, as opposed to `end="r"`)
+    pub writable: bool,
+    /// The packet, header and trailer included
+    pub bytes: Vec<u8>,
+}
+
+impl PacketInfo {
+    /// Parse the packet
+    pub fn parse(&self) -> crate::XmpResult<XmpMeta> {
+        XmpMeta::parse_bytes(&self.bytes)
+    }
+}
+
+/// All encodings, to look for packets in each
+const ENCODINGS: [PacketEncoding; 5] = [
+    PacketEncoding::Utf8,
+    PacketEncoding::Utf16Be,
+    PacketEncoding::Utf16Le,
+    PacketEncoding::Utf32Be,
+    PacketEncoding::Utf32Le,
+];
+
+/// Find all packets in `data`, in file order
+///
+/// The same bytes can match in two encodings: a UTF-16LE packet after a zero
+/// byte also reads as UTF-16BE one byte earlier, and likewise for UTF-32.
+/// Of overlapping matches, only the most plausible one is kept: the one with
+/// the byte order mark of its encoding in the header, then the one aligned
+/// to its code unit size.
+pub(crate) fn scan_packets(data: &[u8]) -> Vec<PacketInfo> {
+    let mut candidates: Vec<PacketInfo> = ENCODINGS
+        .iter()
+        .flat_map(|&encoding| scan_encoding(data, encoding))
+        .collect();
+    // Stable, so ties keep the order of ENCODINGS
+    candidates.sort_by_key(|packet| {
+        std::cmp::Reverse((
+            has_bom(packet),
+            packet.offset % packet.encoding.unit_size() == 0,
+        ))
+    });
+
+    let mut packets: Vec<PacketInfo> = Vec::new();
+    for candidate in candidates {
+        let end = candidate.offset + candidate.length;
+        let overlaps = packets
+            .iter()
+            .any(|packet| candidate.offset < packet.offset + packet.length && packet.offset < end);
+        if !overlaps {
+            packets.push(candidate);
+        }
+    }
+    packets.sort_by_key(|packet| packet.offset);
+    packets
+}
+
+/// Whether the `begin` attribute of the header holds the byte order mark of
+/// the packet's encoding
+fn has_bom(packet: &PacketInfo) -> bool {
+    // <?xpacket begin= and the opening quote
+    let start = encode(b"<?xpacket begin=\"", packet.encoding).len();
+    match packet.bytes.get(start..) {
+        Some(rest) => matches!(
+            PacketEncoding::detect(rest),
+            (encoding, bom_len) if bom_len > 0 && encoding == packet.encoding
+        ),
+        None => false,
+    }
+}
+
+/// Find the packets of one encoding
+fn scan_encoding(data: &[u8], encoding: PacketEncoding) -> Vec<PacketInfo> {
+    let header = encode(b"<?xpacket begin", encoding);
+    let trailer = encode(b"<?xpacket end=", encoding);
+    let unit = encoding.unit_size();
+
+    let mut packets = Vec::new();
+    let mut pos = 0;
+    while let Some(start) = find(data, &header, pos) {
+        pos = start + header.len();
+        let Some(end_start) = find(data, &trailer, pos) else {
+            break;
+        };
+        // A header before the trailer: this packet is truncated
+        if find(&data[..end_start], &header, pos).is_some() {
+            continue;
+        }
+
+        // end="w"?> or end='r'?>
+        let attr_start = end_start + trailer.len();
+        let Some(attr) = data.get(attr_start..attr_start + 5 * unit) else {
+            break;
+        };
+        let chars: Vec<Option<u8>> = attr
+            .chunks(unit)
+            .map(|chunk| decode_ascii(chunk, encoding))
+            .collect();
+        let writable = match chars[..] {
+            [Some(q1), Some(flag @ (b'w' | b'r')), Some(q2), Some(b'?'), Some(b'>')]
+                if q1 == q2 && (q1 == b'"' || q1 == b'\'') =>
+            {
+                flag == b'w'
+            }
+            _ => continue,
+        };
+        let end = attr_start + attr.len();
+
+        // Whitespace padding before the trailer
+        let mut padding_start = end_start;
+        while padding_start >= pos + unit {
+            let c = decode_ascii(&data[padding_start - unit..padding_start], encoding);
+            if !matches!(c, Some(b' ' | b'\t' | b'\n' | b'\r')) {
+                break;
+            }
+            padding_start -= unit;
+        }
+
+        packets.push(PacketInfo {
+            offset: start,
+            length: end - start,
+            padding: end_start - padding_start,
+            encoding,
+            writable,
+            bytes: data[start..end].to_vec(),
+        });
+        pos = end;
+    }
+    packets
+}
+
+/// Pick the main packet: the one describing the file itself
+///
+/// Only packets that parse are considered. The main packet is the one
+/// modified last, by `xmp:MetadataDate` (or `xmp:ModifyDate`), as stale
+/// copies and the packets of embedded documents are older. Packets without
+/// dates come before dated ones, and ties go to the last packet in the
+/// file, since updates are usually appended.
+pub(crate) fn main_packet(packets: &[PacketInfo]) -> Option<&PacketInfo> {
+    packets
+        .iter()
+        .filter_map(|packet| {
+            let meta = packet.parse().ok()?;
+            Some((modify_time(&meta), packet))
+        })
+        .max_by_key(|(time, packet)| (*time, packet.offset))
+        .map(|(_, packet)| packet)
+}
+
+/// When the metadata was last modified, as a Unix timestamp
+fn modify_time(meta: &XmpMeta) -> Option<i64> {
+    ["MetadataDate", "ModifyDate"].iter().find_map(|name| {
+        match meta.get_property(ns::XMP, name)? {
+            XmpValue::DateTime(date) => Some(date.to_unix_timestamp()),
+            value => XmpDateTime::parse(&value.to_string())
+                .ok()
+                .map(|date| date.to_unix_timestamp()),
+        }
+    })
+}
+
+/// Encode ASCII text in `encoding`
+fn encode(text: &[u8], encoding: PacketEncoding) -> Vec<u8> {
+    text.iter()
+        .flat_map(|&c| {
+            let c = u32::from(c);
+            match encoding {
+                PacketEncoding::Utf8 => vec![c as u8],
+                PacketEncoding::Utf16Be => (c as u16).to_be_bytes().to_vec(),
+                PacketEncoding::Utf16Le => (c as u16).to_le_bytes().to_vec(),
+                PacketEncoding::Utf32Be => c.to_be_bytes().to_vec(),
+                PacketEncoding::Utf32Le => c.to_le_bytes().to_vec(),
+            }
+        })
+        .collect()
+}
+
+/// Decode one code unit, if it is an ASCII character
+fn decode_ascii(unit: &[u8], encoding: PacketEncoding) -> Option<u8> {
+    let c = match (encoding, unit) {
+        (PacketEncoding::Utf8, [c]) => u32::from(*c),
+        (PacketEncoding::Utf16Be, [a, b]) => u32::from(u16::from_be_bytes([*a, *b])),
+        (PacketEncoding::Utf16Le, [a, b]) => u32::from(u16::from_le_bytes([*a, *b])),
+        (PacketEncoding::Utf32Be, [a, b, c, d]) => u32::from_be_bytes([*a, *b, *c, *d]),
+        (PacketEncoding::Utf32Le, [a, b, c, d]) => u32::from_le_bytes([*a, *b, *c, *d]),
+        _ => return None,
+    };
+    u8::try_from(c).ok().filter(u8::is_ascii)
+}
+
+/// Find `needle` in `haystack` from `from`
+fn find(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|pos| from + pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packet(date: &str, padding: usize, end: &str) -> String {
+        format!(
+            "<?xpacket begin=\"\u{FEFF}\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?>\
+             <x:xmpmeta xmlns:x=\"adobe:ns:meta/\">\
+             <rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\
+             <rdf:Description rdf:about=\"\" xmlns:xmp=\"http://ns.adobe.com/xap/1.0/\" \
+             xmp:MetadataDate=\"{}\"/></rdf:RDF></x:xmpmeta>{}<?xpacket end={}?>",
+            date,
+            " ".repeat(padding),
+            end
+        )
+    }
+
+    #[test]
+    fn test_scan_packets() {
+        let newest = packet("2024-05-01T10:00:00Z", 100, "\"w\"");
+        let older = packet("2023-01-01T00:00:00Z", 0, "'r'");
+        let utf16: Vec<u8> = packet("2022-01-01T00:00:00Z", 10, "\"w\"")
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+
+        let mut data = b"%PDF-1.4 binary \x00\xFF".to_vec();
+        data.extend_from_slice(newest.as_bytes());
+        data.extend_from_slice(b"\nstream\x00\x01");
+        data.extend_from_slice(&utf16);
+        // Truncated packet
+        data.extend_from_slice(b"<?xpacket begin=\"\"?><x:xmpmeta");
+        data.extend_from_slice(older.as_bytes());
+        // Header without trailer
+        data.extend_from_slice(b"<?xpacket begin=\"\"?>");
+
+        let packets = scan_packets(&data);
+        assert_eq!(packets.len(), 3);
+
+        let first = &packets[0];
+        assert_eq!(first.offset, 18);
+        assert_eq!(first.length, newest.len());
+        assert_eq!(first.bytes, newest.as_bytes());
+        assert_eq!(first.padding, 100);
+        assert_eq!(first.encoding, PacketEncoding::Utf8);
+        assert!(first.writable);
+
+        assert_eq!(packets[1].encoding, PacketEncoding::Utf16Le);
+        assert_eq!(packets[1].padding, 20);
+        assert_eq!(packets[1].bytes, utf16);
+        assert!(packets[1].parse().is_ok());
+
+        assert_eq!(packets[2].bytes, older.as_bytes());
+        assert!(!packets[2].writable);
+        assert_eq!(packets[2].padding, 0);
+
+        // The newest packet, even though it is not the last
+        assert_eq!(main_packet(&packets), Some(&packets[0]));
+        assert_eq!(main_packet(&packets[1..]), Some(&packets[2]));
+        assert_eq!(main_packet(&[]), None);
+    }
+
+    #[test]
+    fn test_scan_packets_overlapping_encodings() {
+        let text = packet("2024-05-01T10:00:00Z", 0, "\"w\"");
+        let utf16le: Vec<u8> = text.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let utf32le: Vec<u8> = text
+            .chars()
+            .flat_map(|c| u32::from(c).to_le_bytes())
+            .collect();
+
+        // UTF-16LE after a zero byte also reads as UTF-16BE from the zero
+        // byte, and UTF-32LE after three zero bytes as UTF-32BE
+        let mut data = vec![0, 0];
+        data.extend_from_slice(&utf16le);
+        data.extend_from_slice(&[0, 0, 0]);
+        let utf32_offset = data.len();
+        data.extend_from_slice(&utf32le);
+
+        let packets = scan_packets(&data);
+        assert_eq!(packets.len(), 2);
+        assert_eq!(packets[0].offset, 2);
+        assert_eq!(packets[0].encoding, PacketEncoding::Utf16Le);
+        assert_eq!(packets[0].bytes, utf16le);
+        assert_eq!(packets[1].offset, utf32_offset);
+        assert_eq!(packets[1].encoding, PacketEncoding::Utf32Le);
+        assert_eq!(packets[1].bytes, utf32le);
+        assert!(packets.iter().all(|packet| packet.parse().is_ok()));
+    }
+}
@@ -80,7 +80,7 @@
 //! ### Parsing XMP from String
 //!
 //! ```rust
-//! use xmpkit::{XmpMeta, XmpValue};
+//! use xmpkit::{XmpDateTime, XmpMeta, XmpValue};
 //! use xmpkit::core::namespace::ns;
 //!
 //! # fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -104,7 +104,7 @@
 //! meta.set_property(
 //!     ns::XMP,
 //!     "ModifyDate",
-//!     XmpValue::DateTime("2024-01-01T00:00:00Z".to_string()),
+//!     XmpValue::DateTime(XmpDateTime::parse("2024-01-01T00:00:00Z")?),
 //! )?;
 //!
 //! let serialized = meta.serialize_packet()?;
@@ -229,16 +229,18 @@ pub mod ohos;
 
 // Re-export commonly used types
 #[cfg(feature = "core")]
-pub use core::error::{XmpError, XmpResult};
+pub use core::alias::{register_alias, resolve_alias, AliasForm};
 #[cfg(feature = "core")]
-pub use core::metadata::XmpMeta;
+pub use core::error::{ParseErrorInfo, ParseLocation, XmpError, XmpResult};
+#[cfg(feature = "core")]
+pub use core::metadata::{SharedXmpMeta, XmpMeta};
 #[cfg(feature = "core")]
 pub use core::namespace::{
     get_all_registered_namespaces, get_builtin_namespace_uris, get_global_namespace_prefix,
     get_global_namespace_uri, is_namespace_registered, ns, register_namespace,
 };
 #[cfg(feature = "files")]
-pub use files::{XmpFile, XmpOptions};
+pub use files::{PacketInfo, XmpFile, XmpOptions};
 pub use types::qualifier::Qualifier;
 pub use types::value::XmpValue;
 pub use utils::datetime::XmpDateTime;
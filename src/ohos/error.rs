@@ -1,6 +1,6 @@
 //! OpenHarmony error handling for XMP operations
 
-use crate::core::error::XmpError as RustXmpError;
+use crate::core::error::{ParseLocation, XmpError as RustXmpError};
 use napi_derive_ohos::napi;
 
 /// OpenHarmony error type for XMP operations
@@ -10,11 +10,18 @@ use napi_derive_ohos::napi;
 pub struct XmpError {
     kind: XmpErrorKind,
     message: String,
+    location: Option<ParseLocation>,
 }
 
 impl std::fmt::Display for XmpError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}: {}", self.kind, self.message)
+        write!(f, "{:?}: {}", self.kind, self.message)?;
+        // Errors are thrown with this text as the reason, so it has to
+        // carry the location too
+        if let Some(location) = &self.location {
+            write!(f, " at {}", location)?;
+        }
+        Ok(())
     }
 }
 
@@ -42,6 +49,8 @@ pub enum XmpErrorKind {
     NotFound,
     /// Not supported error
     NotSupported,
+    /// Limit exceeded error
+    LimitExceeded,
 }
 
 #[napi]
@@ -57,21 +66,90 @@ impl XmpError {
     pub fn message(&self) -> String {
         self.message.clone()
     }
+
+    /// Get the byte offset of a parse error in the packet, if known
+    #[napi(getter)]
+    pub fn offset(&self) -> Option<u32> {
+        self.location
+            .as_ref()
+            .map(|location| clamp_to_u32(location.offset))
+    }
+
+    /// Get the line of a parse error (starting at 1), if known
+    #[napi(getter)]
+    pub fn line(&self) -> Option<u32> {
+        self.location
+            .as_ref()
+            .map(|location| clamp_to_u32(location.line))
+    }
+
+    /// Get the column of a parse error (in bytes, starting at 1), if known
+    #[napi(getter)]
+    pub fn column(&self) -> Option<u32> {
+        self.location
+            .as_ref()
+            .map(|location| clamp_to_u32(location.column))
+    }
+
+    /// Get the element path of a parse error (e.g.,
+    /// `rdf:RDF/rdf:Description/dc:creator/rdf:Seq`), if known
+    #[napi(getter)]
+    pub fn path(&self) -> Option<String> {
+        self.location.as_ref().map(ParseLocation::path_string)
+    }
+
+    /// Get a short excerpt of the source at a parse error, if known
+    #[napi(getter)]
+    pub fn snippet(&self) -> Option<String> {
+        self.location
+            .as_ref()
+            .map(|location| location.snippet.clone())
+    }
 }
 
 /// Convert Rust XmpError to OpenHarmony XmpError
 pub(crate) fn xmp_error_to_ohos_error(err: RustXmpError) -> XmpError {
+    let location = err.parse_location().cloned();
     let (kind, message) = match &err {
         RustXmpError::BadParam(msg) => (XmpErrorKind::BadParam, msg.clone()),
         RustXmpError::BadValue(msg) => (XmpErrorKind::BadValue, msg.clone()),
         RustXmpError::BadSchema(msg) => (XmpErrorKind::BadSchema, msg.clone()),
         RustXmpError::BadXPath(msg) => (XmpErrorKind::BadXPath, msg.clone()),
-        RustXmpError::ParseError(msg) => (XmpErrorKind::ParseError, msg.clone()),
+        RustXmpError::ParseError(info) => (XmpErrorKind::ParseError, info.message.clone()),
         RustXmpError::SerializationError(msg) => (XmpErrorKind::SerializationError, msg.clone()),
         RustXmpError::IoError(io_err) => (XmpErrorKind::IoError, io_err.to_string()),
         RustXmpError::InternalError(msg) => (XmpErrorKind::InternalError, msg.clone()),
         RustXmpError::NotFound(msg) => (XmpErrorKind::NotFound, msg.clone()),
         RustXmpError::NotSupported(msg) => (XmpErrorKind::NotSupported, msg.clone()),
+        RustXmpError::LimitExceeded(msg) => (XmpErrorKind::LimitExceeded, msg.clone()),
     };
-    XmpError { kind, message }
+    XmpError {
+        kind,
+        message,
+        location,
+    }
+}
+
+fn clamp_to_u32(value: u64) -> u32 {
+    u32::try_from(value).unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XmpMeta;
+
+    #[test]
+    fn test_parse_error_location() {
+        let err = XmpMeta::parse("<x:xmpmeta xmlns:x=\"adobe:ns:meta/\">\n<rdf:RDF>").unwrap_err();
+
+        let err = xmp_error_to_ohos_error(err);
+        assert_eq!(err.kind(), XmpErrorKind::ParseError);
+        assert_eq!(err.line(), Some(2));
+        assert_eq!(err.column(), Some(1));
+        assert_eq!(err.path().as_deref(), Some("x:xmpmeta/rdf:RDF"));
+        assert!(err
+            .to_string()
+            .ends_with(" at line 2, column 1 in x:xmpmeta/rdf:RDF"));
+    }
 }
@@ -35,7 +35,9 @@ impl XmpMeta {
                 XmpValue::String(s) => s,
                 XmpValue::Integer(i) => i.to_string(),
                 XmpValue::Boolean(b) => b.to_string(),
-                XmpValue::DateTime(d) => d,
+                XmpValue::DateTime(d) => d.format(),
+                XmpValue::Real(_) | XmpValue::Rational(_, _) => value.to_string(),
+                XmpValue::Uri(u) => u,
                 _ => format!("{:?}", value),
             })
     }
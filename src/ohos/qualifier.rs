@@ -1,7 +1,10 @@
 //! OpenHarmony bindings for XMP qualifiers
 
+use crate::ohos::error::xmp_error_to_ohos_error;
+use crate::ohos::meta::XmpMeta;
 use crate::types::qualifier::Qualifier as RustQualifier;
 use napi_derive_ohos::napi;
+use napi_ohos::bindgen_prelude::*;
 
 #[napi]
 #[derive(Clone)]
@@ -22,4 +25,89 @@ impl Qualifier {
     pub fn namespace(&self) -> String {
         self.inner.namespace.clone()
     }
+
+    #[napi(getter)]
+    pub fn name(&self) -> String {
+        self.inner.name.clone()
+    }
+
+    #[napi(getter)]
+    pub fn value(&self) -> String {
+        self.inner.value.clone()
+    }
+
+    #[napi]
+    pub fn path(&self) -> String {
+        self.inner.path()
+    }
+}
+
+/// Qualifier operations on `XmpMeta`
+#[napi]
+impl XmpMeta {
+    /// Get a qualifier value
+    #[napi]
+    pub fn get_qualifier(
+        &self,
+        namespace: String,
+        path: String,
+        qual_namespace: String,
+        qual_name: String,
+    ) -> Option<String> {
+        self.inner
+            .get_qualifier(&namespace, &path, &qual_namespace, &qual_name)
+            .map(|value| value.to_string())
+    }
+
+    /// Set a qualifier on an existing property
+    #[napi]
+    pub fn set_qualifier(
+        &mut self,
+        namespace: String,
+        path: String,
+        qual_namespace: String,
+        qual_name: String,
+        value: String,
+    ) -> Result<()> {
+        self.inner
+            .set_qualifier(&namespace, &path, &qual_namespace, &qual_name, &value)
+            .map_err(|e| Error::from_reason(format!("{}", xmp_error_to_ohos_error(e))))
+    }
+
+    /// Delete a qualifier
+    #[napi]
+    pub fn delete_qualifier(
+        &mut self,
+        namespace: String,
+        path: String,
+        qual_namespace: String,
+        qual_name: String,
+    ) -> Result<()> {
+        self.inner
+            .delete_qualifier(&namespace, &path, &qual_namespace, &qual_name)
+            .map_err(|e| Error::from_reason(format!("{}", xmp_error_to_ohos_error(e))))
+    }
+
+    /// Check if a qualifier exists
+    #[napi]
+    pub fn has_qualifier(
+        &self,
+        namespace: String,
+        path: String,
+        qual_namespace: String,
+        qual_name: String,
+    ) -> bool {
+        self.inner
+            .has_qualifier(&namespace, &path, &qual_namespace, &qual_name)
+    }
+
+    /// Get all qualifiers of a property
+    #[napi]
+    pub fn qualifiers(&self, namespace: String, path: String) -> Vec<Qualifier> {
+        self.inner
+            .qualifiers(&namespace, &path)
+            .into_iter()
+            .map(|inner| Qualifier { inner })
+            .collect()
+    }
 }
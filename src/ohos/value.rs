@@ -9,6 +9,9 @@ pub enum XmpValueKind {
     Integer,
     Boolean,
     DateTime,
+    Real,
+    Rational,
+    Uri,
 }
 
 #[napi]
@@ -21,6 +24,10 @@ pub struct XmpValue {
     integer_value: Option<i64>,
     #[allow(dead_code)]
     boolean_value: Option<bool>,
+    #[allow(dead_code)]
+    real_value: Option<f64>,
+    #[allow(dead_code)]
+    rational_value: Option<(i64, i64)>,
 }
 
 #[napi]
@@ -32,6 +39,8 @@ impl XmpValue {
             string_value: Some(s),
             integer_value: None,
             boolean_value: None,
+            real_value: None,
+            rational_value: None,
         }
     }
 
@@ -42,6 +51,8 @@ impl XmpValue {
             string_value: None,
             integer_value: Some(i),
             boolean_value: None,
+            real_value: None,
+            rational_value: None,
         }
     }
 
@@ -52,6 +63,44 @@ impl XmpValue {
             string_value: None,
             integer_value: None,
             boolean_value: Some(b),
+            real_value: None,
+            rational_value: None,
+        }
+    }
+
+    #[napi]
+    pub fn real(r: f64) -> XmpValue {
+        XmpValue {
+            kind: XmpValueKind::Real,
+            string_value: None,
+            integer_value: None,
+            boolean_value: None,
+            real_value: Some(r),
+            rational_value: None,
+        }
+    }
+
+    #[napi]
+    pub fn rational(numerator: i64, denominator: i64) -> XmpValue {
+        XmpValue {
+            kind: XmpValueKind::Rational,
+            string_value: None,
+            integer_value: None,
+            boolean_value: None,
+            real_value: None,
+            rational_value: Some((numerator, denominator)),
+        }
+    }
+
+    #[napi]
+    pub fn uri(uri: String) -> XmpValue {
+        XmpValue {
+            kind: XmpValueKind::Uri,
+            string_value: Some(uri),
+            integer_value: None,
+            boolean_value: None,
+            real_value: None,
+            rational_value: None,
         }
     }
 
@@ -60,3 +109,25 @@ impl XmpValue {
         self.kind
     }
 }
+
+impl From<crate::types::value::XmpValue> for XmpValue {
+    fn from(value: crate::types::value::XmpValue) -> Self {
+        use crate::types::value::XmpValue as RustXmpValue;
+        match value {
+            RustXmpValue::Integer(i) => XmpValue::integer(i),
+            RustXmpValue::Boolean(b) => XmpValue::boolean(b),
+            RustXmpValue::Real(r) => XmpValue::real(r),
+            RustXmpValue::Rational(n, d) => XmpValue::rational(n, d),
+            RustXmpValue::Uri(uri) => XmpValue::uri(uri),
+            RustXmpValue::DateTime(dt) => XmpValue {
+                kind: XmpValueKind::DateTime,
+                string_value: Some(dt.format()),
+                integer_value: None,
+                boolean_value: None,
+                real_value: None,
+                rational_value: None,
+            },
+            other => XmpValue::string(other.to_string()),
+        }
+    }
+}
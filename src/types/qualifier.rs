@@ -7,6 +7,7 @@ use std::fmt;
 
 /// A qualifier for an XMP property
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Qualifier {
     /// The namespace URI of the qualifier
     pub namespace: String,
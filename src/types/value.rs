@@ -2,9 +2,16 @@
 //!
 //! This module defines the value types that can be stored in XMP properties.
 
+use crate::core::node::ArrayType;
+use crate::utils::datetime::XmpDateTime;
 use std::fmt;
 
 /// XMP property value types
+///
+/// With the `serde` feature, values serialize as plain data: strings,
+/// numbers, sequences and maps. Array forms are not kept, and a deserialized
+/// sequence is an ordered array; serialize an [`XmpMeta`](crate::XmpMeta)
+/// to keep them.
 #[derive(Debug, Clone, PartialEq)]
 pub enum XmpValue {
     /// String value
@@ -13,10 +20,19 @@ pub enum XmpValue {
     Integer(i64),
     /// Boolean value
     Boolean(bool),
-    /// Date/time value (ISO 8601 format)
-    DateTime(String),
-    /// Array of values
-    Array(Vec<XmpValue>),
+    /// Real number value
+    Real(f64),
+    /// Rational value as `(numerator, denominator)`, written as `"n/d"`
+    Rational(i64, i64),
+    /// URI value, written as an `rdf:resource` reference
+    Uri(String),
+    /// Date/time value
+    DateTime(XmpDateTime),
+    /// Array of values, with its form (rdf:Seq, rdf:Bag or rdf:Alt)
+    Array(ArrayType, Vec<XmpValue>),
+    /// Language alternative (rdf:Alt whose items all carry `xml:lang`),
+    /// as `(language, text)` pairs in document order
+    AltText(Vec<(String, String)>),
     /// Structure (key-value pairs)
     Structure(std::collections::HashMap<String, XmpValue>),
 }
@@ -53,6 +69,64 @@ impl XmpValue {
             _ => None,
         }
     }
+
+    /// Get the value as a floating point number, if it is numeric or
+    /// can be converted to.
+    ///
+    /// Rationals are divided out; a zero denominator gives `None`.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            XmpValue::String(s) => match parse_rational(s) {
+                Some((n, d)) => XmpValue::Rational(n, d).as_f64(),
+                None => s.trim().parse::<f64>().ok(),
+            },
+            XmpValue::Integer(i) => Some(*i as f64),
+            XmpValue::Real(r) => Some(*r),
+            XmpValue::Rational(_, 0) => None,
+            XmpValue::Rational(n, d) => Some(*n as f64 / *d as f64),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a `(numerator, denominator)` pair, if it is a
+    /// rational or integer type, or can be converted to.
+    pub fn as_rational(&self) -> Option<(i64, i64)> {
+        match self {
+            XmpValue::String(s) => {
+                parse_rational(s).or_else(|| s.trim().parse::<i64>().ok().map(|i| (i, 1)))
+            }
+            XmpValue::Integer(i) => Some((*i, 1)),
+            XmpValue::Rational(n, d) => Some((*n, *d)),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a URI, if it is a URI type
+    pub fn as_uri(&self) -> Option<&str> {
+        match self {
+            XmpValue::Uri(uri) => Some(uri),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a date/time, if it is a date/time type, or
+    /// can be converted to.
+    pub fn as_date_time(&self) -> Option<XmpDateTime> {
+        match self {
+            XmpValue::String(s) => XmpDateTime::parse(s).ok(),
+            XmpValue::DateTime(dt) => Some(dt.clone()),
+            _ => None,
+        }
+    }
+
+    /// Get the array form, if it is an array or a language alternative
+    pub fn array_type(&self) -> Option<ArrayType> {
+        match self {
+            XmpValue::Array(array_type, _) => Some(*array_type),
+            XmpValue::AltText(_) => Some(ArrayType::Alternative),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for XmpValue {
@@ -61,8 +135,12 @@ impl fmt::Display for XmpValue {
             XmpValue::String(s) => write!(f, "{}", s),
             XmpValue::Integer(i) => write!(f, "{}", i),
             XmpValue::Boolean(b) => write!(f, "{}", b),
-            XmpValue::DateTime(dt) => write!(f, "{}", dt),
-            XmpValue::Array(_) => write!(f, "[Array]"),
+            XmpValue::Real(r) => write!(f, "{}", r),
+            XmpValue::Rational(n, d) => write!(f, "{}/{}", n, d),
+            XmpValue::Uri(uri) => write!(f, "{}", uri),
+            XmpValue::DateTime(dt) => write!(f, "{}", dt.format()),
+            XmpValue::Array(_, _) => write!(f, "[Array]"),
+            XmpValue::AltText(_) => write!(f, "[AltText]"),
             XmpValue::Structure(_) => write!(f, "[Structure]"),
         }
     }
@@ -78,13 +156,90 @@ impl serde::ser::Serialize for XmpValue {
             XmpValue::String(s) => serializer.serialize_str(s),
             XmpValue::Integer(i) => serializer.serialize_i64(*i),
             XmpValue::Boolean(b) => serializer.serialize_bool(*b),
-            XmpValue::DateTime(dt) => serializer.serialize_str(dt),
-            XmpValue::Array(arr) => arr.serialize(serializer),
+            XmpValue::Real(r) => serializer.serialize_f64(*r),
+            XmpValue::Rational(n, d) => serializer.serialize_str(&format!("{}/{}", n, d)),
+            XmpValue::Uri(uri) => serializer.serialize_str(uri),
+            XmpValue::DateTime(dt) => serializer.serialize_str(&dt.format()),
+            XmpValue::Array(_, arr) => arr.serialize(serializer),
+            XmpValue::AltText(items) => {
+                use serde::ser::SerializeMap;
+                let mut map = serializer.serialize_map(Some(items.len()))?;
+                for (lang, text) in items {
+                    map.serialize_entry(lang, text)?;
+                }
+                map.end()
+            }
             XmpValue::Structure(structure) => structure.serialize(serializer),
         }
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Deserialize<'de> for XmpValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        struct ValueVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for ValueVisitor {
+            type Value = XmpValue;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a string, number, boolean, sequence or map")
+            }
+
+            fn visit_bool<E: serde::de::Error>(self, v: bool) -> Result<XmpValue, E> {
+                Ok(XmpValue::Boolean(v))
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<XmpValue, E> {
+                Ok(XmpValue::Integer(v))
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<XmpValue, E> {
+                Ok(i64::try_from(v).map_or(XmpValue::Real(v as f64), XmpValue::Integer))
+            }
+
+            fn visit_f64<E: serde::de::Error>(self, v: f64) -> Result<XmpValue, E> {
+                Ok(XmpValue::Real(v))
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<XmpValue, E> {
+                Ok(XmpValue::String(v.to_string()))
+            }
+
+            fn visit_string<E: serde::de::Error>(self, v: String) -> Result<XmpValue, E> {
+                Ok(XmpValue::String(v))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<XmpValue, A::Error>
+            where
+                A: serde::de::SeqAccess<'de>,
+            {
+                let mut items = Vec::new();
+                while let Some(item) = seq.next_element()? {
+                    items.push(item);
+                }
+                Ok(XmpValue::Array(ArrayType::Ordered, items))
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<XmpValue, A::Error>
+            where
+                A: serde::de::MapAccess<'de>,
+            {
+                let mut fields = std::collections::HashMap::new();
+                while let Some((key, value)) = map.next_entry()? {
+                    fields.insert(key, value);
+                }
+                Ok(XmpValue::Structure(fields))
+            }
+        }
+
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
 impl From<String> for XmpValue {
     fn from(s: String) -> Self {
         XmpValue::String(s)
@@ -109,6 +264,24 @@ impl From<bool> for XmpValue {
     }
 }
 
+impl From<f64> for XmpValue {
+    fn from(r: f64) -> Self {
+        XmpValue::Real(r)
+    }
+}
+
+impl From<XmpDateTime> for XmpValue {
+    fn from(dt: XmpDateTime) -> Self {
+        XmpValue::DateTime(dt)
+    }
+}
+
+/// Parse an `"n/d"` rational string
+fn parse_rational(s: &str) -> Option<(i64, i64)> {
+    let (n, d) = s.trim().split_once('/')?;
+    Some((n.trim().parse().ok()?, d.trim().parse().ok()?))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -159,4 +332,86 @@ mod tests {
         let value: XmpValue = true.into();
         assert_eq!(value.as_bool(), Some(true));
     }
+
+    #[test]
+    fn test_xmp_value_array_type() {
+        let value = XmpValue::Array(ArrayType::Ordered, vec!["a".into()]);
+        assert_eq!(value.array_type(), Some(ArrayType::Ordered));
+
+        let value = XmpValue::AltText(vec![("x-default".to_string(), "Title".to_string())]);
+        assert_eq!(value.array_type(), Some(ArrayType::Alternative));
+
+        assert_eq!(XmpValue::from("a").array_type(), None);
+    }
+
+    #[test]
+    fn test_xmp_value_numeric() {
+        let value = XmpValue::Rational(28, 10);
+        assert_eq!(value.as_rational(), Some((28, 10)));
+        assert_eq!(value.as_f64(), Some(2.8));
+        assert_eq!(value.to_string(), "28/10");
+        assert_eq!(XmpValue::Rational(1, 0).as_f64(), None);
+
+        let value: XmpValue = 0.5.into();
+        assert_eq!(value.as_f64(), Some(0.5));
+        assert_eq!(value.as_rational(), None);
+        assert_eq!(value.to_string(), "0.5");
+
+        // Type conversion
+        let value = XmpValue::String("1/125".to_string());
+        assert_eq!(value.as_rational(), Some((1, 125)));
+        assert_eq!(value.as_f64(), Some(0.008));
+        let value = XmpValue::String("-1.25".to_string());
+        assert_eq!(value.as_f64(), Some(-1.25));
+        assert_eq!(value.as_rational(), None);
+        assert_eq!(XmpValue::Integer(3).as_rational(), Some((3, 1)));
+    }
+
+    #[test]
+    fn test_xmp_value_uri_and_date_time() {
+        let value = XmpValue::Uri("https://example.com/license".to_string());
+        assert_eq!(value.as_uri(), Some("https://example.com/license"));
+        assert_eq!(value.as_str(), None);
+
+        let dt = XmpDateTime::parse("2024-01-01T12:00:00Z").unwrap();
+        let value: XmpValue = dt.clone().into();
+        assert_eq!(value.as_date_time(), Some(dt));
+        assert_eq!(value.to_string(), "2024-01-01T12:00:00Z");
+
+        let value = XmpValue::String("2024-01-01".to_string());
+        assert_eq!(value.as_date_time().map(|dt| dt.day), Some(1));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_xmp_value_serde() {
+        let value = XmpValue::Array(
+            ArrayType::Unordered,
+            vec![
+                XmpValue::DateTime(XmpDateTime::parse("2024-01-01").unwrap()),
+                XmpValue::AltText(vec![("x-default".to_string(), "a".to_string())]),
+                XmpValue::Integer(3),
+            ],
+        );
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(json, r#"["2024-01-01",{"x-default":"a"},3]"#);
+
+        // Plain data comes back as strings, numbers, ordered arrays and structures
+        let value = serde_json::from_str::<XmpValue>(r#"[true,-1,2.5,"x",{"k":"v"}]"#).unwrap();
+        assert_eq!(
+            value,
+            XmpValue::Array(
+                ArrayType::Ordered,
+                vec![
+                    XmpValue::Boolean(true),
+                    XmpValue::Integer(-1),
+                    XmpValue::Real(2.5),
+                    XmpValue::String("x".to_string()),
+                    XmpValue::Structure(
+                        [("k".to_string(), XmpValue::String("v".to_string()))].into()
+                    ),
+                ],
+            )
+        );
+    }
 }
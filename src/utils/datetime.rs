@@ -59,6 +59,98 @@ impl XmpDateTime {
         }
     }
 
+    /// Create a UTC date/time from a Unix timestamp
+    ///
+    /// `secs` counts seconds since 1970-01-01T00:00:00Z (negative before).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use xmpkit::utils::datetime::XmpDateTime;
+    ///
+    /// let dt = XmpDateTime::from_unix_timestamp(1_700_000_000, 0);
+    /// assert_eq!(dt.format(), "2023-11-14T22:13:20Z");
+    /// ```
+    pub fn from_unix_timestamp(secs: i64, nanosecond: u32) -> Self {
+        let days = secs.div_euclid(86_400);
+        let seconds_of_day = secs.rem_euclid(86_400);
+
+        // Civil date from days since the epoch (Howard Hinnant's algorithm)
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        Self {
+            year: year as i32,
+            month: month as u8,
+            day: day as u8,
+            hour: (seconds_of_day / 3600) as u8,
+            minute: (seconds_of_day % 3600 / 60) as u8,
+            second: (seconds_of_day % 60) as u8,
+            nanosecond: nanosecond.min(999_999_999),
+            has_date: true,
+            has_time: true,
+            has_timezone: true,
+            tz_sign: 0,
+            tz_hour: 0,
+            tz_minute: 0,
+        }
+    }
+
+    /// Seconds since 1970-01-01T00:00:00Z (the inverse of
+    /// [`XmpDateTime::from_unix_timestamp`])
+    ///
+    /// Missing date components count as 1 (e.g., `2024` is 2024-01-01), and
+    /// a date/time without timezone is taken as UTC. Nanoseconds are dropped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use xmpkit::utils::datetime::XmpDateTime;
+    ///
+    /// let dt = XmpDateTime::parse("2023-11-15T00:13:20+02:00").unwrap();
+    /// assert_eq!(dt.to_unix_timestamp(), 1_700_000_000);
+    /// ```
+    pub fn to_unix_timestamp(&self) -> i64 {
+        let month = i64::from(self.month.clamp(1, 12));
+        let day = i64::from(self.day.max(1));
+
+        // Days since the epoch from the civil date (Howard Hinnant's algorithm)
+        let year = i64::from(self.year) - i64::from(month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        let days = era * 146_097 + day_of_era - 719_468;
+
+        let seconds_of_day =
+            i64::from(self.hour) * 3600 + i64::from(self.minute) * 60 + i64::from(self.second);
+        let offset = i64::from(self.tz_sign)
+            * (i64::from(self.tz_hour) * 3600 + i64::from(self.tz_minute) * 60);
+        days * 86_400 + seconds_of_day - offset
+    }
+
+    /// The current date/time in UTC, to the second
+    ///
+    /// Not available on Wasm, where there is no system clock; use
+    /// [`XmpDateTime::from_unix_timestamp`] with a time from the host instead.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn now() -> Self {
+        use std::time::{SystemTime, UNIX_EPOCH};
+        let secs = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(elapsed) => elapsed.as_secs() as i64,
+            Err(before) => -(before.duration().as_secs() as i64),
+        };
+        Self::from_unix_timestamp(secs, 0)
+    }
+
     /// Parse an XMP date/time string
     ///
     /// XMP date/time format:
@@ -509,6 +601,21 @@ impl Default for XmpDateTime {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for XmpDateTime {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.format())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for XmpDateTime {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        XmpDateTime::parse(&s).map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -647,4 +754,34 @@ mod tests {
             assert_eq!(dt.second, dt2.second);
         }
     }
+
+    #[test]
+    fn test_to_unix_timestamp() {
+        for secs in [0, 951_782_400, -1, 1_700_000_000, -62_135_596_800] {
+            assert_eq!(
+                XmpDateTime::from_unix_timestamp(secs, 0).to_unix_timestamp(),
+                secs
+            );
+        }
+        let dt = XmpDateTime::parse("2024-03-01T08:30:00-05:00").unwrap();
+        assert_eq!(dt.to_unix_timestamp(), 1_709_299_800);
+        let dt = XmpDateTime::parse("2024").unwrap();
+        assert_eq!(dt.to_unix_timestamp(), 1_704_067_200);
+    }
+
+    #[test]
+    fn test_from_unix_timestamp() {
+        assert_eq!(
+            XmpDateTime::from_unix_timestamp(0, 0).format(),
+            "1970-01-01T00:00:00Z"
+        );
+        assert_eq!(
+            XmpDateTime::from_unix_timestamp(951_782_400, 500_000_000).format(),
+            "2000-02-29T00:00:00.5Z"
+        );
+        assert_eq!(
+            XmpDateTime::from_unix_timestamp(-1, 0).format(),
+            "1969-12-31T23:59:59Z"
+        );
+    }
 }
@@ -3,3 +3,4 @@
 //! This module contains utility functions used throughout the XMP Toolkit.
 
 pub mod datetime;
+pub mod uuid;
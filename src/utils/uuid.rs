@@ -0,0 +1,100 @@
+//! UUID generation
+//!
+//! XMP Media Management identifiers (`xmp.did:...`, `xmp.iid:...`) are built
+//! from random version 4 UUIDs. The randomness comes from the standard
+//! library's hasher seeding, mixed with a counter and the system clock, so
+//! identifiers are unique but not suitable for cryptographic use.
+//!
+//! On WebAssembly, the hasher seeding is fixed and there is no clock, so the
+//! `wasm` feature takes the randomness from the host's
+//! `crypto.getRandomValues` (or `Math.random` where it is missing). Without
+//! that feature, identifiers generated on WebAssembly are only unique within
+//! one instance of the module.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+fn random_u64() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        use std::time::{SystemTime, UNIX_EPOCH};
+        if let Ok(elapsed) = SystemTime::now().duration_since(UNIX_EPOCH) {
+            hasher.write_u128(elapsed.as_nanos());
+        }
+    }
+    #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+    hasher.write_u64(host_random_u64());
+    hasher.finish()
+}
+
+/// Random bits from the JavaScript host
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+fn host_random_u64() -> u64 {
+    use js_sys::{Function, Reflect, Uint8Array};
+    use wasm_bindgen::{JsCast, JsValue};
+
+    let crypto_random = || -> Option<u64> {
+        let crypto = Reflect::get(&js_sys::global(), &JsValue::from_str("crypto")).ok()?;
+        let get_random_values = Reflect::get(&crypto, &JsValue::from_str("getRandomValues"))
+            .ok()?
+            .dyn_into::<Function>()
+            .ok()?;
+        let array = Uint8Array::new_with_length(8);
+        get_random_values.call1(&crypto, &array).ok()?;
+        let mut bytes = [0u8; 8];
+        array.copy_to(&mut bytes);
+        Some(u64::from_le_bytes(bytes))
+    };
+    crypto_random().unwrap_or_else(|| {
+        js_sys::Math::random().to_bits() ^ js_sys::Date::now().to_bits().rotate_left(32)
+    })
+}
+
+/// Generate a random (version 4) UUID in its lowercase hyphenated form
+///
+/// # Example
+///
+/// ```rust
+/// use xmpkit::utils::uuid::new_uuid;
+///
+/// let uuid = new_uuid();
+/// assert_eq!(uuid.len(), 36);
+/// assert_eq!(&uuid[14..15], "4");
+/// ```
+pub fn new_uuid() -> String {
+    let mut bytes = [0u8; 16];
+    bytes[..8].copy_from_slice(&random_u64().to_be_bytes());
+    bytes[8..].copy_from_slice(&random_u64().to_be_bytes());
+    // Version 4, RFC 4122 variant
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_uuid() {
+        let a = new_uuid();
+        let b = new_uuid();
+        assert_ne!(a, b);
+        assert_eq!(a.matches('-').count(), 4);
+        assert!(matches!(&a[19..20], "8" | "9" | "a" | "b"));
+    }
+}
@@ -1,6 +1,6 @@
 //! WebAssembly error handling for XMP operations
 
-use crate::core::error::XmpError as RustXmpError;
+use crate::core::error::{ParseLocation, XmpError as RustXmpError};
 use wasm_bindgen::prelude::*;
 
 /// WebAssembly error type for XMP operations
@@ -18,6 +18,9 @@ use wasm_bindgen::prelude::*;
 ///         if (error.kind === XmpErrorKind.BadParam) {
 ///             console.log("Bad parameter error:", error.message);
 ///         }
+///         if (error.kind === XmpErrorKind.ParseError && error.line !== undefined) {
+///             console.log(`Line ${error.line}, column ${error.column} in ${error.path}`);
+///         }
 ///     }
 /// }
 /// ```
@@ -25,6 +28,7 @@ use wasm_bindgen::prelude::*;
 pub struct XmpError {
     kind: XmpErrorKind,
     message: String,
+    location: Option<ParseLocation>,
 }
 
 impl std::fmt::Display for XmpError {
@@ -70,6 +74,8 @@ pub enum XmpErrorKind {
     NotFound,
     /// Not supported error
     NotSupported,
+    /// Limit exceeded error
+    LimitExceeded,
 }
 
 #[wasm_bindgen]
@@ -85,21 +91,70 @@ impl XmpError {
     pub fn message(&self) -> String {
         self.message.clone()
     }
+
+    /// Get the byte offset of a parse error in the packet, if known
+    #[wasm_bindgen(getter)]
+    pub fn offset(&self) -> Option<u32> {
+        self.location
+            .as_ref()
+            .map(|location| clamp_to_u32(location.offset))
+    }
+
+    /// Get the line of a parse error (starting at 1), if known
+    #[wasm_bindgen(getter)]
+    pub fn line(&self) -> Option<u32> {
+        self.location
+            .as_ref()
+            .map(|location| clamp_to_u32(location.line))
+    }
+
+    /// Get the column of a parse error (in bytes, starting at 1), if known
+    #[wasm_bindgen(getter)]
+    pub fn column(&self) -> Option<u32> {
+        self.location
+            .as_ref()
+            .map(|location| clamp_to_u32(location.column))
+    }
+
+    /// Get the element path of a parse error (e.g.,
+    /// `rdf:RDF/rdf:Description/dc:creator/rdf:Seq`), if known
+    #[wasm_bindgen(getter)]
+    pub fn path(&self) -> Option<String> {
+        self.location.as_ref().map(ParseLocation::path_string)
+    }
+
+    /// Get a short excerpt of the source at a parse error, if known
+    #[wasm_bindgen(getter)]
+    pub fn snippet(&self) -> Option<String> {
+        self.location
+            .as_ref()
+            .map(|location| location.snippet.clone())
+    }
 }
 
 /// Convert Rust XmpError to WebAssembly XmpError
 pub(crate) fn xmp_error_to_wasm_error(err: RustXmpError) -> XmpError {
+    let location = err.parse_location().cloned();
     let (kind, message) = match &err {
         RustXmpError::BadParam(msg) => (XmpErrorKind::BadParam, msg.clone()),
         RustXmpError::BadValue(msg) => (XmpErrorKind::BadValue, msg.clone()),
         RustXmpError::BadSchema(msg) => (XmpErrorKind::BadSchema, msg.clone()),
         RustXmpError::BadXPath(msg) => (XmpErrorKind::BadXPath, msg.clone()),
-        RustXmpError::ParseError(msg) => (XmpErrorKind::ParseError, msg.clone()),
+        RustXmpError::ParseError(info) => (XmpErrorKind::ParseError, info.message.clone()),
         RustXmpError::SerializationError(msg) => (XmpErrorKind::SerializationError, msg.clone()),
         RustXmpError::IoError(io_err) => (XmpErrorKind::IoError, io_err.to_string()),
         RustXmpError::InternalError(msg) => (XmpErrorKind::InternalError, msg.clone()),
         RustXmpError::NotFound(msg) => (XmpErrorKind::NotFound, msg.clone()),
         RustXmpError::NotSupported(msg) => (XmpErrorKind::NotSupported, msg.clone()),
+        RustXmpError::LimitExceeded(msg) => (XmpErrorKind::LimitExceeded, msg.clone()),
     };
-    XmpError { kind, message }
+    XmpError {
+        kind,
+        message,
+        location,
+    }
+}
+
+fn clamp_to_u32(value: u64) -> u32 {
+    u32::try_from(value).unwrap_or(u32::MAX)
 }
@@ -1,6 +1,8 @@
 //! WebAssembly bindings for XMP qualifiers
 
 use crate::types::qualifier::Qualifier as RustQualifier;
+use crate::wasm::error::{xmp_error_to_wasm_error, XmpError};
+use crate::wasm::meta::XmpMeta;
 use wasm_bindgen::prelude::*;
 
 /// A qualifier for an XMP property
@@ -51,3 +53,84 @@ impl Qualifier {
         self.inner.path()
     }
 }
+
+/// Qualifier operations on `XmpMeta`
+///
+/// # Example
+///
+/// ```javascript
+/// meta.append_array_item("http://purl.org/dc/elements/1.1/", "creator", "Alice");
+/// meta.set_qualifier("http://purl.org/dc/elements/1.1/", "creator[1]",
+///     "http://ns.adobe.com/xap/1.0/", "Role", "author");
+/// const role = meta.get_qualifier("http://purl.org/dc/elements/1.1/", "creator[1]",
+///     "http://ns.adobe.com/xap/1.0/", "Role");
+/// ```
+#[wasm_bindgen]
+impl XmpMeta {
+    /// Get a qualifier value, or null if not found
+    ///
+    /// # Arguments
+    /// * `namespace` - Namespace URI of the property
+    /// * `path` - Property path (e.g., "creator[1]")
+    /// * `qual_namespace` - Namespace URI of the qualifier
+    /// * `qual_name` - Qualifier name
+    pub fn get_qualifier(
+        &self,
+        namespace: &str,
+        path: &str,
+        qual_namespace: &str,
+        qual_name: &str,
+    ) -> Option<String> {
+        self.inner
+            .get_qualifier(namespace, path, qual_namespace, qual_name)
+            .map(|value| value.to_string())
+    }
+
+    /// Set a qualifier on an existing property
+    pub fn set_qualifier(
+        &mut self,
+        namespace: &str,
+        path: &str,
+        qual_namespace: &str,
+        qual_name: &str,
+        value: &str,
+    ) -> Result<(), XmpError> {
+        self.inner
+            .set_qualifier(namespace, path, qual_namespace, qual_name, value)
+            .map_err(xmp_error_to_wasm_error)
+    }
+
+    /// Delete a qualifier
+    pub fn delete_qualifier(
+        &mut self,
+        namespace: &str,
+        path: &str,
+        qual_namespace: &str,
+        qual_name: &str,
+    ) -> Result<(), XmpError> {
+        self.inner
+            .delete_qualifier(namespace, path, qual_namespace, qual_name)
+            .map_err(xmp_error_to_wasm_error)
+    }
+
+    /// Check if a qualifier exists
+    pub fn has_qualifier(
+        &self,
+        namespace: &str,
+        path: &str,
+        qual_namespace: &str,
+        qual_name: &str,
+    ) -> bool {
+        self.inner
+            .has_qualifier(namespace, path, qual_namespace, qual_name)
+    }
+
+    /// Get all qualifiers of a property
+    pub fn qualifiers(&self, namespace: &str, path: &str) -> Vec<Qualifier> {
+        self.inner
+            .qualifiers(namespace, path)
+            .into_iter()
+            .map(|inner| Qualifier { inner })
+            .collect()
+    }
+}
@@ -14,6 +14,12 @@ pub enum XmpValueKind {
     Boolean,
     /// Date/time value
     DateTime,
+    /// Real number value
+    Real,
+    /// Rational value
+    Rational,
+    /// URI value
+    Uri,
 }
 
 /// XMP property value types
@@ -26,6 +32,8 @@ pub struct XmpValue {
     string_value: Option<String>,
     integer_value: Option<i64>,
     boolean_value: Option<bool>,
+    real_value: Option<f64>,
+    rational_value: Option<(i64, i64)>,
 }
 
 #[wasm_bindgen]
@@ -38,6 +46,8 @@ impl XmpValue {
             string_value: Some(s),
             integer_value: None,
             boolean_value: None,
+            real_value: None,
+            rational_value: None,
         }
     }
 
@@ -48,6 +58,8 @@ impl XmpValue {
             string_value: None,
             integer_value: Some(i),
             boolean_value: None,
+            real_value: None,
+            rational_value: None,
         }
     }
 
@@ -58,6 +70,8 @@ impl XmpValue {
             string_value: None,
             integer_value: None,
             boolean_value: Some(b),
+            real_value: None,
+            rational_value: None,
         }
     }
 
@@ -68,6 +82,44 @@ impl XmpValue {
             string_value: Some(dt),
             integer_value: None,
             boolean_value: None,
+            real_value: None,
+            rational_value: None,
+        }
+    }
+
+    /// Create a real number value
+    pub fn real(r: f64) -> XmpValue {
+        XmpValue {
+            kind: XmpValueKind::Real,
+            string_value: None,
+            integer_value: None,
+            boolean_value: None,
+            real_value: Some(r),
+            rational_value: None,
+        }
+    }
+
+    /// Create a rational value
+    pub fn rational(numerator: i64, denominator: i64) -> XmpValue {
+        XmpValue {
+            kind: XmpValueKind::Rational,
+            string_value: None,
+            integer_value: None,
+            boolean_value: None,
+            real_value: None,
+            rational_value: Some((numerator, denominator)),
+        }
+    }
+
+    /// Create a URI value
+    pub fn uri(uri: String) -> XmpValue {
+        XmpValue {
+            kind: XmpValueKind::Uri,
+            string_value: Some(uri),
+            integer_value: None,
+            boolean_value: None,
+            real_value: None,
+            rational_value: None,
         }
     }
 
@@ -100,4 +152,65 @@ impl XmpValue {
             None
         }
     }
+
+    /// Get the value as a number, if it is an integer, real or rational type
+    pub fn as_f64(&self) -> Option<f64> {
+        self.to_rust().as_f64()
+    }
+
+    /// Get the value as `[numerator, denominator]`, if it is a rational type
+    pub fn as_rational(&self) -> Option<Vec<i64>> {
+        self.rational_value.map(|(n, d)| vec![n, d])
+    }
+
+    /// Get the value as a URI, if it is a URI type
+    pub fn as_uri(&self) -> Option<String> {
+        if self.kind == XmpValueKind::Uri {
+            self.string_value.clone()
+        } else {
+            None
+        }
+    }
+}
+
+impl XmpValue {
+    /// Convert to the core value type
+    ///
+    /// Date/time strings that do not parse are kept as strings.
+    pub(crate) fn to_rust(&self) -> crate::types::value::XmpValue {
+        use crate::types::value::XmpValue as RustXmpValue;
+        use crate::utils::datetime::XmpDateTime;
+
+        let string = || self.string_value.clone().unwrap_or_default();
+        match self.kind {
+            XmpValueKind::String => RustXmpValue::String(string()),
+            XmpValueKind::Integer => RustXmpValue::Integer(self.integer_value.unwrap_or_default()),
+            XmpValueKind::Boolean => RustXmpValue::Boolean(self.boolean_value.unwrap_or_default()),
+            XmpValueKind::DateTime => match XmpDateTime::parse(&string()) {
+                Ok(dt) => RustXmpValue::DateTime(dt),
+                Err(_) => RustXmpValue::String(string()),
+            },
+            XmpValueKind::Real => RustXmpValue::Real(self.real_value.unwrap_or_default()),
+            XmpValueKind::Rational => {
+                let (n, d) = self.rational_value.unwrap_or((0, 1));
+                RustXmpValue::Rational(n, d)
+            }
+            XmpValueKind::Uri => RustXmpValue::Uri(string()),
+        }
+    }
+}
+
+impl From<crate::types::value::XmpValue> for XmpValue {
+    fn from(value: crate::types::value::XmpValue) -> Self {
+        use crate::types::value::XmpValue as RustXmpValue;
+        match value {
+            RustXmpValue::Integer(i) => XmpValue::integer(i),
+            RustXmpValue::Boolean(b) => XmpValue::boolean(b),
+            RustXmpValue::Real(r) => XmpValue::real(r),
+            RustXmpValue::Rational(n, d) => XmpValue::rational(n, d),
+            RustXmpValue::Uri(uri) => XmpValue::uri(uri),
+            RustXmpValue::DateTime(dt) => XmpValue::date_time(dt.format()),
+            other => XmpValue::string(other.to_string()),
+        }
+    }
 }
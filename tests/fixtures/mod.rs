@@ -10,7 +10,8 @@
 //! └── data/           # Test data files
 //!     ├── image2.jpg
 //!     ├── no_xmp.txt
-//!     └── Purple Square.psd
+//!     ├── Purple Square.psd
+//!     └── rdf/        # RDF/XML conformance packets
 //! ```
 //!
 //! ## Usage
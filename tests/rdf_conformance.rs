@@ -0,0 +1,262 @@
+//! RDF/XML conformance tests
+//!
+//! The packets in `tests/fixtures/data/rdf/` follow the examples of the XMP
+//! specification (Part 1, chapter 7) and the packets written by Lightroom
+//! and Bridge. Each valid packet is checked for its values and for surviving
+//! a serialize/parse round trip unchanged.
+
+mod fixtures;
+
+use fixtures::fixture_path;
+use std::fs;
+use xmpkit::core::parser::{ParseOptions, ParseWarningKind};
+use xmpkit::{XmpError, XmpMeta, XmpValue};
+
+fn parse_fixture(name: &str) -> XmpMeta {
+    let xml = fs::read_to_string(fixture_path(&format!("rdf/{}", name))).unwrap();
+    XmpMeta::parse(&xml).unwrap_or_else(|e| panic!("{}: {}", name, e))
+}
+
+fn value(meta: &XmpMeta, namespace: &str, path: &str) -> Option<String> {
+    meta.get_property(namespace, path)
+        .map(|value| value.to_string())
+}
+
+/// Serialize and parse again, expecting the same metadata
+fn assert_round_trip(name: &str) -> XmpMeta {
+    let meta = parse_fixture(name);
+    let packet = meta.serialize_packet().unwrap();
+    let reparsed = XmpMeta::parse(&packet).unwrap_or_else(|e| panic!("{}: {}", name, e));
+    let diff = meta.diff(&reparsed);
+    assert!(diff.is_empty(), "{} changed: {:#?}", name, diff.changes);
+    assert_eq!(meta.about_uri(), reparsed.about_uri());
+    meta
+}
+
+#[test]
+fn simple_properties() {
+    let meta = assert_round_trip("simple.xmp");
+    assert_eq!(
+        value(&meta, "xmp", "CreatorTool").as_deref(),
+        Some("Adobe Photoshop 25.0")
+    );
+    assert_eq!(value(&meta, "xmp", "Rating").as_deref(), Some("3"));
+    assert_eq!(
+        meta.get_property("xmpRights", "WebStatement"),
+        Some(XmpValue::Uri("http://www.example.com/rights.html".into()))
+    );
+    assert_eq!(value(&meta, "xmpRights", "Marked").as_deref(), Some("True"));
+}
+
+#[test]
+fn structures() {
+    let meta = assert_round_trip("structures.xmp");
+    let field = |namespace: &str, path: &str, name: &str| {
+        meta.get_struct_field(namespace, path, name)
+            .map(|value| value.to_string())
+    };
+    assert_eq!(
+        field("xmpTPg", "MaxPageSize", "stDim:w").as_deref(),
+        Some("4")
+    );
+    assert_eq!(
+        field("xmpTPg", "MaxPageSize", "stDim:unit").as_deref(),
+        Some("inch")
+    );
+    assert_eq!(
+        field("xmpMM", "DerivedFrom", "stRef:documentID").as_deref(),
+        Some("xmp.did:0E8B2F96")
+    );
+    assert_eq!(
+        field("xmpMM", "ManagedFrom", "stRef:filePath").as_deref(),
+        Some("/images/original.tif")
+    );
+    assert_eq!(
+        field(
+            "Iptc4xmpCore",
+            "CreatorContactInfo",
+            "Iptc4xmpCore:CiAdrCity"
+        )
+        .as_deref(),
+        Some("Berlin")
+    );
+}
+
+#[test]
+fn arrays() {
+    let meta = assert_round_trip("arrays.xmp");
+    assert_eq!(meta.get_array_size("dc", "subject"), Some(2));
+    assert_eq!(
+        meta.get_localized_text("dc", "title", "fr", "fr-FR"),
+        Some(("Coucher de soleil".to_string(), "fr-FR".to_string()))
+    );
+    // xml:lang on the array is kept as a qualifier of the array
+    assert_eq!(
+        meta.get_qualifier("dc", "description", "xml", "lang")
+            .map(|value| value.to_string())
+            .as_deref(),
+        Some("en-US")
+    );
+    assert_eq!(meta.get_array_size("xmpMM", "History"), Some(3));
+    for (index, action) in ["created", "saved", "converted"].iter().enumerate() {
+        let path = format!("History[{}]/stEvt:action", index + 1);
+        assert_eq!(value(&meta, "xmpMM", &path).as_deref(), Some(*action));
+    }
+    assert_eq!(meta.get_array_size("xmpMM", "Versions"), Some(0));
+}
+
+#[test]
+fn qualifiers() {
+    let meta = assert_round_trip("qualifiers.xmp");
+    let qualifier = |path: &str, name: &str| {
+        meta.get_qualifier("dc", path, "ns", name)
+            .map(|value| value.to_string())
+    };
+    assert_eq!(
+        value(&meta, "dc", "creator[1]").as_deref(),
+        Some("Jane Smith")
+    );
+    assert_eq!(
+        qualifier("creator[1]", "role").as_deref(),
+        Some("photographer")
+    );
+    assert_eq!(
+        value(&meta, "dc", "creator[2]").as_deref(),
+        Some("John Doe")
+    );
+    assert_eq!(qualifier("creator[2]", "role").as_deref(), Some("editor"));
+
+    assert_eq!(value(&meta, "dc", "source").as_deref(), Some("Archive"));
+    assert_eq!(qualifier("source", "box").as_deref(), Some("12"));
+    assert_eq!(
+        meta.get_qualifier("dc", "source", "xml", "lang")
+            .map(|value| value.to_string())
+            .as_deref(),
+        Some("en-US")
+    );
+
+    assert_eq!(value(&meta, "ns", "shortForm").as_deref(), Some("value"));
+    assert_eq!(
+        meta.get_qualifier("ns", "shortForm", "ns", "qual")
+            .map(|value| value.to_string())
+            .as_deref(),
+        Some("qualifier")
+    );
+    assert_eq!(value(&meta, "ns", "literal").as_deref(), Some("42"));
+    assert_eq!(
+        meta.get_qualifier("ns", "literal", "ns", "unit")
+            .map(|value| value.to_string())
+            .as_deref(),
+        Some("mm")
+    );
+}
+
+#[test]
+fn identifiers_and_datatypes() {
+    let meta = assert_round_trip("identifiers.xmp");
+    assert_eq!(
+        meta.about_uri(),
+        Some("uuid:9A3B7F2E-6E1C-11DB-9E6F-C9D7A6A4E5C1")
+    );
+    assert_eq!(value(&meta, "xmp", "Rating").as_deref(), Some("5"));
+    assert_eq!(value(&meta, "xmp", "Label").as_deref(), Some("Red"));
+    assert_eq!(value(&meta, "xmp", "Nickname").as_deref(), Some("Sunset"));
+    assert!(meta.has_property("xmpMM", "DerivedFrom"));
+    assert_eq!(
+        meta.get_struct_field("xmpMM", "ManagedFrom", "stRef:filePath")
+            .map(|value| value.to_string())
+            .as_deref(),
+        Some("/a.tif")
+    );
+}
+
+#[test]
+fn lightroom_packet() {
+    let meta = assert_round_trip("lightroom.xmp");
+    assert_eq!(
+        value(&meta, "crs", "Exposure2012").as_deref(),
+        Some("+0.35")
+    );
+    assert_eq!(meta.get_array_size("crs", "ToneCurvePV2012"), Some(2));
+    assert_eq!(
+        value(&meta, "crs", "Look/crs:Parameters/crs:ToneCurvePV2012[2]").as_deref(),
+        Some("22, 16")
+    );
+    assert_eq!(
+        value(&meta, "crs", "Look/crs:Group[1]").as_deref(),
+        Some("Profiles")
+    );
+    assert_eq!(
+        value(
+            &meta,
+            "crs",
+            "MaskGroupBasedCorrections[1]/crs:CorrectionMasks[1]/crs:MaskName"
+        )
+        .as_deref(),
+        Some("Subject")
+    );
+    assert_eq!(
+        value(&meta, "exif", "Flash/exif:Mode").as_deref(),
+        Some("2")
+    );
+    assert_eq!(
+        value(&meta, "xmpMM", "History[2]/stEvt:changed").as_deref(),
+        Some("/metadata")
+    );
+    assert_eq!(
+        value(&meta, "lr", "hierarchicalSubject[1]").as_deref(),
+        Some("Places|Germany|Berlin")
+    );
+}
+
+#[test]
+fn bridge_packet() {
+    let meta = assert_round_trip("bridge.xmp");
+    assert_eq!(value(&meta, "xmp", "Label").as_deref(), Some("Select"));
+    assert_eq!(
+        meta.get_array_size("photoshop", "DocumentAncestors"),
+        Some(2)
+    );
+    assert_eq!(
+        value(&meta, "dc", "description[1]").as_deref(),
+        Some("Fishing boats & fog")
+    );
+    assert_eq!(
+        value(&meta, "xmpMM", "History[1]/stEvt:softwareAgent").as_deref(),
+        Some("Adobe Photoshop 21.0 (Macintosh)")
+    );
+    assert_eq!(
+        value(&meta, "xmpMM", "DerivedFrom/stRef:instanceID").as_deref(),
+        Some("xmp.iid:1a2b3c4d-5e6f-7a8b-9c0d-1e2f3a4b5c6d")
+    );
+    assert_eq!(
+        value(&meta, "exif", "PixelXDimension").as_deref(),
+        Some("4000")
+    );
+}
+
+#[test]
+fn parse_type_literal_is_rejected() {
+    let xml = fs::read_to_string(fixture_path("rdf/parse_type_literal.xmp")).unwrap();
+    assert!(matches!(XmpMeta::parse(&xml), Err(XmpError::ParseError(_))));
+}
+
+#[test]
+fn parse_type_collection_is_rejected() {
+    let xml = fs::read_to_string(fixture_path("rdf/parse_type_collection.xmp")).unwrap();
+    assert!(matches!(XmpMeta::parse(&xml), Err(XmpError::ParseError(_))));
+}
+
+#[test]
+fn rdf_value_with_composite_qualifier_is_rejected() {
+    let xml = fs::read_to_string(fixture_path("rdf/rdf_value_composite_qualifier.xmp")).unwrap();
+    assert!(matches!(XmpMeta::parse(&xml), Err(XmpError::ParseError(_))));
+
+    // Lenient mode skips the whole property with a warning
+    let (meta, warnings) =
+        XmpMeta::parse_with_options(&xml, &ParseOptions::default().lenient()).unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].kind, ParseWarningKind::InvalidRdf);
+    assert!(!meta.has_property("dc", "source"));
+    assert_eq!(value(&meta, "dc", "format").as_deref(), Some("image/jpeg"));
+}
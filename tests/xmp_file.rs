@@ -35,6 +35,42 @@ mod native_tests {
         let result = file.open("doesnotexist.jpg");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn record_save_event_on_close() {
+        use xmpkit::XmpOptions;
+
+        if !fixture_exists("image2.jpg") {
+            eprintln!("Skipping test: fixture image2.jpg not found");
+            return;
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("image2.jpg");
+        std::fs::copy(fixture_path("image2.jpg"), &path).unwrap();
+
+        let mut file = XmpFile::new();
+        file.open_with(
+            &path,
+            XmpOptions::default().for_update().record_save_event(),
+        )
+        .unwrap();
+        let mut meta = file.get_xmp().cloned().unwrap_or_else(XmpMeta::new);
+        meta.media_management_mut().init_ids().unwrap();
+        let instance_id = meta.media_management().instance_id();
+        file.put_xmp(meta);
+        file.try_close().unwrap();
+
+        let mut file = XmpFile::new();
+        file.open(&path).unwrap();
+        let meta = file.get_xmp().unwrap();
+        let mm = meta.media_management();
+        assert_ne!(mm.instance_id(), instance_id);
+        let saved = mm.history().pop().unwrap();
+        assert_eq!(saved.action, "saved");
+        assert_eq!(saved.instance_id, mm.instance_id());
+        assert!(saved.when.is_some());
+    }
 }
 
 mod wasm_compatible_tests {
@@ -66,6 +102,32 @@ mod wasm_compatible_tests {
         file.put_xmp(meta);
         assert!(file.get_xmp().is_some());
     }
+
+    #[test]
+    fn scan_packets() {
+        if !fixture_exists("Purple Square.psd") {
+            eprintln!("Skipping test: fixture Purple Square.psd not found");
+            return;
+        }
+        let data = std::fs::read(fixture_path("Purple Square.psd")).unwrap();
+
+        let packets = XmpFile::scan_packets(&data);
+        assert_eq!(packets.len(), 1);
+        let packet = &packets[0];
+        assert_eq!(
+            &data[packet.offset..packet.offset + packet.length],
+            &packet.bytes[..]
+        );
+        assert!(packet.bytes.starts_with(b"<?xpacket begin"));
+        assert!(packet.bytes.ends_with(b"?>"));
+
+        let main = XmpFile::main_packet(&packets).unwrap();
+        let meta = main.parse().unwrap();
+        let scanned = XmpFile::scan_for_xmp_packet(&data).unwrap().unwrap();
+        assert!(meta.diff(&scanned).is_empty());
+
+        assert!(XmpFile::scan_packets(b"no packets here").is_empty());
+    }
 }
 
 /// Tests for streaming read functionality (Issue #31)
@@ -307,6 +307,82 @@ mod property_operations {
     }
 }
 
+mod from_bytes {
+    use xmpkit::{XmpError, XmpMeta};
+
+    const PACKET: &str = "<?xpacket begin=\"\u{FEFF}\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?>
+<x:xmpmeta xmlns:x=\"adobe:ns:meta/\">
+  <rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\"
+           xmlns:dc=\"http://purl.org/dc/elements/1.1/\">
+    <rdf:Description rdf:about=\"\">
+      <dc:title>
+        <rdf:Alt>
+          <rdf:li xml:lang=\"x-default\">Caf\u{E9} \u{1F4F7} \u{6771}\u{4EAC}</rdf:li>
+        </rdf:Alt>
+      </dc:title>
+    </rdf:Description>
+  </rdf:RDF>
+</x:xmpmeta>
+<?xpacket end=\"w\"?>";
+
+    fn title(meta: &XmpMeta) -> String {
+        meta.get_property("dc", "title[1]").unwrap().to_string()
+    }
+
+    #[test]
+    fn parse_bytes_utf8() {
+        let meta = XmpMeta::parse_bytes(PACKET.as_bytes()).unwrap();
+        assert_eq!(title(&meta), "Caf\u{E9} \u{1F4F7} \u{6771}\u{4EAC}");
+
+        // With a leading byte order mark
+        let bytes = [b"\xEF\xBB\xBF".as_slice(), PACKET.as_bytes()].concat();
+        assert!(XmpMeta::parse_bytes(&bytes).is_ok());
+    }
+
+    #[test]
+    fn parse_bytes_utf16_and_utf32() {
+        let encodings: [Vec<u8>; 4] = [
+            PACKET.encode_utf16().flat_map(u16::to_be_bytes).collect(),
+            PACKET.encode_utf16().flat_map(u16::to_le_bytes).collect(),
+            PACKET
+                .chars()
+                .flat_map(|c| (c as u32).to_be_bytes())
+                .collect(),
+            PACKET
+                .chars()
+                .flat_map(|c| (c as u32).to_le_bytes())
+                .collect(),
+        ];
+        for bytes in encodings {
+            let meta = XmpMeta::parse_bytes(&bytes).unwrap();
+            assert_eq!(title(&meta), "Caf\u{E9} \u{1F4F7} \u{6771}\u{4EAC}");
+        }
+    }
+
+    #[test]
+    fn parse_reader() {
+        let bytes: Vec<u8> = PACKET.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let meta = XmpMeta::parse_reader(std::io::Cursor::new(bytes)).unwrap();
+        assert!(meta.semantically_eq(&XmpMeta::parse(PACKET).unwrap()));
+    }
+
+    #[test]
+    fn parse_bytes_invalid() {
+        assert!(matches!(
+            XmpMeta::parse_bytes(b""),
+            Err(XmpError::ParseError(_))
+        ));
+        assert!(matches!(
+            XmpMeta::parse_bytes(b"not xml"),
+            Err(XmpError::ParseError(_))
+        ));
+        // Odd number of UTF-16 bytes
+        let mut bytes: Vec<u8> = PACKET.encode_utf16().flat_map(u16::to_be_bytes).collect();
+        bytes.push(0);
+        assert!(XmpMeta::parse_bytes(&bytes).is_err());
+    }
+}
+
 mod serialize {
     use xmpkit::{XmpMeta, XmpValue};
 